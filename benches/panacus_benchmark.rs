@@ -45,10 +45,30 @@ fn benchmark_graph_broker_hist_node(c: &mut Criterion) {
     });
 }
 
+// Exercises the `parse_gfa_paths_walks*`/`update_tables*` hot path (node + bp counting, no
+// subsetting) that chunk18-4 moved onto `FxHashMap`/`ShardedMap` instead of the default
+// SipHash-keyed `HashMap`. `chrM.pan...gfa` is the only fixture shipped in this tree; re-point
+// `gfa_file` at a multi-megabase pangenome GFA to see the hashing change actually move the
+// needle -- on a graph this small the fixed per-iteration overhead dominates.
+fn benchmark_graph_broker_hist_node_bp(c: &mut Criterion) {
+    let gfa_file = "./benches/chrM.pan.fa.6626ff2.4030258.6a1ecc2.smooth.gfa";
+    let input_requirements = HashSet::from([
+        InputRequirement::Hist,
+        InputRequirement::Graph(gfa_file.to_string()),
+        InputRequirement::Node,
+        InputRequirement::Bp,
+        InputRequirement::PathLens,
+    ]);
+    c.bench_function("graph_broker_hist_node_bp", |b| {
+        b.iter(|| GraphBroker::from_gfa(black_box(&input_requirements)))
+    });
+}
+
 criterion_group!(
     benches,
     benchmark_graph_broker_hist,
     benchmark_graph_broker_hist_finish,
-    benchmark_graph_broker_hist_node
+    benchmark_graph_broker_hist_node,
+    benchmark_graph_broker_hist_node_bp
 );
 criterion_main!(benches);
@@ -1,30 +1,154 @@
+pub mod components;
+pub mod counts;
+pub mod coverage_line;
+pub mod cycles;
+pub mod export;
+pub mod geodesic;
 pub mod growth;
 pub mod hist;
 pub mod info;
 pub mod node_distribution;
 pub mod ordered_histgrowth;
+pub mod similarity;
+pub mod superbubbles;
 pub mod table;
+pub mod windowed_similarity;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
 
 use crate::{
     analysis_parameter::AnalysisParameter, graph_broker::GraphBroker, html_report::AnalysisSection,
+    progress::Progress,
 };
 
-pub trait Analysis {
+/// `Send + Sync` so a batch of independent analyses between two `Task::GraphStateChange`
+/// boundaries can run concurrently via `rayon` in `execute_pipeline` -- each analysis only reads
+/// the shared `GraphBroker` through `gb: Option<&GraphBroker>` below, never mutates it, so
+/// running several of them at once over the same borrow is sound. `&mut self` on the methods
+/// below is per-analysis (each boxed `dyn Analysis` is only ever touched by one thread), not a
+/// borrow of anything shared.
+pub trait Analysis: Send + Sync {
     fn generate_table(&mut self, gb: Option<&GraphBroker>) -> anyhow::Result<String>;
+    /// `progress` is the handle `run_cli` builds from `--progress`/`--quiet`/`--json` (see the
+    /// `progress` module); implementations should announce themselves via `progress.stage(..)`
+    /// before doing real work and are otherwise free to ignore it -- it's a no-op handle unless
+    /// the user actually asked for progress output.
     fn generate_report_section(
         &mut self,
         gb: Option<&GraphBroker>,
+        progress: Option<&Progress>,
     ) -> anyhow::Result<Vec<AnalysisSection>>;
     fn get_graph_requirements(&self) -> HashSet<InputRequirement>;
     fn get_type(&self) -> String;
+
+    /// Whether this analysis can fold incremental `(node/edge id, bp length, path id)` batches
+    /// as they're produced, instead of requiring the full dense `Node`/`Edge`/`Bp` coverage
+    /// matrix to be materialized up front. Defaults to `false`, matching every built-in
+    /// analysis today -- `get_graph_requirements`'s `Node`/`Edge`/`Bp` entries are still always
+    /// collected by building the complete [`ItemTable`](crate::graph_broker::ItemTable)/
+    /// [`IntervalContainer`](crate::util::IntervalContainer) up front, since the requirement
+    /// collectors have no bounded-memory/batched emission path yet. An analysis whose online
+    /// accumulator (e.g. a running histogram or growth curve) doesn't need random access back
+    /// into earlier paths can override this to `true` once such a path exists, so
+    /// out-of-core-sized pangenomes don't force the whole coverage table into RAM just to
+    /// answer a question that only ever needed a single running fold.
+    fn accepts_streaming(&self) -> bool {
+        false
+    }
+
+    /// Serializes this analysis' report sections (subcommand name, parameters, thresholds,
+    /// group mapping and the full count/growth matrices already carried by `AnalysisSection`)
+    /// to a stable, nested JSON document, for downstream pipelines that want structured output
+    /// instead of parsing the tsv table. Backed by `generate_report_section` rather than a
+    /// separate serialization path, so every analysis gets this for free.
+    fn write_json(&mut self, gb: Option<&GraphBroker>) -> anyhow::Result<String> {
+        let sections = self.generate_report_section(gb, None)?;
+        Ok(serde_json::to_string_pretty(&sections)?)
+    }
 }
 
 pub trait ConstructibleAnalysis: Analysis {
     fn from_parameter(parameter: AnalysisParameter) -> Self;
 }
 
+/// A backend registered under a unique name, so `AnalysisParameter::Custom { name, params }`
+/// can construct an analysis without `AnalysisParameter::into_tasks` having to know about it at
+/// compile time. `ConstructibleAnalysis::from_parameter` returns `Self`, so it isn't
+/// object-safe on its own -- a factory instead deserializes `params` into whatever
+/// `AnalysisParameter` shape the backend expects and returns the already-boxed `dyn Analysis`,
+/// exactly what `get_analysis_task!` does for the built-ins below.
+pub type AnalysisFactory =
+    Box<dyn Fn(serde_json::Value) -> anyhow::Result<Box<dyn Analysis>> + Send + Sync>;
+
+static REGISTRY: Lazy<Mutex<HashMap<String, AnalysisFactory>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Registers `factory` under `name`, overwriting any previous registration of the same name.
+/// Third parties call this at startup; [`register_builtin_analyses`] does the same for the
+/// built-in analyses, to prove the mechanism isn't special-cased for them.
+pub fn register_analysis(name: impl Into<String>, factory: AnalysisFactory) {
+    REGISTRY.lock().unwrap().insert(name.into(), factory);
+}
+
+/// Looks up `name` in the registry and hands it `params` to deserialize and construct.
+/// Used by `AnalysisParameter::Custom`'s `into_tasks` arm in place of a hardcoded match arm.
+pub fn construct_custom_analysis(
+    name: &str,
+    params: serde_json::Value,
+) -> anyhow::Result<Box<dyn Analysis>> {
+    let registry = REGISTRY.lock().unwrap();
+    let factory = registry
+        .get(name)
+        .ok_or_else(|| anyhow::anyhow!("no analysis registered under the name '{name}'"))?;
+    factory(params)
+}
+
+/// Wraps a `ConstructibleAnalysis` as an `AnalysisFactory`. `AnalysisParameter` already derives
+/// `Deserialize`, so `params` (expected to be the externally-tagged `{"<Variant>": {...}}` shape
+/// serde gives that enum) round-trips straight into it, the same value `T::from_parameter` would
+/// otherwise receive from a `get_analysis_task!` call site.
+fn builtin_factory<T: ConstructibleAnalysis + 'static>() -> AnalysisFactory {
+    Box::new(|params: serde_json::Value| {
+        let parameter: AnalysisParameter = serde_json::from_value(params)?;
+        Ok(Box::new(T::from_parameter(parameter)) as Box<dyn Analysis>)
+    })
+}
+
+/// Registers every built-in analysis through [`register_analysis`], the same entry point a
+/// third-party `Custom` backend would use. Called once before the first run file is converted
+/// to tasks (see `AnalysisRun::convert_to_tasks`).
+pub fn register_builtin_analyses() {
+    register_analysis("hist", builtin_factory::<hist::Hist>());
+    register_analysis("growth", builtin_factory::<growth::Growth>());
+    register_analysis(
+        "node_distribution",
+        builtin_factory::<node_distribution::NodeDistribution>(),
+    );
+    register_analysis("info", builtin_factory::<info::Info>());
+    register_analysis(
+        "ordered_growth",
+        builtin_factory::<ordered_histgrowth::OrderedHistgrowth>(),
+    );
+    register_analysis(
+        "coverage_line",
+        builtin_factory::<coverage_line::CoverageLine>(),
+    );
+    register_analysis("similarity", builtin_factory::<similarity::Similarity>());
+    register_analysis("cycles", builtin_factory::<cycles::Cycles>());
+    register_analysis(
+        "superbubbles",
+        builtin_factory::<superbubbles::Superbubbles>(),
+    );
+    register_analysis("components", builtin_factory::<components::Components>());
+    register_analysis("geodesic", builtin_factory::<geodesic::Geodesic>());
+    register_analysis("export", builtin_factory::<export::Export>());
+    register_analysis("table", builtin_factory::<table::Table>());
+    register_analysis("counts", builtin_factory::<counts::Count>());
+}
+
 #[derive(PartialEq, Eq, Debug, Clone, Hash)]
 pub enum InputRequirement {
     Node,
@@ -33,5 +157,9 @@ pub enum InputRequirement {
     PathLens,
     Hist,
     AbacusByGroup,
+    Cycles,
+    Kmer,
+    Minimizer,
+    Branch,
     Graph(String),
 }
@@ -0,0 +1,213 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::graph_broker::GraphBroker;
+use crate::{
+    analyses::InputRequirement, analysis_parameter::AnalysisParameter, html_report::ReportItem,
+    io::write_metadata_comments,
+};
+
+use super::{Analysis, AnalysisSection, ConstructibleAnalysis};
+
+/// A disjoint-set forest over node ids, with union-by-rank and path compression.
+struct UnionFind {
+    parent: HashMap<usize, usize>,
+    rank: HashMap<usize, usize>,
+}
+
+impl UnionFind {
+    fn new(nodes: impl IntoIterator<Item = usize>) -> Self {
+        let mut parent = HashMap::new();
+        let mut rank = HashMap::new();
+        for n in nodes {
+            parent.insert(n, n);
+            rank.insert(n, 0);
+        }
+        Self { parent, rank }
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        let p = self.parent[&x];
+        if p == x {
+            return x;
+        }
+        let root = self.find(p);
+        self.parent.insert(x, root);
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let ra = self.find(a);
+        let rb = self.find(b);
+        if ra == rb {
+            return;
+        }
+        let (lo, hi) = if self.rank[&ra] < self.rank[&rb] {
+            (ra, rb)
+        } else {
+            (rb, ra)
+        };
+        self.parent.insert(lo, hi);
+        if self.rank[&lo] == self.rank[&hi] {
+            *self.rank.get_mut(&hi).unwrap() += 1;
+        }
+    }
+}
+
+struct ComponentsReport {
+    num_components: usize,
+    // component size -> number of components of that size
+    size_hist: BTreeMap<usize, usize>,
+    largest_node_fraction: f64,
+    largest_bp_fraction: f64,
+}
+
+/// Connected-component / fragmentation report over the node graph reconstructed from the
+/// canonicalized `Edge` set.
+///
+/// `GraphBroker` does not expose which nodes each group touches, only the group -> run-length
+/// metadata returned by `get_path_lens`/`get_groups`, so unlike the global component report
+/// below, a per-group fragmentation breakdown can't be built from the currently available
+/// accessors; this analysis reports only the global component-size distribution.
+pub struct Components {
+    parameter: AnalysisParameter,
+    report: Option<ComponentsReport>,
+}
+
+impl Analysis for Components {
+    fn generate_table(&mut self, gb: Option<&GraphBroker>) -> anyhow::Result<String> {
+        if self.report.is_none() {
+            self.set_report(gb);
+        }
+        let report = self.report.as_ref().unwrap();
+        let mut text = write_metadata_comments()?;
+        text.push_str(&format!("# components\t{}\n", report.num_components));
+        text.push_str(&format!(
+            "# largest_component_node_fraction\t{:.6}\n",
+            report.largest_node_fraction
+        ));
+        text.push_str(&format!(
+            "# largest_component_bp_fraction\t{:.6}\n",
+            report.largest_bp_fraction
+        ));
+        text.push_str("size\tcount\n");
+        for (size, count) in &report.size_hist {
+            text.push_str(&format!("{}\t{}\n", size, count));
+        }
+        Ok(text)
+    }
+
+    fn get_type(&self) -> String {
+        "Components".to_string()
+    }
+
+    fn get_graph_requirements(&self) -> HashSet<InputRequirement> {
+        HashSet::from([InputRequirement::Node, InputRequirement::Edge, InputRequirement::Bp])
+    }
+
+    fn generate_report_section(
+        &mut self,
+        gb: Option<&GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
+    ) -> anyhow::Result<Vec<AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
+        if self.report.is_none() {
+            self.set_report(gb);
+        }
+        if gb.is_none() {
+            panic!("Components analysis needs a graph")
+        }
+        let gb = gb.unwrap();
+        let report = self.report.as_ref().unwrap();
+        let id_prefix = format!(
+            "components-{}",
+            self.get_run_name(gb)
+                .to_lowercase()
+                .replace(&[' ', '|', '\\'], "-")
+        );
+        let header = vec!["size".to_string(), "count".to_string()];
+        let values = report
+            .size_hist
+            .iter()
+            .map(|(size, count)| vec![size.to_string(), count.to_string()])
+            .collect();
+        let countable = report.num_components.to_string();
+        let tabs = vec![AnalysisSection {
+            id: id_prefix.clone(),
+            analysis: "Components".to_string(),
+            table: Some(self.generate_table(Some(gb))?),
+            run_name: self.get_run_name(gb),
+            countable,
+            items: vec![ReportItem::Table {
+                id: id_prefix,
+                header,
+                values,
+            }],
+        }];
+        Ok(tabs)
+    }
+}
+
+impl ConstructibleAnalysis for Components {
+    fn from_parameter(parameter: AnalysisParameter) -> Self {
+        Self {
+            parameter,
+            report: None,
+        }
+    }
+}
+
+impl Components {
+    fn set_report(&mut self, gb: Option<&GraphBroker>) {
+        let gb = gb.as_ref().unwrap();
+        if !matches!(self.parameter, AnalysisParameter::Components { .. }) {
+            panic!("Components analysis needs Components parameter");
+        }
+
+        let node_lens = gb.get_node_lens();
+        let nodes: Vec<usize> = gb.get_nodes().iter().map(|n| n.0 as usize).collect();
+        let mut uf = UnionFind::new(nodes.iter().copied());
+        for e in gb.get_edges().keys() {
+            uf.union(e.0 .0 as usize, e.2 .0 as usize);
+        }
+
+        let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &n in &nodes {
+            let root = uf.find(n);
+            members.entry(root).or_default().push(n);
+        }
+
+        let mut size_hist: BTreeMap<usize, usize> = BTreeMap::new();
+        for comp in members.values() {
+            *size_hist.entry(comp.len()).or_insert(0) += 1;
+        }
+
+        let total_nodes = nodes.len();
+        let total_bp: u64 = nodes.iter().map(|&n| node_lens[n] as u64).sum();
+        let largest = members.values().max_by_key(|c| c.len());
+        let (largest_nodes, largest_bp) = match largest {
+            Some(c) => (c.len(), c.iter().map(|&n| node_lens[n] as u64).sum::<u64>()),
+            None => (0, 0),
+        };
+
+        self.report = Some(ComponentsReport {
+            num_components: members.len(),
+            size_hist,
+            largest_node_fraction: if total_nodes > 0 {
+                largest_nodes as f64 / total_nodes as f64
+            } else {
+                0.0
+            },
+            largest_bp_fraction: if total_bp > 0 {
+                largest_bp as f64 / total_bp as f64
+            } else {
+                0.0
+            },
+        });
+    }
+
+    fn get_run_name(&self, gb: &GraphBroker) -> String {
+        format!("{}-components", gb.get_run_name())
+    }
+}
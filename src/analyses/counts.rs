@@ -1,8 +1,22 @@
 use std::collections::HashSet;
 
-use super::{Analysis, ConstructibleAnalysis};
+use serde::{Deserialize, Serialize};
 
-struct Count {}
+use crate::{analysis_parameter::AnalysisParameter, util::CountType};
+
+use super::{Analysis, ConstructibleAnalysis, InputRequirement};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum CountsHistogram {
+    // divide the observed coverage range into `bins` equal-width buckets
+    EqualWidth { bins: usize },
+    // explicit, comma-separated list of breakpoints delimiting the buckets
+    Breakpoints(Vec<usize>),
+}
+
+pub struct Count {
+    parameter: AnalysisParameter,
+}
 
 impl Analysis for Count {
     fn get_type(&self) -> String {
@@ -13,16 +27,79 @@ impl Analysis for Count {
         &mut self,
         gb: Option<&crate::graph_broker::GraphBroker>,
     ) -> anyhow::Result<String> {
-        Ok(String::new())
+        let gb = gb.expect("Counts analysis needs a graph");
+        let coverage = &gb.get_abacus_by_total(CountType::Node).countable[1..];
+        let lens = &gb.get_node_lens()[1..];
+
+        if let AnalysisParameter::Counts {
+            histogram: Some(histogram),
+            ..
+        } = &self.parameter
+        {
+            let buckets = Self::make_buckets(coverage, histogram);
+            let mut header_cols = vec![
+                vec!["panacus".to_string(), "bucket".to_string()],
+                vec!["count".to_string(), "node".to_string()],
+                vec!["count".to_string(), "bp_sum".to_string()],
+                vec!["count".to_string(), "bp_mean".to_string()],
+            ];
+            let mut res = String::new();
+            res.push_str(&format!(
+                "# {}\n",
+                std::env::args().collect::<Vec<String>>().join(" ")
+            ));
+            res.push_str(&format!(
+                "{}\t{}\t{}\t{}\n",
+                header_cols[0][0], header_cols[1][0], header_cols[2][0], header_cols[3][0]
+            ));
+            res.push_str(&format!(
+                "{}\t{}\t{}\t{}\n",
+                header_cols[0][1], header_cols[1][1], header_cols[2][1], header_cols[3][1]
+            ));
+            for (lo, hi) in buckets {
+                let (n, bp_sum) = coverage
+                    .iter()
+                    .zip(lens)
+                    .filter(|(c, _)| (**c as usize) >= lo && (**c as usize) < hi)
+                    .fold((0usize, 0u64), |(n, bp), (_, l)| (n + 1, bp + *l as u64));
+                let bp_mean = if n > 0 { bp_sum as f64 / n as f64 } else { 0.0 };
+                res.push_str(&format!("{}-{}\t{}\t{}\t{:.2}\n", lo, hi, n, bp_sum, bp_mean));
+            }
+            Ok(res)
+        } else {
+            let header_cols = vec![
+                vec!["panacus".to_string(), "node".to_string()],
+                vec!["count".to_string(), "coverage".to_string()],
+                vec!["count".to_string(), "length".to_string()],
+            ];
+            let mut res = String::new();
+            res.push_str(&format!(
+                "# {}\n",
+                std::env::args().collect::<Vec<String>>().join(" ")
+            ));
+            res.push_str(&format!(
+                "{}\t{}\t{}\n",
+                header_cols[0][0], header_cols[1][0], header_cols[2][0]
+            ));
+            res.push_str(&format!(
+                "{}\t{}\t{}\n",
+                header_cols[0][1], header_cols[1][1], header_cols[2][1]
+            ));
+            for (i, (c, l)) in coverage.iter().zip(lens).enumerate() {
+                res.push_str(&format!("{}\t{}\t{}\n", i + 1, c, l));
+            }
+            Ok(res)
+        }
     }
 
     fn get_graph_requirements(&self) -> std::collections::HashSet<super::InputRequirement> {
-        HashSet::new()
+        HashSet::from([InputRequirement::Node])
     }
 
     fn generate_report_section(
         &mut self,
-        gb: Option<&crate::graph_broker::GraphBroker>,
+        _gb: Option<&crate::graph_broker::GraphBroker>,
+        _progress: Option<&crate::progress::Progress>,
     ) -> anyhow::Result<Vec<crate::html_report::AnalysisSection>> {
         Ok(Vec::new())
     }
@@ -30,6 +107,38 @@ impl Analysis for Count {
 
 impl ConstructibleAnalysis for Count {
     fn from_parameter(parameter: crate::analysis_parameter::AnalysisParameter) -> Self {
-        Self {}
+        Self { parameter }
+    }
+}
+
+impl Count {
+    fn make_buckets(coverage: &[u32], histogram: &CountsHistogram) -> Vec<(usize, usize)> {
+        match histogram {
+            CountsHistogram::Breakpoints(points) => {
+                let mut points = points.clone();
+                points.sort_unstable();
+                let mut buckets = Vec::new();
+                let mut prev = 0;
+                for p in points {
+                    buckets.push((prev, p));
+                    prev = p;
+                }
+                buckets.push((prev, usize::MAX));
+                buckets
+            }
+            CountsHistogram::EqualWidth { bins } => {
+                let min = *coverage.iter().min().unwrap_or(&0) as usize;
+                let max = *coverage.iter().max().unwrap_or(&0) as usize;
+                let bins = (*bins).max(1);
+                let width = ((max - min) as f64 / bins as f64).ceil().max(1.0) as usize;
+                // buckets are half-open, so the last one is widened to take in `max` itself
+                (0..bins)
+                    .map(|i| {
+                        let hi = min + (i + 1) * width;
+                        (min + i * width, if i + 1 == bins { hi.max(max + 1) } else { hi })
+                    })
+                    .collect()
+            }
+        }
     }
 }
@@ -59,7 +59,11 @@ impl Analysis for CoverageLine {
     fn generate_report_section(
         &mut self,
         gb: Option<&crate::graph_broker::GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
     ) -> anyhow::Result<Vec<crate::html_report::AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
         if gb.is_none() {
             panic!("CoverageLine analysis needs a graph")
         }
@@ -131,6 +135,9 @@ impl CoverageLine {
             CountType::Bp => HashSet::from([InputRequirement::Bp]),
             CountType::Node => HashSet::from([InputRequirement::Node]),
             CountType::Edge => HashSet::from([InputRequirement::Edge]),
+            CountType::Kmer => HashSet::from([InputRequirement::Kmer]),
+            CountType::Minimizer => HashSet::from([InputRequirement::Minimizer]),
+            CountType::Branch => HashSet::from([InputRequirement::Branch]),
             CountType::All => HashSet::from([
                 InputRequirement::Bp,
                 InputRequirement::Node,
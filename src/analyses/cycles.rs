@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+use crate::graph_broker::GraphBroker;
+use crate::{
+    analyses::InputRequirement, analysis_parameter::AnalysisParameter, html_report::ReportItem,
+    io::write_metadata_comments,
+};
+
+use super::{Analysis, AnalysisSection, ConstructibleAnalysis};
+
+pub struct Cycles {
+    parameter: AnalysisParameter,
+    cycles: Option<Vec<Vec<usize>>>,
+}
+
+impl Analysis for Cycles {
+    fn generate_table(&mut self, gb: Option<&GraphBroker>) -> anyhow::Result<String> {
+        if self.cycles.is_none() {
+            self.set_cycles(gb);
+        }
+        let mut text = write_metadata_comments()?;
+        text.push_str("cycle\tsize\tnodes\n");
+        for (i, cycle) in self.cycles.as_ref().unwrap().iter().enumerate() {
+            text.push_str(&format!(
+                "{}\t{}\t{}\n",
+                i,
+                cycle.len(),
+                cycle
+                    .iter()
+                    .map(|n| n.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ));
+        }
+        Ok(text)
+    }
+
+    fn get_type(&self) -> String {
+        "Cycles".to_string()
+    }
+
+    fn get_graph_requirements(&self) -> HashSet<InputRequirement> {
+        HashSet::from([InputRequirement::Edge, InputRequirement::Cycles])
+    }
+
+    fn generate_report_section(
+        &mut self,
+        gb: Option<&GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
+    ) -> anyhow::Result<Vec<AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
+        if self.cycles.is_none() {
+            self.set_cycles(gb);
+        }
+        if gb.is_none() {
+            panic!("Cycles analysis needs a graph")
+        }
+        let gb = gb.unwrap();
+        let cycles = self.cycles.as_ref().unwrap();
+        let id_prefix = format!(
+            "cycles-{}",
+            self.get_run_name(gb)
+                .to_lowercase()
+                .replace(&[' ', '|', '\\'], "-")
+        );
+        let header = vec![
+            "cycle".to_string(),
+            "size".to_string(),
+            "nodes".to_string(),
+        ];
+        let values = cycles
+            .iter()
+            .enumerate()
+            .map(|(i, cycle)| {
+                vec![
+                    i.to_string(),
+                    cycle.len().to_string(),
+                    cycle
+                        .iter()
+                        .map(|n| n.to_string())
+                        .collect::<Vec<_>>()
+                        .join(","),
+                ]
+            })
+            .collect();
+        let tabs = vec![AnalysisSection {
+            id: id_prefix.clone(),
+            analysis: "Cycles".to_string(),
+            table: Some(self.generate_table(Some(gb))?),
+            run_name: self.get_run_name(gb),
+            countable: cycles.len().to_string(),
+            items: vec![ReportItem::Table {
+                id: id_prefix,
+                header,
+                values,
+            }],
+        }];
+        Ok(tabs)
+    }
+}
+
+impl ConstructibleAnalysis for Cycles {
+    fn from_parameter(parameter: AnalysisParameter) -> Self {
+        Self {
+            parameter,
+            cycles: None,
+        }
+    }
+}
+
+impl Cycles {
+    fn set_cycles(&mut self, gb: Option<&GraphBroker>) {
+        let gb = gb.as_ref().unwrap();
+        let min_size = match self.parameter {
+            AnalysisParameter::Cycles { min_size } => min_size,
+            _ => panic!("Cycles analysis needs Cycles parameter"),
+        };
+        let cycles = gb
+            .get_cycles()
+            .iter()
+            .filter(|c| c.len() >= min_size)
+            .map(|c| c.iter().map(|id| id.0 as usize).collect::<Vec<_>>())
+            .collect();
+        self.cycles = Some(cycles);
+    }
+
+    fn get_run_name(&self, gb: &GraphBroker) -> String {
+        format!("{}-cycles", gb.get_run_name())
+    }
+}
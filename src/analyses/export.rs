@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+use std::io::BufWriter;
+
+use crate::graph_broker::{GraphBroker, ItemId};
+use crate::{analyses::InputRequirement, analysis_parameter::AnalysisParameter};
+
+use super::{Analysis, AnalysisSection, ConstructibleAnalysis};
+
+pub struct Export {
+    parameter: AnalysisParameter,
+}
+
+impl Analysis for Export {
+    fn generate_table(&mut self, gb: Option<&GraphBroker>) -> anyhow::Result<String> {
+        let gb = gb.expect("Export analysis needs a graph");
+        let (from, to) = match &self.parameter {
+            AnalysisParameter::Export { from, to } => (*from, *to),
+            _ => panic!("Export analysis needs Export parameter"),
+        };
+        let restrict_to = match (from, to) {
+            (Some(from), Some(to)) => {
+                let from = ItemId(from);
+                let to = ItemId(to);
+                if !gb.path_exists(from, to, false) {
+                    log::warn!("no path between node {} and node {}", from.0, to.0);
+                }
+                Some(gb.reachable_from(from, false))
+            }
+            (Some(from), None) => Some(gb.reachable_from(ItemId(from), false)),
+            _ => None,
+        };
+        let mut buf = BufWriter::new(Vec::new());
+        gb.write_dot(&mut buf, restrict_to.as_ref())?;
+        Ok(String::from_utf8(buf.into_inner()?)?)
+    }
+
+    fn get_type(&self) -> String {
+        "Export".to_string()
+    }
+
+    fn get_graph_requirements(&self) -> HashSet<InputRequirement> {
+        HashSet::from([InputRequirement::Edge])
+    }
+
+    fn generate_report_section(
+        &mut self,
+        gb: Option<&GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
+    ) -> anyhow::Result<Vec<AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
+        let gb = gb.expect("Export analysis needs a graph");
+        let dot = self.generate_table(Some(gb))?;
+        let id = format!("export-{}", gb.get_run_name().to_lowercase().replace([' ', '|', '\\'], "-"));
+        Ok(vec![AnalysisSection {
+            id,
+            analysis: "Graph Export".to_string(),
+            table: Some(dot),
+            run_name: gb.get_run_name(),
+            countable: "dot".to_string(),
+            items: vec![],
+        }])
+    }
+}
+
+impl ConstructibleAnalysis for Export {
+    fn from_parameter(parameter: AnalysisParameter) -> Self {
+        Self { parameter }
+    }
+}
@@ -0,0 +1,209 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::graph_broker::{GraphBroker, Orientation};
+use crate::{
+    analyses::InputRequirement, analysis_parameter::AnalysisParameter, html_report::ReportItem,
+    io::write_metadata_comments,
+};
+
+use super::{Analysis, AnalysisSection, ConstructibleAnalysis};
+
+// a traversal state: the node id together with the strand it is entered on, since a GFA edge
+// only connects specific node sides and `GraphBroker` exposes no walk-level API to resolve a
+// `PathSegment` query down to such a state -- `from`/`to` are therefore plain node ids, the same
+// convention `Export`'s `from`/`to` parameters already use
+type Side = (usize, Orientation);
+
+struct GeodesicResult {
+    // `None` when `to` is unreachable from `from`
+    distance: Option<u64>,
+    hops: usize,
+    walk: Vec<usize>,
+}
+
+/// Minimum base-pair-weighted distance between two nodes, computed with Dijkstra over the node
+/// adjacency reconstructed from the canonicalized `Edge` set.
+///
+/// Each `Edge(u, ou, v, ov)` connects a specific side of `u` to a specific side of `v`; to
+/// respect that instead of collapsing the graph to a plain undirected node graph (as
+/// [`super::superbubbles::Superbubbles`] and [`super::components::Components`] do), traversal
+/// state here is the pair `(node, strand entered on)` -- both the `(u, ou) -> (v, ov)` arc and
+/// its flipped counterpart `(v, ov.flip()) -> (u, ou.flip())` are added, since an edge can be
+/// walked in either direction. The search starts from both strands of `from` and stops as soon
+/// as either strand of `to` is settled.
+pub struct Geodesic {
+    parameter: AnalysisParameter,
+    result: Option<GeodesicResult>,
+}
+
+impl Analysis for Geodesic {
+    fn generate_table(&mut self, gb: Option<&GraphBroker>) -> anyhow::Result<String> {
+        if self.result.is_none() {
+            self.set_result(gb);
+        }
+        let result = self.result.as_ref().unwrap();
+        let mut text = write_metadata_comments()?;
+        text.push_str(&format!(
+            "distance\t{}\n",
+            result
+                .distance
+                .map(|d| d.to_string())
+                .unwrap_or_else(|| "unreachable".to_string())
+        ));
+        text.push_str(&format!("hops\t{}\n", result.hops));
+        text.push_str(&format!(
+            "walk\t{}\n",
+            result
+                .walk
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        ));
+        Ok(text)
+    }
+
+    fn get_type(&self) -> String {
+        "Geodesic".to_string()
+    }
+
+    fn get_graph_requirements(&self) -> HashSet<InputRequirement> {
+        HashSet::from([InputRequirement::Node, InputRequirement::Edge])
+    }
+
+    fn generate_report_section(
+        &mut self,
+        gb: Option<&GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
+    ) -> anyhow::Result<Vec<AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
+        if self.result.is_none() {
+            self.set_result(gb);
+        }
+        if gb.is_none() {
+            panic!("Geodesic analysis needs a graph")
+        }
+        let gb = gb.unwrap();
+        let result = self.result.as_ref().unwrap();
+        let id_prefix = format!(
+            "geodesic-{}",
+            gb.get_run_name()
+                .to_lowercase()
+                .replace(&[' ', '|', '\\'], "-")
+        );
+        let header = vec!["distance".to_string(), "hops".to_string(), "walk".to_string()];
+        let values = vec![vec![
+            result
+                .distance
+                .map(|d| d.to_string())
+                .unwrap_or_else(|| "unreachable".to_string()),
+            result.hops.to_string(),
+            result
+                .walk
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        ]];
+        let countable = result
+            .distance
+            .map(|d| d.to_string())
+            .unwrap_or_else(|| "unreachable".to_string());
+        Ok(vec![AnalysisSection {
+            id: id_prefix.clone(),
+            analysis: "Geodesic".to_string(),
+            table: Some(self.generate_table(Some(gb))?),
+            run_name: gb.get_run_name(),
+            countable,
+            items: vec![ReportItem::Table {
+                id: id_prefix,
+                header,
+                values,
+            }],
+        }])
+    }
+}
+
+impl ConstructibleAnalysis for Geodesic {
+    fn from_parameter(parameter: AnalysisParameter) -> Self {
+        Self {
+            parameter,
+            result: None,
+        }
+    }
+}
+
+impl Geodesic {
+    fn set_result(&mut self, gb: Option<&GraphBroker>) {
+        let gb = gb.as_ref().unwrap();
+        let (from, to) = match self.parameter {
+            AnalysisParameter::Geodesic { from, to } => (from as usize, to as usize),
+            _ => panic!("Geodesic analysis needs Geodesic parameter"),
+        };
+
+        let node_lens = gb.get_node_lens();
+        let mut adj: HashMap<Side, Vec<Side>> = HashMap::new();
+        for e in gb.get_edges().keys() {
+            let u = e.0 .0 as usize;
+            let ou = e.1;
+            let v = e.2 .0 as usize;
+            let ov = e.3;
+            adj.entry((u, ou)).or_default().push((v, ov));
+            adj.entry((v, ov.flip())).or_default().push((u, ou.flip()));
+        }
+
+        let mut dist: HashMap<Side, u64> = HashMap::new();
+        let mut prev: HashMap<Side, Side> = HashMap::new();
+        let mut heap: BinaryHeap<Reverse<(u64, Side)>> = BinaryHeap::new();
+        for start in [(from, Orientation::Forward), (from, Orientation::Backward)] {
+            dist.insert(start, 0);
+            heap.push(Reverse((0, start)));
+        }
+
+        let mut settled: Option<Side> = None;
+        while let Some(Reverse((d, side))) = heap.pop() {
+            if side.0 == to {
+                settled = Some(side);
+                break;
+            }
+            if dist.get(&side).is_some_and(|&best| d > best) {
+                continue;
+            }
+            for &next in adj.get(&side).into_iter().flatten() {
+                let weight = *node_lens.get(next.0).unwrap_or(&0) as u64;
+                let candidate = d + weight;
+                if dist.get(&next).is_none_or(|&best| candidate < best) {
+                    dist.insert(next, candidate);
+                    prev.insert(next, side);
+                    heap.push(Reverse((candidate, next)));
+                }
+            }
+        }
+
+        let result = match settled {
+            Some(end) => {
+                let mut walk = vec![end.0];
+                let mut cur = end;
+                while let Some(&p) = prev.get(&cur) {
+                    walk.push(p.0);
+                    cur = p;
+                }
+                walk.reverse();
+                GeodesicResult {
+                    distance: Some(dist[&end]),
+                    hops: walk.len().saturating_sub(1),
+                    walk,
+                }
+            }
+            None => GeodesicResult {
+                distance: None,
+                hops: 0,
+                walk: Vec::new(),
+            },
+        };
+        self.result = Some(result);
+    }
+}
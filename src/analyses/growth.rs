@@ -6,14 +6,93 @@ use rayon::iter::{ParallelBridge, ParallelIterator};
 use crate::analysis_parameter::AnalysisParameter;
 use crate::graph_broker::{GraphBroker, Hist, ThresholdContainer};
 use crate::html_report::ReportItem;
-use crate::{io::write_table, util::CountType};
+use crate::{
+    io::{write_table, write_table_json},
+    util::CountType,
+};
 
+use super::ordered_histgrowth::{bootstrap_growth_bands, GrowthBand};
 use super::{Analysis, AnalysisSection, ConstructibleAnalysis, InputRequirement};
 
 type Hists = Vec<Hist>;
 type Growths = Vec<(CountType, Vec<Vec<f64>>)>;
 type Comments = Vec<Vec<u8>>;
 
+#[derive(Debug, Clone, Copy)]
+pub struct OpennessFit {
+    pub alpha: f64,
+    pub kappa: f64,
+    pub r_squared: f64,
+}
+
+impl OpennessFit {
+    pub fn is_open(&self) -> bool {
+        self.alpha <= 1.0
+    }
+}
+
+// Fit delta_n(m) = kappa * m^(-alpha) via ordinary least-squares regression
+// of log(delta_n) on log(m) (Heaps/Tettelin power law).
+fn fit_openness(growth: &[f64]) -> Option<OpennessFit> {
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+    for m in 1..growth.len() {
+        let delta = growth[m] - growth[m - 1];
+        if delta > 0.0 {
+            xs.push((m as f64 + 1.0).ln());
+            ys.push(delta.ln());
+        }
+    }
+    if xs.len() < 2 {
+        return None;
+    }
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let mut cov_xy = 0.0;
+    let mut var_x = 0.0;
+    for (x, y) in xs.iter().zip(&ys) {
+        cov_xy += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+    }
+    if var_x == 0.0 {
+        return None;
+    }
+    let slope = cov_xy / var_x;
+    let intercept = mean_y - slope * mean_x;
+
+    let mut ss_res = 0.0;
+    let mut ss_tot = 0.0;
+    for (x, y) in xs.iter().zip(&ys) {
+        let pred = intercept + slope * x;
+        ss_res += (y - pred).powi(2);
+        ss_tot += (y - mean_y).powi(2);
+    }
+    let r_squared = if ss_tot == 0.0 {
+        1.0
+    } else {
+        1.0 - ss_res / ss_tot
+    };
+
+    Some(OpennessFit {
+        alpha: -slope,
+        kappa: intercept.exp(),
+        r_squared,
+    })
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, serde::Serialize, serde::Deserialize,
+)]
+pub enum ReportFormat {
+    #[default]
+    Table,
+    Summary,
+    Html,
+    Json,
+    Term,
+}
+
 pub struct Growth {
     parameter: AnalysisParameter,
     inner: Option<InnerGrowth>,
@@ -30,6 +109,20 @@ impl Analysis for Growth {
         log::info!("reporting hist table");
 
         self.set_inner(dm)?;
+        if let AnalysisParameter::Growth {
+            report_format: ReportFormat::Summary,
+            ..
+        } = &self.parameter
+        {
+            return self.generate_summary();
+        }
+        if let AnalysisParameter::Growth {
+            report_format: ReportFormat::Term,
+            ..
+        } = &self.parameter
+        {
+            return self.generate_term();
+        }
         let growths = &self.inner.as_ref().unwrap().growths;
         let hist_aux = &self.inner.as_ref().unwrap().hist_aux;
         let comments = &self.inner.as_ref().unwrap().comments;
@@ -43,6 +136,24 @@ impl Analysis for Growth {
             std::env::args().collect::<Vec<String>>().join(" ")
         ));
 
+        let openness = &self.inner.as_ref().unwrap().openness;
+        let bands = &self.inner.as_ref().unwrap().bands;
+        for (count, fits) in openness {
+            for (i, fit) in fits.iter().enumerate() {
+                if let Some(fit) = fit {
+                    res.push_str(&format!(
+                        "# openness {}[{}]: alpha={:.4} kappa={:.4} R^2={:.4} ({})\n",
+                        count,
+                        i,
+                        fit.alpha,
+                        fit.kappa,
+                        fit.r_squared,
+                        if fit.is_open() { "open" } else { "closed" }
+                    ));
+                }
+            }
+        }
+
         let mut header_cols = vec![vec![
             "panacus".to_string(),
             "count".to_string(),
@@ -76,7 +187,7 @@ impl Analysis for Growth {
             panic!("Growth needs growth parameter");
         }
 
-        for (count, g) in growths {
+        for ((count, g), (_, fits)) in growths.iter().zip(openness.iter()) {
             output_columns.extend(g.clone());
             let m = hist_aux.coverage.len();
             header_cols.extend(
@@ -89,6 +200,59 @@ impl Analysis for Growth {
                         vec![p.to_string(), t.to_string(), c.get_string(), q.get_string()]
                     }),
             );
+
+            // one broadcast column per fitted Heaps'-law parameter, alongside the growth
+            // curve it was fit from; NaN when a setting had too few usable points to fit
+            let n = g.first().map(|row| row.len()).unwrap_or(0);
+            for (i, fit) in fits.iter().enumerate() {
+                let (alpha, kappa, r_squared) = match fit {
+                    Some(f) => (f.alpha, f.kappa, f.r_squared),
+                    None => (f64::NAN, f64::NAN, f64::NAN),
+                };
+                for (label, value) in [
+                    ("openness_alpha", alpha),
+                    ("openness_kappa", kappa),
+                    ("openness_r_squared", r_squared),
+                ] {
+                    output_columns.push(vec![value; n]);
+                    header_cols.push(vec![
+                        label.to_string(),
+                        count.to_string(),
+                        hist_aux.coverage[i].get_string(),
+                        hist_aux.quorum[i].get_string(),
+                    ]);
+                }
+            }
+
+            // bootstrap confidence band, one lower/median/upper column per coverage/quorum
+            // pair, alongside the growth curve it bands; omitted entirely for a CountType that
+            // didn't get bands (bootstrap off, or skipped -- see `Growth::bootstrap_bands_for`)
+            if let Some(Some(count_bands)) =
+                bands.iter().find(|(c, _)| c == count).map(|(_, b)| b)
+            {
+                for (i, band) in count_bands.iter().enumerate() {
+                    for (label, values) in [
+                        ("bootstrap_lower", &band.lower),
+                        ("bootstrap_median", &band.median),
+                        ("bootstrap_upper", &band.upper),
+                    ] {
+                        output_columns.push(values.clone());
+                        header_cols.push(vec![
+                            label.to_string(),
+                            count.to_string(),
+                            hist_aux.coverage[i].get_string(),
+                            hist_aux.quorum[i].get_string(),
+                        ]);
+                    }
+                }
+            }
+        }
+        if let AnalysisParameter::Growth {
+            report_format: ReportFormat::Json,
+            ..
+        } = &self.parameter
+        {
+            return write_table_json(&header_cols, &output_columns);
         }
         res.push_str(&write_table(&header_cols, &output_columns)?);
         Ok(res)
@@ -97,7 +261,11 @@ impl Analysis for Growth {
     fn generate_report_section(
         &mut self,
         dm: Option<&crate::graph_broker::GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
     ) -> anyhow::Result<Vec<AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
         self.set_inner(dm)?;
         let hist_aux = &self.inner.as_ref().unwrap().hist_aux;
         let growth_labels = (0..hist_aux.coverage.len())
@@ -115,6 +283,8 @@ impl Analysis for Growth {
         let table = self.generate_table(dm)?;
         let table = format!("`{}`", &table);
         let growths = &self.inner.as_ref().unwrap().growths;
+        let openness = &self.inner.as_ref().unwrap().openness;
+        let bands = &self.inner.as_ref().unwrap().bands;
         let id_prefix = format!(
             "pan-growth-{}",
             self.get_run_name(dm.expect("Growth should be called with a graph"))
@@ -123,28 +293,66 @@ impl Analysis for Growth {
         );
         let growth_tabs = growths
             .iter()
-            .map(|(k, v)| AnalysisSection {
-                id: format!("{id_prefix}-{k}"),
-                analysis: "Pangenome Growth".to_string(),
-                run_name: self.get_run_name(dm.expect("Growth should be called with a graph")),
-                countable: k.to_string(),
-                table: Some(table.clone()),
-                items: vec![ReportItem::MultiBar {
+            .zip(openness.iter())
+            .map(|((k, v), (_, fits))| {
+                let mut names = growth_labels.clone();
+                let mut values: Vec<Vec<f64>> = v
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|el| if el.is_nan() { 0.0 } else { *el })
+                            .collect()
+                    })
+                    .collect();
+
+                // overlay the fitted Heaps'-law curve g_hat(m) = g(1) + sum_{k=2}^{m} kappa*k^-alpha
+                // as an extra series alongside the observed growth curve it was fit from
+                for (i, fit) in fits.iter().enumerate() {
+                    if let Some(fit) = fit {
+                        let n = v[i].len();
+                        let mut predicted = Vec::with_capacity(n);
+                        let mut acc = v[i].first().copied().unwrap_or(0.0);
+                        predicted.push(acc);
+                        for m in 2..=n {
+                            acc += fit.kappa * (m as f64).powf(-fit.alpha);
+                            predicted.push(acc);
+                        }
+                        names.push(format!("{} (fit)", growth_labels[i]));
+                        values.push(predicted);
+                    }
+                }
+
+                // bootstrap confidence bands, same three-series-per-curve convention as
+                // `ordered_histgrowth.rs`'s own report section
+                if let Some(Some(count_bands)) =
+                    bands.iter().find(|(c, _)| c == k).map(|(_, b)| b)
+                {
+                    for (label, band) in growth_labels.iter().zip(count_bands) {
+                        names.push(format!("{} (bootstrap median)", label));
+                        values.push(band.median.clone());
+                        names.push(format!("{} (bootstrap lower 2.5%)", label));
+                        values.push(band.lower.clone());
+                        names.push(format!("{} (bootstrap upper 97.5%)", label));
+                        values.push(band.upper.clone());
+                    }
+                }
+
+                AnalysisSection {
                     id: format!("{id_prefix}-{k}"),
-                    names: growth_labels.clone(),
-                    x_label: "taxa".to_string(),
-                    y_label: format!("#{}s", k),
-                    labels: (1..v[0].len()).map(|i| i.to_string()).collect(),
-                    values: v
-                        .iter()
-                        .map(|row| {
-                            row.iter()
-                                .map(|el| if el.is_nan() { 0.0 } else { *el })
-                                .collect()
-                        })
-                        .collect(),
-                    log_toggle: false,
-                }],
+                    analysis: "Pangenome Growth".to_string(),
+                    run_name: self.get_run_name(dm.expect("Growth should be called with a graph")),
+                    countable: k.to_string(),
+                    table: Some(table.clone()),
+                    items: vec![ReportItem::MultiBar {
+                        id: format!("{id_prefix}-{k}"),
+                        names,
+                        x_label: "taxa".to_string(),
+                        y_label: format!("#{}s", k),
+                        labels: (1..v[0].len()).map(|i| i.to_string()).collect(),
+                        values,
+                        log_toggle: false,
+                    }],
+                }
             })
             .collect();
         Ok(growth_tabs)
@@ -164,6 +372,23 @@ impl Analysis for Growth {
     // }
 
     fn get_graph_requirements(&self) -> HashSet<super::InputRequirement> {
+        if let AnalysisParameter::Growth {
+            hist_file,
+            bootstrap,
+            ..
+        } = &self.parameter
+        {
+            if hist_file.is_some() {
+                return HashSet::new();
+            }
+            let mut req = HashSet::from([InputRequirement::Hist]);
+            // the bootstrap bands need the live per-group presence matrix, only available via
+            // an AbacusByGroup (see the `bootstrap` field doc comment in `AnalysisParameter::Growth`)
+            if matches!(bootstrap, Some(n) if *n > 0) {
+                req.insert(InputRequirement::AbacusByGroup(CountType::Node));
+            }
+            return req;
+        }
         HashSet::from([InputRequirement::Hist])
     }
 }
@@ -187,40 +412,229 @@ impl Growth {
             return Ok(());
         }
         if let AnalysisParameter::Growth {
-            coverage, quorum, ..
+            coverage,
+            quorum,
+            hist_file,
+            bootstrap,
+            seed,
+            ..
         } = &self.parameter
         {
             let quorum = quorum.to_owned().unwrap_or("0".to_string());
             let coverage = coverage.to_owned().unwrap_or("1".to_string());
             let hist_aux = ThresholdContainer::parse_params(&quorum, &coverage)?;
 
-            if gb.is_none() {
-                unimplemented!("Have not implemented growth without graph");
-            } else {
-                let gb = gb.unwrap();
-                let growths: Growths = gb
-                    .get_hists()
+            let hists: Option<Hists> = match hist_file {
+                Some(file) => Some(Self::parse_hist_file(file)?),
+                None => None,
+            };
+
+            let growths: Growths = if let Some(hists) = &hists {
+                hists
+                    .iter()
+                    .par_bridge()
+                    .map(|h| (h.count, h.calc_all_growths(&hist_aux)))
+                    .collect()
+            } else if let Some(gb) = gb {
+                gb.get_hists()
                     .values()
                     .par_bridge()
                     .map(|h| (h.count, h.calc_all_growths(&hist_aux)))
-                    .collect();
-                self.inner = Some(InnerGrowth {
-                    growths,
-                    comments: Vec::new(),
-                    hist_aux,
-                    hists: None,
-                });
-            }
+                    .collect()
+            } else {
+                anyhow::bail!("Growth needs either a hist file or a graph");
+            };
+            let openness = growths
+                .iter()
+                .map(|(count, g)| (*count, g.iter().map(|row| fit_openness(row)).collect()))
+                .collect();
+
+            let bands = match bootstrap {
+                Some(r) if *r > 0 && hists.is_none() => {
+                    let gb = gb.expect("Growth needs a graph to compute bootstrap bands");
+                    growths
+                        .iter()
+                        .map(|(count, _)| (*count, Self::bootstrap_bands_for(gb, *count, &hist_aux, *r, seed.unwrap_or(0))))
+                        .collect()
+                }
+                Some(r) if *r > 0 => {
+                    log::warn!(
+                        "--bootstrap was requested together with --hist-file; skipping bootstrap \
+                         bands, since a previously exported histogram has lost per-genome identity"
+                    );
+                    Vec::new()
+                }
+                _ => Vec::new(),
+            };
+
+            self.inner = Some(InnerGrowth {
+                growths,
+                openness,
+                comments: Vec::new(),
+                hist_aux,
+                hists,
+                bands,
+            });
             Ok(())
         } else {
             panic!("Growth should always contain growth parameter")
         }
     }
+
+    // Bootstrap confidence bands reuse `OrderedGrowth`'s machinery, but that machinery reads a
+    // single `AbacusByGroup`, built for exactly one `CountType` at `GraphBroker` construction
+    // time (see `GraphBroker::get_count_type`). `Growth`, unlike `OrderedGrowth`, has no single
+    // `count_type` of its own -- it reports every `CountType` present in `gb.get_hists()` -- so
+    // bands can only be computed for whichever one the live `AbacusByGroup` actually matches;
+    // every other `CountType` is skipped with a warning rather than silently left out.
+    fn bootstrap_bands_for(
+        gb: &GraphBroker,
+        count: CountType,
+        hist_aux: &ThresholdContainer,
+        permutations: usize,
+        seed: u64,
+    ) -> Option<Vec<GrowthBand>> {
+        if count != gb.get_count_type() {
+            log::warn!(
+                "bootstrap bands requested for {}, but the graph's abacus was built for {}; \
+                 skipping bands for {}",
+                count,
+                gb.get_count_type(),
+                count
+            );
+            return None;
+        }
+        let weight_by_bp = count == CountType::Bp;
+        Some(bootstrap_growth_bands(
+            &gb.get_abacus_by_group().r,
+            &gb.get_abacus_by_group().c,
+            gb.get_node_lens(),
+            gb.get_group_count(),
+            hist_aux,
+            weight_by_bp,
+            permutations,
+            seed,
+        ))
+    }
+
+    // Render a compact, human-readable digest of the growth curves: total pangenome
+    // size, core size at quorum=1, singleton/private fraction, and (if available) the
+    // openness exponent, per CountType and per coverage/quorum column.
+    fn generate_summary(&self) -> anyhow::Result<String> {
+        use crate::util::Threshold;
+
+        let inner = self.inner.as_ref().unwrap();
+        let mut res = String::new();
+        for ((count, rows), (_, fits)) in inner.growths.iter().zip(&inner.openness) {
+            res.push_str(&format!("== {} ==\n", count));
+            for (i, row) in rows.iter().enumerate() {
+                let total = *row.last().unwrap_or(&f64::NAN);
+                let private = row.get(1).copied().unwrap_or(f64::NAN);
+                let private_fraction = if total > 0.0 { private / total } else { f64::NAN };
+                let is_core = matches!(inner.hist_aux.quorum[i], Threshold::Relative(q) if q >= 1.0)
+                    || matches!(inner.hist_aux.quorum[i], Threshold::Absolute(_));
+                let mut line = format!(
+                    "  coverage >= {}, quorum >= {}: total = {:.0}, private fraction = {:.4}",
+                    inner.hist_aux.coverage[i], inner.hist_aux.quorum[i], total, private_fraction
+                );
+                if is_core {
+                    line.push_str(&format!(", core = {:.0}", total));
+                }
+                if let Some(fit) = fits[i] {
+                    line.push_str(&format!(
+                        ", openness alpha = {:.4} ({})",
+                        fit.alpha,
+                        if fit.is_open() { "open" } else { "closed" }
+                    ));
+                }
+                res.push_str(&line);
+                res.push('\n');
+            }
+        }
+        Ok(res)
+    }
+
+    // renders each growth curve as an ASCII/Unicode bar chart (one row per coverage-level m),
+    // for quick inspection on a headless machine without opening the html report
+    fn generate_term(&self) -> anyhow::Result<String> {
+        let inner = self.inner.as_ref().unwrap();
+        let mut res = String::new();
+        for (count, rows) in &inner.growths {
+            for (i, row) in rows.iter().enumerate() {
+                res.push_str(&format!(
+                    "== {} growth (coverage >= {}, quorum >= {}) ==\n",
+                    count, inner.hist_aux.coverage[i], inner.hist_aux.quorum[i]
+                ));
+                let labels: Vec<String> = (1..=row.len()).map(|m| m.to_string()).collect();
+                let values: Vec<f64> = row.iter().map(|v| if v.is_nan() { 0.0 } else { *v }).collect();
+                res.push_str(&crate::io::render_term_bar_chart(&labels, &values));
+                res.push('\n');
+            }
+        }
+        Ok(res)
+    }
+
+    // Parse a panacus-format histogram table (the layout `Growth::generate_table` emits with
+    // `add_hist` set: a `panacus/count/coverage/quorum` header block and one `hist` column per
+    // CountType; all other columns are ignored) back into a list of `Hist`s, so growth curves
+    // can be recomputed from a previously exported table without re-reading the source graph.
+    fn parse_hist_file(file: &str) -> anyhow::Result<Hists> {
+        let content = std::fs::read_to_string(file)?;
+        let mut header_rows: Vec<Vec<&str>> = Vec::new();
+        let mut data_lines: Vec<&str> = Vec::new();
+        for line in content.lines() {
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+            let cols: Vec<&str> = line.split('\t').collect();
+            if cols[0] == "panacus" || cols[0] == "count" || cols[0] == "coverage"
+                || cols[0] == "quorum"
+            {
+                header_rows.push(cols);
+            } else {
+                data_lines.push(line);
+            }
+        }
+        let type_row = header_rows
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("hist file {} has no panacus header row", file))?;
+        let count_row = header_rows
+            .get(1)
+            .ok_or_else(|| anyhow::anyhow!("hist file {} has no count header row", file))?;
+
+        let hist_columns: Vec<(usize, CountType)> = (1..type_row.len())
+            .filter(|&i| type_row[i] == "hist")
+            .map(|i| {
+                use std::str::FromStr;
+                (i, CountType::from_str(count_row[i]).expect("valid count type in hist header"))
+            })
+            .collect();
+
+        let mut coverages: Vec<Vec<usize>> = vec![Vec::new(); hist_columns.len()];
+        for line in data_lines {
+            let cols: Vec<&str> = line.split('\t').collect();
+            for (j, (i, _)) in hist_columns.iter().enumerate() {
+                coverages[j].push(cols[*i].parse::<f64>()? as usize);
+            }
+        }
+
+        Ok(hist_columns
+            .into_iter()
+            .zip(coverages)
+            .map(|((_, count), coverage)| Hist { count, coverage })
+            .collect())
+    }
 }
 
 struct InnerGrowth {
     growths: Growths,
+    openness: Vec<(CountType, Vec<Option<OpennessFit>>)>,
     comments: Comments,
     hist_aux: ThresholdContainer,
     hists: Option<Hists>,
+    // one entry per CountType in `growths` when bootstrap runs, `None` (logged via `log::warn!`)
+    // for a CountType that isn't the one `GraphBroker`'s single `AbacusByGroup` was built for --
+    // see `Growth::bootstrap_bands_for`; empty when bootstrap wasn't requested, was given 0
+    // permutations, or was combined with a hist file
+    bands: Vec<(CountType, Option<Vec<GrowthBand>>)>,
 }
@@ -1,12 +1,13 @@
 use core::panic;
 use std::collections::HashSet;
 
-use crate::analysis_parameter::AnalysisParameter;
+use crate::analysis_parameter::{AnalysisParameter, BinScale, CumulativeDirection, NormalizeMode};
+use crate::analyses::growth::ReportFormat;
 use crate::graph_broker::GraphBroker;
 use crate::html_report::ReportItem;
 use crate::{
     analyses::InputRequirement,
-    io::write_table,
+    io::{write_table, write_table_json},
     util::{get_default_plot_downloads, CountType},
 };
 
@@ -30,9 +31,26 @@ impl Analysis for Hist {
             panic!("Hist analysis needs a graph")
         }
         let gb = gb.unwrap();
+        if let AnalysisParameter::Hist {
+            report_format: ReportFormat::Term,
+            ..
+        } = &self.parameter
+        {
+            let mut res = String::new();
+            for h in gb.get_hists().values() {
+                let (labels, binned) = self.binned_coverage(&h.coverage);
+                let values = self.transform_coverage(&binned);
+                res.push_str(&format!("== {} ==\n", h.count));
+                res.push_str(&crate::io::render_term_bar_chart(&labels, &values));
+                res.push('\n');
+            }
+            return Ok(res);
+        }
+
         let mut res = String::new();
         res.push_str(&crate::io::write_metadata_comments()?);
 
+        let normalize = self.normalize();
         let mut header_cols = vec![vec![
             "panacus".to_string(),
             "count".to_string(),
@@ -41,22 +59,44 @@ impl Analysis for Hist {
         ]];
         let mut output_columns = Vec::new();
         for h in gb.get_hists().values() {
-            output_columns.push(h.coverage.iter().map(|x| *x as f64).collect());
+            let (_, binned) = self.binned_coverage(&h.coverage);
+            output_columns.push(self.transform_coverage(&binned));
             header_cols.push(vec![
                 "hist".to_string(),
                 h.count.to_string(),
-                String::new(),
-                String::new(),
+                normalize.map(|m| m.to_string()).unwrap_or_default(),
+                self.cumulative().map(|d| d.to_string()).unwrap_or_default(),
             ])
         }
+        if let AnalysisParameter::Hist {
+            report_format: ReportFormat::Json,
+            ..
+        } = &self.parameter
+        {
+            return write_table_json(&header_cols, &output_columns);
+        }
         res.push_str(&write_table(&header_cols, &output_columns)?);
+
+        res.push_str("\n# core / soft-core / shell / cloud\n");
+        res.push_str("compartment\tcount\tvalue\n");
+        let num_groups = gb.get_group_count();
+        for h in gb.get_hists().values() {
+            let compartments = Self::classify_compartments(&h.coverage, num_groups, self.soft_core_cutoff());
+            for (label, value) in compartments.as_pairs() {
+                res.push_str(&format!("{}\t{}\t{}\n", label, h.count, value));
+            }
+        }
         Ok(res)
     }
 
     fn generate_report_section(
         &mut self,
         gb: Option<&crate::graph_broker::GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
     ) -> anyhow::Result<Vec<AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
         if gb.is_none() {
             panic!("Hist analysis needs a graph")
         }
@@ -69,26 +109,58 @@ impl Analysis for Hist {
                 .to_lowercase()
                 .replace(&[' ', '|', '\\'], "-")
         );
+        let num_groups = gb.get_group_count();
+        let soft_core_cutoff = self.soft_core_cutoff();
         let histogram_tabs = gb
             .get_hists()
             .iter()
-            .map(|(k, v)| AnalysisSection {
-                id: format!("{id_prefix}-{k}"),
-                analysis: "Coverage Histogram".to_string(),
-                table: Some(table.clone()),
-                run_name: self.get_run_name(gb),
-                run_id: self.get_run_id(gb),
-                countable: k.to_string(),
-                items: vec![ReportItem::Bar {
+            .map(|(k, v)| {
+                let (labels, binned) = self.binned_coverage(&v.coverage);
+                let compartments =
+                    Self::classify_compartments(&v.coverage, num_groups, soft_core_cutoff);
+                let (compartment_labels, compartment_values): (Vec<_>, Vec<_>) = compartments
+                    .as_pairs()
+                    .into_iter()
+                    .map(|(label, value)| (label.to_string(), value as f64))
+                    .unzip();
+                AnalysisSection {
                     id: format!("{id_prefix}-{k}"),
-                    name: gb.get_fname(),
-                    x_label: "taxa".to_string(),
-                    y_label: format!("#{}s", k),
-                    labels: (0..v.coverage.len()).map(|s| s.to_string()).collect(),
-                    values: v.coverage.iter().map(|c| *c as f64).collect(),
-                    log_toggle: true,
-                }],
-                plot_downloads: get_default_plot_downloads(),
+                    analysis: "Coverage Histogram".to_string(),
+                    table: Some(table.clone()),
+                    run_name: self.get_run_name(gb),
+                    run_id: self.get_run_id(gb),
+                    countable: k.to_string(),
+                    items: vec![
+                        ReportItem::Bar {
+                            id: format!("{id_prefix}-{k}"),
+                            name: gb.get_fname(),
+                            x_label: "taxa".to_string(),
+                            y_label: self
+                                .normalize()
+                                .map(|m| m.to_string())
+                                .unwrap_or(format!("#{}s", k)),
+                            labels,
+                            values: self.transform_coverage(&binned),
+                            log_toggle: true,
+                        },
+                        ReportItem::Rarefaction {
+                            id: format!("{id_prefix}-{k}-rarefaction"),
+                            name: gb.get_fname(),
+                            hist: v.coverage.clone(),
+                            num_samples: v.coverage.len().saturating_sub(1),
+                        },
+                        ReportItem::Bar {
+                            id: format!("{id_prefix}-{k}-compartments"),
+                            name: gb.get_fname(),
+                            x_label: "compartment".to_string(),
+                            y_label: format!("#{}s", k),
+                            labels: compartment_labels,
+                            values: compartment_values,
+                            log_toggle: true,
+                        },
+                    ],
+                    plot_downloads: get_default_plot_downloads(),
+                }
             })
             .collect::<Vec<_>>();
         Ok(histogram_tabs)
@@ -105,6 +177,60 @@ impl Analysis for Hist {
     }
 }
 
+// partitions the coverage levels `1..=g` into `bins` contiguous, non-empty ranges: equal-width
+// for `BinScale::Linear`, geometrically spaced for `BinScale::Log`. Every level falls in
+// exactly one bin and the final bin always ends at `g`.
+fn bin_edges(g: usize, bins: usize, scale: BinScale) -> Vec<(usize, usize)> {
+    if g == 0 {
+        return Vec::new();
+    }
+    let bins = bins.clamp(1, g);
+    let mut boundaries = vec![0usize; bins];
+    match scale {
+        BinScale::Linear => {
+            for (i, b) in boundaries.iter_mut().enumerate() {
+                *b = (g * i) / bins;
+            }
+        }
+        BinScale::Log => {
+            let ln_g = (g as f64).ln().max(0.0);
+            for (i, b) in boundaries.iter_mut().enumerate() {
+                let frac = i as f64 / bins as f64;
+                *b = (ln_g * frac).exp().round() as usize;
+            }
+        }
+    }
+
+    let mut edges = Vec::with_capacity(bins);
+    let mut lo = 1;
+    for (i, boundary) in boundaries.iter().enumerate().skip(1) {
+        let hi = (*boundary).max(lo);
+        edges.push((lo, hi));
+        lo = hi + 1;
+    }
+    edges.push((lo, g));
+    edges
+}
+
+#[derive(Default, Clone, Copy)]
+struct Compartments {
+    core: usize,
+    soft_core: usize,
+    shell: usize,
+    cloud: usize,
+}
+
+impl Compartments {
+    fn as_pairs(&self) -> [(&'static str, usize); 4] {
+        [
+            ("core", self.core),
+            ("soft-core", self.soft_core),
+            ("shell", self.shell),
+            ("cloud", self.cloud),
+        ]
+    }
+}
+
 impl ConstructibleAnalysis for Hist {
     fn from_parameter(parameter: AnalysisParameter) -> Self {
         Self { parameter }
@@ -117,6 +243,9 @@ impl Hist {
             CountType::Bp => HashSet::from([InputRequirement::Bp]),
             CountType::Node => HashSet::from([InputRequirement::Node]),
             CountType::Edge => HashSet::from([InputRequirement::Edge]),
+            CountType::Kmer => HashSet::from([InputRequirement::Kmer]),
+            CountType::Minimizer => HashSet::from([InputRequirement::Minimizer]),
+            CountType::Branch => HashSet::from([InputRequirement::Branch]),
             CountType::All => HashSet::from([
                 InputRequirement::Bp,
                 InputRequirement::Node,
@@ -132,4 +261,191 @@ impl Hist {
     fn get_run_id(&self, gb: &GraphBroker) -> String {
         format!("{}-hist", gb.get_run_id())
     }
+
+    fn normalize(&self) -> Option<NormalizeMode> {
+        match &self.parameter {
+            AnalysisParameter::Hist { normalize, .. } => *normalize,
+            _ => None,
+        }
+    }
+
+    // divides each coverage-level count by the column sum, so the emitted values are a
+    // fraction (or, for `NormalizeMode::Percentage`, a percentage) of the total count
+    fn normalized_coverage(&self, coverage: &[f64]) -> Vec<f64> {
+        match self.normalize() {
+            None => coverage.to_vec(),
+            Some(mode) => {
+                let sum: f64 = coverage.iter().sum();
+                let scale = match mode {
+                    NormalizeMode::Fraction => 1.0,
+                    NormalizeMode::Percentage => 100.0,
+                };
+                if sum == 0.0 {
+                    coverage.to_vec()
+                } else {
+                    coverage.iter().map(|v| v / sum * scale).collect()
+                }
+            }
+        }
+    }
+
+    fn cumulative(&self) -> Option<CumulativeDirection> {
+        match &self.parameter {
+            AnalysisParameter::Hist { cumulative, .. } => *cumulative,
+            _ => None,
+        }
+    }
+
+    // runs `normalized_coverage` first (so a combined normalize+cumulative curve sums fractions
+    // rather than raw counts, landing on 1.0/100% at its end regardless of graph size), then
+    // folds the result into a running sum in the requested direction; with no `--cumulative`
+    // this is exactly `normalized_coverage`
+    fn transform_coverage(&self, coverage: &[f64]) -> Vec<f64> {
+        let normalized = self.normalized_coverage(coverage);
+        match self.cumulative() {
+            None => normalized,
+            Some(CumulativeDirection::Ascending) => {
+                let mut sum = 0.0;
+                normalized
+                    .iter()
+                    .map(|v| {
+                        sum += v;
+                        sum
+                    })
+                    .collect()
+            }
+            Some(CumulativeDirection::Descending) => {
+                let mut sum = 0.0;
+                let mut res: Vec<f64> = normalized
+                    .iter()
+                    .rev()
+                    .map(|v| {
+                        sum += v;
+                        sum
+                    })
+                    .collect();
+                res.reverse();
+                res
+            }
+        }
+    }
+
+    fn soft_core_cutoff(&self) -> f64 {
+        match &self.parameter {
+            AnalysisParameter::Hist {
+                soft_core_cutoff, ..
+            } => *soft_core_cutoff,
+            _ => 0.95,
+        }
+    }
+
+    // buckets a coverage histogram (indexed by number of groups a countable is present in,
+    // index 0 unused) into the classic pangenome compartments: cloud (present in exactly one
+    // group), shell (more than one but below `soft_core_cutoff`), soft-core (at or above
+    // `soft_core_cutoff`, but not all groups), core (present in every group)
+    fn classify_compartments(
+        coverage: &[usize],
+        num_groups: usize,
+        soft_core_cutoff: f64,
+    ) -> Compartments {
+        let mut compartments = Compartments::default();
+        if num_groups == 0 {
+            return compartments;
+        }
+        let soft_core_threshold = soft_core_cutoff * num_groups as f64;
+        for (i, &count) in coverage.iter().enumerate().take(num_groups + 1).skip(1) {
+            if i == num_groups {
+                compartments.core += count;
+            } else if i == 1 {
+                compartments.cloud += count;
+            } else if i as f64 >= soft_core_threshold {
+                compartments.soft_core += count;
+            } else {
+                compartments.shell += count;
+            }
+        }
+        compartments
+    }
+
+    fn bins(&self) -> Option<(usize, BinScale)> {
+        match &self.parameter {
+            AnalysisParameter::Hist {
+                bins: Some(bins),
+                bin_scale,
+                ..
+            } => Some((*bins, *bin_scale)),
+            _ => None,
+        }
+    }
+
+    fn interval(&self) -> Option<(usize, Option<(usize, usize)>, usize)> {
+        match &self.parameter {
+            AnalysisParameter::Hist {
+                interval: Some(interval),
+                bounds,
+                min_bucket_count,
+                ..
+            } => Some((*interval, *bounds, *min_bucket_count)),
+            _ => None,
+        }
+    }
+
+    // aggregates `coverage` (one raw count per coverage level 1..=G, index 0 unused) into
+    // `self.bins()` contiguous buckets, returning each bucket's inclusive "lo-hi" label
+    // alongside its summed count; without `--bins`/`--hist-interval` every level gets its own
+    // row, as before
+    fn binned_coverage(&self, coverage: &[usize]) -> (Vec<String>, Vec<f64>) {
+        if let Some((interval, bounds, min_bucket_count)) = self.interval() {
+            let edges = interval_edges(coverage.len(), interval, bounds);
+            return edges
+                .iter()
+                .map(|(lo, hi)| (format!("{lo}-{hi}"), coverage[*lo..=*hi].iter().sum::<usize>()))
+                .filter(|(_, count)| *count >= min_bucket_count)
+                .map(|(label, count)| (label, count as f64))
+                .unzip();
+        }
+        match self.bins() {
+            None => (
+                (0..coverage.len()).map(|i| i.to_string()).collect(),
+                coverage.iter().map(|x| *x as f64).collect(),
+            ),
+            Some((bins, scale)) => {
+                let g = coverage.len().saturating_sub(1);
+                let edges = bin_edges(g, bins, scale);
+                let labels = edges.iter().map(|(lo, hi)| format!("{lo}-{hi}")).collect();
+                let values = edges
+                    .iter()
+                    .map(|(lo, hi)| coverage[*lo..=*hi].iter().sum::<usize>() as f64)
+                    .collect();
+                (labels, values)
+            }
+        }
+    }
+}
+
+// tiles `[min, max]` (clipped to the hist's observed range `1..=g`, defaulting to the whole
+// range when `bounds` is `None`) into fixed-width `interval`-sized buckets; unlike `bin_edges`,
+// levels outside `[min, max]` are dropped entirely rather than folded into the first/last
+// bucket, so a narrow `--hist-bounds` window reports only the levels the caller asked about.
+fn interval_edges(
+    coverage_len: usize,
+    interval: usize,
+    bounds: Option<(usize, usize)>,
+) -> Vec<(usize, usize)> {
+    let interval = interval.max(1);
+    let g = coverage_len.saturating_sub(1);
+    let (min, max) = bounds.unwrap_or((1, g));
+    let max = max.min(g);
+    if g == 0 || min > max {
+        return Vec::new();
+    }
+
+    let mut edges = Vec::new();
+    let mut lo = min;
+    while lo <= max {
+        let hi = (lo + interval - 1).min(max);
+        edges.push((lo, hi));
+        lo = hi + 1;
+    }
+    edges
 }
@@ -4,7 +4,7 @@ use std::collections::{HashMap, HashSet};
 use crate::{
     analyses::{Analysis, AnalysisSection, InputRequirement},
     analysis_parameter::AnalysisParameter,
-    graph_broker::{Edge, GraphBroker, ItemId},
+    graph_broker::{Edge, GraphBroker, ItemId, Orientation},
     html_report::ReportItem,
     util::{averageu32, median_already_sorted, n50_already_sorted},
 };
@@ -12,6 +12,7 @@ use crate::{
 use super::ConstructibleAnalysis;
 
 pub struct Info {
+    clustering_sample_size: Option<usize>,
     graph_info: Option<GraphInfo>,
     path_info: Option<PathInfo>,
     group_info: Option<GroupInfo>,
@@ -37,7 +38,11 @@ impl Analysis for Info {
     fn generate_report_section(
         &mut self,
         gb: Option<&GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
     ) -> anyhow::Result<Vec<AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
         if self.group_info.is_none() || self.path_info.is_none() {
             self.set_info(gb.expect("Cannot set info without a GraphBroker"));
         }
@@ -70,11 +75,21 @@ impl Analysis for Info {
                 run_name: graph.clone(),
                 countable: "Node Info".to_string(),
                 table: Some(table.clone()),
-                items: vec![ReportItem::Table {
-                    id: "info-2-table".to_string(),
-                    header: node_header,
-                    values: node_values,
-                }],
+                items: vec![
+                    ReportItem::Table {
+                        id: "info-2-table".to_string(),
+                        header: node_header,
+                        values: node_values,
+                    },
+                    self.get_degree_bar(
+                        &graph,
+                        &self
+                            .graph_info
+                            .as_ref()
+                            .expect("Graph info should have been calculated")
+                            .degrees,
+                    ),
+                ],
             },
             AnalysisSection {
                 id: format!("info-{graph}-path"),
@@ -114,8 +129,15 @@ impl Analysis for Info {
 }
 
 impl ConstructibleAnalysis for Info {
-    fn from_parameter(_parameter: AnalysisParameter) -> Self {
+    fn from_parameter(parameter: AnalysisParameter) -> Self {
+        let clustering_sample_size = match parameter {
+            AnalysisParameter::Info {
+                clustering_sample_size,
+            } => clustering_sample_size,
+            _ => panic!("expected info parameter"),
+        };
         Self {
+            clustering_sample_size,
             graph_info: None,
             path_info: None,
             group_info: None,
@@ -125,7 +147,7 @@ impl ConstructibleAnalysis for Info {
 
 impl Info {
     fn set_info(&mut self, gb: &GraphBroker) {
-        self.graph_info = Some(GraphInfo::from(gb));
+        self.graph_info = Some(GraphInfo::from(gb, self.clustering_sample_size));
         self.path_info = Some(PathInfo::from(gb));
         self.group_info = Some(GroupInfo::from(gb));
     }
@@ -184,6 +206,31 @@ impl Info {
                 "component",
                 graph_info.median_component.to_string(),
             ),
+            Self::get_row(
+                "graph",
+                "total",
+                "articulation node",
+                graph_info.articulation_nodes.to_string(),
+            ),
+            Self::get_row(
+                "graph",
+                "total",
+                "bridge edge",
+                graph_info.bridges.to_string(),
+            ),
+            Self::get_row(
+                "graph",
+                "largest",
+                "diameter",
+                graph_info.diameter_estimate.to_string(),
+            ),
+            Self::get_row("graph", "total", "scc", graph_info.scc_count.to_string()),
+            Self::get_row(
+                "graph",
+                "largest",
+                "scc",
+                graph_info.largest_scc.to_string(),
+            ),
         ];
         (header, values)
     }
@@ -216,10 +263,63 @@ impl Info {
             Self::get_row("node", "N50 node", "bp", graph_info.n50_node.to_string()),
             Self::get_row("node", "max", "degree", graph_info.max_degree.to_string()),
             Self::get_row("node", "min", "degree", graph_info.min_degree.to_string()),
+            Self::get_row(
+                "node",
+                "average",
+                "clustering",
+                graph_info.avg_clustering.to_string(),
+            ),
         ];
         (header, values)
     }
 
+    fn get_degree_bar(&self, graph: &str, degree: &[u32]) -> ReportItem {
+        if degree.is_empty() {
+            return ReportItem::Bar {
+                id: format!("info-{}-degree", graph),
+                name: "degree".to_string(),
+                x_label: "degree".to_string(),
+                y_label: "#nodes".to_string(),
+                log_toggle: true,
+                labels: Vec::new(),
+                values: Vec::new(),
+            };
+        }
+        let distinct = degree.iter().collect::<HashSet<_>>().len();
+        if distinct <= 100 {
+            let mut counts: HashMap<u32, usize> = HashMap::new();
+            for &d in degree {
+                *counts.entry(d).or_insert(0) += 1;
+            }
+            let mut labels_values: Vec<_> = counts.into_iter().collect();
+            labels_values.sort_by_key(|(d, _)| *d);
+            let (labels, values): (Vec<_>, Vec<_>) = labels_values
+                .into_iter()
+                .map(|(d, c)| (d.to_string(), c))
+                .unzip();
+            ReportItem::Bar {
+                id: format!("info-{}-degree", graph),
+                name: "degree".to_string(),
+                x_label: "degree".to_string(),
+                y_label: "#nodes".to_string(),
+                log_toggle: true,
+                labels,
+                values: values.into_iter().map(|v| v as f64).collect(),
+            }
+        } else {
+            let (labels, values) = Self::bin_values(degree.to_vec());
+            ReportItem::Bar {
+                id: format!("info-{}-degree", graph),
+                name: "degree".to_string(),
+                x_label: "degree".to_string(),
+                y_label: "#nodes".to_string(),
+                log_toggle: true,
+                labels,
+                values: values.into_iter().map(|v| v as f64).collect(),
+            }
+        }
+    }
+
     fn get_group_bar(&self, graph: &str, countable: &str) -> ReportItem {
         let groups = &self.group_info.as_ref().unwrap().groups;
         let (labels, values): (Vec<_>, Vec<_>) = if countable == "node" {
@@ -403,6 +503,19 @@ impl fmt::Display for Info {
             "graph\tmedian\tcomponent\t{}",
             graph_info.median_component
         )?;
+        writeln!(
+            f,
+            "graph\ttotal\tarticulation node\t{}",
+            graph_info.articulation_nodes
+        )?;
+        writeln!(f, "graph\ttotal\tbridge edge\t{}", graph_info.bridges)?;
+        writeln!(
+            f,
+            "graph\tlargest\tdiameter\t{}",
+            graph_info.diameter_estimate
+        )?;
+        writeln!(f, "graph\ttotal\tscc\t{}", graph_info.scc_count)?;
+        writeln!(f, "graph\tlargest\tscc\t{}", graph_info.largest_scc)?;
         writeln!(f, "node\taverage\tbp\t{}", graph_info.average_node)?;
         writeln!(f, "node\taverage\tdegree\t{}", graph_info.average_degree)?;
         writeln!(f, "node\tlongest\tbp\t{}", graph_info.largest_node)?;
@@ -411,6 +524,11 @@ impl fmt::Display for Info {
         writeln!(f, "node\tN50 node\tbp\t{}", graph_info.n50_node)?;
         writeln!(f, "node\tmax\tdegree\t{}", graph_info.max_degree)?;
         writeln!(f, "node\tmin\tdegree\t{}", graph_info.min_degree)?;
+        writeln!(
+            f,
+            "node\taverage\tclustering\t{}",
+            graph_info.avg_clustering
+        )?;
         writeln!(f, "path\taverage\tbp\t{}", path_info.bp_len.average)?;
         writeln!(f, "path\taverage\tnode\t{}", path_info.node_len.average)?;
         writeln!(f, "path\tlongest\tbp\t{}", path_info.bp_len.longest)?;
@@ -447,15 +565,41 @@ pub struct GraphInfo {
     pub n50_node: u32,
     pub basepairs: u32,
     pub group_count: usize,
+    pub articulation_nodes: usize,
+    pub bridges: usize,
+    pub diameter_estimate: u32,
+    pub scc_count: usize,
+    pub largest_scc: usize,
+    pub avg_clustering: f32,
+    pub degrees: Vec<u32>,
 }
 
 impl GraphInfo {
-    fn from(gb: &GraphBroker) -> Self {
+    // below this many nodes the exact clustering coefficient (O(sum k^2)) is cheap enough
+    // to always compute; larger graphs fall back to sampling unless the caller forces exact
+    // with an explicit clustering_sample_size of 0
+    const CLUSTERING_EXACT_NODE_THRESHOLD: usize = 10_000;
+    const DEFAULT_CLUSTERING_SAMPLE_SIZE: usize = 1_000;
+
+    fn from(gb: &GraphBroker, clustering_sample_size: Option<usize>) -> Self {
         let degree = gb.get_degree();
         let mut node_lens_sorted = gb.get_node_lens()[1..].to_vec();
         node_lens_sorted.sort_by(|a, b| b.cmp(a)); // decreasing, for N50
         let mut components = connected_components(gb.get_edges(), &gb.get_nodes());
         components.sort();
+        let (articulation_nodes, bridges) =
+            articulation_points_and_bridges(gb.get_edges(), &gb.get_nodes());
+        let diameter_estimate = estimate_diameter(gb.get_edges(), &gb.get_nodes());
+        let (scc_count, largest_scc) = directed_sccs(gb.get_edges());
+        let nodes = gb.get_nodes();
+        let adjacency = build_adjacency(gb.get_edges());
+        let sample_size = clustering_sample_size
+            .unwrap_or(if nodes.len() > Self::CLUSTERING_EXACT_NODE_THRESHOLD {
+                Self::DEFAULT_CLUSTERING_SAMPLE_SIZE
+            } else {
+                0
+            });
+        let avg_clustering = average_clustering_coefficient(&adjacency, &nodes, sample_size);
 
         Self {
             node_count: gb.get_node_count(),
@@ -468,6 +612,13 @@ impl GraphInfo {
             largest_component: *components.iter().max().unwrap_or(&0),
             smallest_component: *components.iter().min().unwrap_or(&0),
             median_component: median_already_sorted(&components),
+            articulation_nodes,
+            bridges,
+            diameter_estimate,
+            scc_count,
+            largest_scc,
+            avg_clustering,
+            degrees: degree[1..].to_vec(),
             largest_node: *node_lens_sorted.iter().max().unwrap(),
             shortest_node: *node_lens_sorted.iter().min().unwrap(),
             average_node: averageu32(&node_lens_sorted),
@@ -535,42 +686,385 @@ impl GroupInfo {
 }
 
 fn connected_components(edge2id: &HashMap<Edge, ItemId>, nodes: &Vec<ItemId>) -> Vec<u32> {
-    let mut component_lengths = Vec::new();
-    let mut visited: HashSet<ItemId> = HashSet::new();
-    let edges: HashMap<ItemId, Vec<ItemId>> = edge2id
+    let node_count = nodes.iter().map(|n| n.0 as usize).max().unwrap_or(0);
+    let mut dsu = DisjointSet::new(node_count + 1);
+    for e in edge2id.keys() {
+        dsu.union(e.0 .0 as usize, e.2 .0 as usize);
+    }
+
+    let mut sizes: HashMap<usize, u32> = HashMap::new();
+    for node in nodes {
+        let root = dsu.find(node.0 as usize);
+        *sizes.entry(root).or_insert(0) += 1;
+    }
+    sizes.into_values().collect()
+}
+
+fn build_adjacency(edge2id: &HashMap<Edge, ItemId>) -> HashMap<ItemId, Vec<ItemId>> {
+    edge2id
         .keys()
         .map(|x| (x.0, x.2))
         .chain(edge2id.keys().map(|x| (x.2, x.0)))
         .fold(HashMap::new(), |mut acc, (k, v)| {
             acc.entry(k).and_modify(|x| x.push(v)).or_insert(vec![v]);
             acc
-        });
-    for node in nodes {
-        if !visited.contains(node) {
-            component_lengths.push(dfs(&edges, *node, &mut visited));
+        })
+}
+
+// iterative Tarjan low-link pass (explicit stack to avoid recursion overflow on deep
+// graphs), run once per connected component since articulation points/bridges are only
+// defined within a component
+fn articulation_points_and_bridges(
+    edge2id: &HashMap<Edge, ItemId>,
+    nodes: &Vec<ItemId>,
+) -> (usize, usize) {
+    let adjacency = build_adjacency(edge2id);
+    let mut disc: HashMap<ItemId, u32> = HashMap::new();
+    let mut low: HashMap<ItemId, u32> = HashMap::new();
+    let mut is_articulation: HashSet<ItemId> = HashSet::new();
+    let mut bridges = 0usize;
+    let mut timer = 0u32;
+
+    // explicit DFS stack: (node, parent, next child index to visit, #tree children of root)
+    struct Frame {
+        node: ItemId,
+        parent: Option<ItemId>,
+        child_idx: usize,
+        root_children: usize,
+    }
+
+    for &root in nodes {
+        if disc.contains_key(&root) {
+            continue;
+        }
+        let mut stack: Vec<Frame> = vec![Frame {
+            node: root,
+            parent: None,
+            child_idx: 0,
+            root_children: 0,
+        }];
+        disc.insert(root, timer);
+        low.insert(root, timer);
+        timer += 1;
+
+        while let Some(frame) = stack.last_mut() {
+            let v = frame.node;
+            let neighbors = adjacency.get(&v).cloned().unwrap_or_default();
+            if frame.child_idx < neighbors.len() {
+                let w = neighbors[frame.child_idx];
+                frame.child_idx += 1;
+                if Some(w) == frame.parent {
+                    continue;
+                }
+                if let Some(&w_disc) = disc.get(&w) {
+                    let v_low = low[&v];
+                    low.insert(v, v_low.min(w_disc));
+                } else {
+                    if frame.node == root {
+                        frame.root_children += 1;
+                    }
+                    disc.insert(w, timer);
+                    low.insert(w, timer);
+                    timer += 1;
+                    stack.push(Frame {
+                        node: w,
+                        parent: Some(v),
+                        child_idx: 0,
+                        root_children: 0,
+                    });
+                }
+            } else {
+                let v = frame.node;
+                let parent = frame.parent;
+                let v_disc = disc[&v];
+                let v_low = low[&v];
+                let is_root = parent.is_none();
+                let root_children = frame.root_children;
+                stack.pop();
+                if let Some(p) = parent {
+                    let p_low = low[&p];
+                    low.insert(p, p_low.min(v_low));
+                    if v_low > v_disc {
+                        bridges += 1;
+                    }
+                    if p != root && v_low >= disc[&p] {
+                        is_articulation.insert(p);
+                    }
+                }
+                if is_root && root_children >= 2 {
+                    is_articulation.insert(v);
+                }
+            }
         }
     }
-    component_lengths
+
+    (is_articulation.len(), bridges)
 }
 
-fn dfs(edges: &HashMap<ItemId, Vec<ItemId>>, node: ItemId, visited: &mut HashSet<ItemId>) -> u32 {
-    let mut s = Vec::new();
-    let mut length = 0;
-    s.push(node);
-    while let Some(v) = s.pop() {
-        if visited.contains(&v) {
+fn flip(o: Orientation) -> Orientation {
+    match o {
+        Orientation::Forward => Orientation::Backward,
+        Orientation::Backward => Orientation::Forward,
+    }
+}
+
+// (node, strand) side of a node in the bidirected overlap graph
+type Side = (ItemId, Orientation);
+
+// Orientation-aware SCC statistics: a GFA link `L a o1 b o2` connects the `o1` end of
+// `a` to the `o2` end of `b`; in the bidirected graph this is the directed arc
+// `(a,o1) -> (b,o2)` together with its reverse complement `(b,!o2) -> (a,!o1)`. Nontrivial
+// SCCs over this directed graph flag cyclic/invertible structure that undirected
+// component counting hides.
+fn directed_sccs(edge2id: &HashMap<Edge, ItemId>) -> (usize, usize) {
+    let mut adjacency: HashMap<Side, Vec<Side>> = HashMap::new();
+    for e in edge2id.keys() {
+        adjacency
+            .entry((e.0, e.1))
+            .or_default()
+            .push((e.2, e.3));
+        adjacency
+            .entry((e.2, flip(e.3)))
+            .or_default()
+            .push((e.0, flip(e.1)));
+    }
+
+    let mut index: HashMap<Side, u32> = HashMap::new();
+    let mut lowlink: HashMap<Side, u32> = HashMap::new();
+    let mut on_stack: HashSet<Side> = HashSet::new();
+    let mut stack: Vec<Side> = Vec::new();
+    let mut counter = 0u32;
+    let mut components: Vec<usize> = Vec::new();
+
+    struct Frame {
+        v: Side,
+        child_idx: usize,
+    }
+
+    let vertices: Vec<Side> = adjacency.keys().cloned().collect();
+    for &start in &vertices {
+        if index.contains_key(&start) {
             continue;
         }
-        visited.insert(v);
-        length += 1;
-        if !edges.contains_key(&v) {
-            continue;
+        let mut call_stack = vec![Frame {
+            v: start,
+            child_idx: 0,
+        }];
+        index.insert(start, counter);
+        lowlink.insert(start, counter);
+        counter += 1;
+        stack.push(start);
+        on_stack.insert(start);
+
+        while let Some(frame) = call_stack.last_mut() {
+            let v = frame.v;
+            let neighbors = adjacency.get(&v).cloned().unwrap_or_default();
+            if frame.child_idx < neighbors.len() {
+                let w = neighbors[frame.child_idx];
+                frame.child_idx += 1;
+                if !index.contains_key(&w) {
+                    index.insert(w, counter);
+                    lowlink.insert(w, counter);
+                    counter += 1;
+                    stack.push(w);
+                    on_stack.insert(w);
+                    call_stack.push(Frame { v: w, child_idx: 0 });
+                } else if on_stack.contains(&w) {
+                    let v_low = lowlink[&v];
+                    lowlink.insert(v, v_low.min(index[&w]));
+                }
+            } else {
+                call_stack.pop();
+                if let Some(parent_frame) = call_stack.last() {
+                    let p = parent_frame.v;
+                    let v_low = lowlink[&v];
+                    let p_low = lowlink[&p];
+                    lowlink.insert(p, p_low.min(v_low));
+                }
+                if lowlink[&v] == index[&v] {
+                    let mut size = 0;
+                    loop {
+                        let w = stack.pop().unwrap();
+                        on_stack.remove(&w);
+                        size += 1;
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(size);
+                }
+            }
+        }
+    }
+
+    (
+        components.len(),
+        components.into_iter().max().unwrap_or(0),
+    )
+}
+
+// double-sweep BFS heuristic: from any vertex find the farthest vertex u, then BFS again
+// from u and report the farthest distance found; exact on trees, a tight lower bound
+// otherwise. Restricted to the largest connected component.
+fn estimate_diameter(edge2id: &HashMap<Edge, ItemId>, nodes: &Vec<ItemId>) -> u32 {
+    if nodes.len() < 2 {
+        return 0;
+    }
+    let node_count = nodes.iter().map(|n| n.0 as usize).max().unwrap_or(0);
+    let mut dsu = DisjointSet::new(node_count + 1);
+    for e in edge2id.keys() {
+        dsu.union(e.0 .0 as usize, e.2 .0 as usize);
+    }
+    let mut sizes: HashMap<usize, u32> = HashMap::new();
+    for node in nodes {
+        *sizes.entry(dsu.find(node.0 as usize)).or_insert(0) += 1;
+    }
+    let largest_root = match sizes.iter().max_by_key(|(_, &size)| size) {
+        Some((&root, _)) => root,
+        None => return 0,
+    };
+    let component: Vec<ItemId> = nodes
+        .iter()
+        .filter(|n| dsu.find(n.0 as usize) == largest_root)
+        .cloned()
+        .collect();
+    if component.len() < 2 {
+        return 0;
+    }
+
+    let adjacency = build_adjacency(edge2id);
+    let bfs_farthest = |start: ItemId| -> (ItemId, u32) {
+        let mut dist: HashMap<ItemId, u32> = HashMap::new();
+        let mut queue = std::collections::VecDeque::new();
+        dist.insert(start, 0);
+        queue.push_back(start);
+        let mut farthest = (start, 0u32);
+        while let Some(v) = queue.pop_front() {
+            let d = dist[&v];
+            if d > farthest.1 {
+                farthest = (v, d);
+            }
+            if let Some(neighbors) = adjacency.get(&v) {
+                for &w in neighbors {
+                    if !dist.contains_key(&w) {
+                        dist.insert(w, d + 1);
+                        queue.push_back(w);
+                    }
+                }
+            }
+        }
+        farthest
+    };
+
+    let (u, _) = bfs_farthest(component[0]);
+    let (_, d) = bfs_farthest(u);
+    d
+}
+
+// average local clustering coefficient: for each node v with neighbor set N(v) of size
+// k>=2, count the edges e present between pairs in N(v) and sum 2e/(k(k-1)), then divide
+// by the node count. Exact when sample_size is 0 (the common case below
+// GraphInfo::CLUSTERING_EXACT_NODE_THRESHOLD); otherwise averages over `sample_size`
+// randomly drawn nodes, since the exact computation is O(sum k^2).
+fn average_clustering_coefficient(
+    adjacency: &HashMap<ItemId, Vec<ItemId>>,
+    nodes: &[ItemId],
+    sample_size: usize,
+) -> f32 {
+    if nodes.is_empty() {
+        return 0.0;
+    }
+    let local = |v: &ItemId| -> f64 {
+        let neighbors = match adjacency.get(v) {
+            Some(n) => n,
+            None => return 0.0,
+        };
+        let k = neighbors.len();
+        if k < 2 {
+            return 0.0;
         }
-        for neigh in &edges[&v] {
-            if !visited.contains(neigh) {
-                s.push(*neigh);
+        let mut e = 0usize;
+        for (i, a) in neighbors.iter().enumerate() {
+            let adj_a: HashSet<ItemId> = adjacency.get(a).cloned().unwrap_or_default().into_iter().collect();
+            for b in &neighbors[i + 1..] {
+                if adj_a.contains(b) {
+                    e += 1;
+                }
             }
         }
+        (2 * e) as f64 / (k * (k - 1)) as f64
+    };
+
+    if sample_size == 0 || sample_size >= nodes.len() {
+        let sum: f64 = nodes.iter().map(local).sum();
+        (sum / nodes.len() as f64) as f32
+    } else {
+        let mut rng = SplitMix64::new(nodes.len() as u64);
+        let sum: f64 = (0..sample_size)
+            .map(|_| &nodes[rng.next_index(nodes.len())])
+            .map(local)
+            .sum();
+        (sum / sample_size as f64) as f32
+    }
+}
+
+// minimal splitmix64 PRNG, good enough for sampling a fixed node subset deterministically
+// without pulling in a dependency
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_index(&mut self, bound: usize) -> usize {
+        (self.next_u64() as usize) % bound
+    }
+}
+
+// disjoint-set (union-find) over ItemId indices, with path-halving and union-by-size
+struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<u32>,
+}
+
+impl DisjointSet {
+    fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        let mut x = x;
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let mut ra = self.find(a);
+        let mut rb = self.find(b);
+        if ra == rb {
+            return;
+        }
+        if self.size[ra] < self.size[rb] {
+            std::mem::swap(&mut ra, &mut rb);
+        }
+        self.parent[rb] = ra;
+        self.size[ra] += self.size[rb];
     }
-    length
 }
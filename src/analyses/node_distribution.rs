@@ -4,6 +4,7 @@ use itertools::multizip;
 use itertools::Itertools;
 
 use crate::{
+    analysis_parameter::{AnalysisParameter, BinMode},
     graph_broker::{GraphBroker, ItemId},
     html_report::{AnalysisSection, Bin, ReportItem},
     util::get_default_plot_downloads,
@@ -13,6 +14,13 @@ use crate::{
 use super::{Analysis, ConstructibleAnalysis, InputRequirement};
 
 pub struct NodeDistribution {
+    radius: u32,
+    bin_mode: BinMode,
+    log_density: bool,
+    knn_k: Option<u32>,
+    log_x: bool,
+    log_y: bool,
+    weight_by_length: bool,
     bins: Vec<Bin>,
     min: (u32, f64),
     max: (u32, f64),
@@ -30,9 +38,12 @@ impl Analysis for NodeDistribution {
         if self.bins.is_empty() {
             self.set_table(gb);
         }
-        let mut result = "Bin\tCoverage\tLog-Length\tLog-Size\n".to_string();
+        let mut result = "Bin\tCoverage\tLog-Length\tLog-Size\tWeight\n".to_string();
         for (i, bin) in self.bins.iter().enumerate() {
-            result.push_str(&format!("{}\t{}\t{}\t{}\n", i, bin.x, bin.y, bin.size));
+            result.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\n",
+                i, bin.x, bin.y, bin.size, bin.weight_sum
+            ));
         }
         Ok(result)
     }
@@ -44,7 +55,11 @@ impl Analysis for NodeDistribution {
     fn generate_report_section(
         &mut self,
         gb: Option<&crate::graph_broker::GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
     ) -> anyhow::Result<Vec<crate::html_report::AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
         let table = self.generate_table(gb)?;
         //let table = "".to_string();
         let table = format!("`{}`", &table);
@@ -73,8 +88,36 @@ impl Analysis for NodeDistribution {
 }
 
 impl ConstructibleAnalysis for NodeDistribution {
-    fn from_parameter(_parameter: crate::analysis_parameter::AnalysisParameter) -> Self {
+    fn from_parameter(parameter: crate::analysis_parameter::AnalysisParameter) -> Self {
+        let (radius, bin_mode, log_density, knn_k, log_x, log_y, weight_by_length) = match parameter
+        {
+            AnalysisParameter::NodeDistribution {
+                radius,
+                bin_mode,
+                log_density,
+                knn_k,
+                log_x,
+                log_y,
+                weight_by_length,
+            } => (
+                radius,
+                bin_mode,
+                log_density,
+                knn_k,
+                log_x,
+                log_y,
+                weight_by_length,
+            ),
+            _ => panic!("expected node distribution parameter"),
+        };
         Self {
+            radius,
+            bin_mode,
+            log_density,
+            knn_k,
+            log_x,
+            log_y,
+            weight_by_length,
             bins: Vec::new(),
             min: (0, 0.0),
             max: (0, 0.0),
@@ -105,7 +148,43 @@ impl NodeDistribution {
                 node_lens.into_iter().copied(),
             ))
             .collect();
-            let bins = Bin::hexbin(&points, 15, 9);
+            // raw (non-log-transformed) node lengths in bp, in the same order as `points`, used
+            // as the weight vector when `weight_by_length` is set
+            let weights: Option<Vec<u64>> = self.weight_by_length.then(|| {
+                gb.get_node_lens()[1..]
+                    .iter()
+                    .map(|x| *x as u64)
+                    .collect()
+            });
+            // the original hard-coded hexbin call used a 15:9 nx:ny ratio; keep that ratio
+            // while letting --radius control the overall hexagon size
+            let nx = self.radius.max(2);
+            let ny = ((nx as f64 * 9.0 / 15.0).round() as u32).max(2);
+            // knn_k takes precedence over log_density: it already adapts to local point density,
+            // so there's no raw count left to log-normalize
+            let mut bins = match self.bin_mode {
+                BinMode::Hex => Bin::hexbin(
+                    &points,
+                    nx,
+                    ny,
+                    self.log_density && self.knn_k.is_none(),
+                    self.log_x,
+                    self.log_y,
+                    weights.as_deref(),
+                ),
+                BinMode::Square => Bin::squarebin(
+                    &points,
+                    nx,
+                    ny,
+                    self.log_density && self.knn_k.is_none(),
+                    self.log_x,
+                    self.log_y,
+                    weights.as_deref(),
+                ),
+            };
+            if let Some(k) = self.knn_k {
+                Bin::apply_knn_density(&mut bins, &points, k as usize);
+            }
             self.bins = bins;
             self.min = (*cov_min, *lens_min);
             self.max = (*cov_max, *lens_max);
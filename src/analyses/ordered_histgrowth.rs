@@ -1,11 +1,11 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
 use crate::analysis_parameter::AnalysisParameter;
 use crate::graph_broker::{GraphBroker, ThresholdContainer};
 use crate::html_report::ReportItem;
-use crate::util::CountType;
+use crate::util::{parse_threshold_cli, CountType, RequireThreshold, Threshold};
 use crate::{analyses::InputRequirement, io::write_ordered_histgrowth_table};
 
 use super::{Analysis, AnalysisSection, ConstructibleAnalysis};
@@ -37,11 +37,45 @@ impl Analysis for OrderedHistgrowth {
         gb: Option<&crate::graph_broker::GraphBroker>,
     ) -> anyhow::Result<String> {
         if let Some(gb) = gb {
-            write_ordered_histgrowth_table(
+            self.set_inner(Some(gb))?;
+            let mut text = write_ordered_histgrowth_table(
                 gb.get_abacus_by_group(),
                 &self.inner.as_ref().unwrap().hist_aux,
                 gb.get_node_lens(),
-            )
+            )?;
+            let inner = self.inner.as_ref().unwrap();
+            text.push_str("\n# heaps-law fit: delta[m] ~ kappa * m^-alpha (alpha < 1: open, alpha > 1: closed)\n");
+            text.push_str("coverage\tquorum\talpha\tkappa\tr_squared\topenness\n");
+            for (i, g) in inner.growths.iter().enumerate() {
+                let fit = fit_heaps(g);
+                text.push_str(&format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\n",
+                    inner.hist_aux.coverage[i].get_string(),
+                    inner.hist_aux.quorum[i].get_string(),
+                    fit.alpha,
+                    fit.kappa,
+                    fit.r_squared,
+                    fit.openness_label(),
+                ));
+            }
+            if let Some(bands) = &inner.bands {
+                text.push_str("\n# bootstrap confidence bands over random group orderings (2.5/50/97.5 percentile)\n");
+                text.push_str("coverage\tquorum\tstep\tlower\tmedian\tupper\n");
+                for (i, band) in bands.iter().enumerate() {
+                    for m in 0..band.median.len() {
+                        text.push_str(&format!(
+                            "{}\t{}\t{}\t{}\t{}\t{}\n",
+                            inner.hist_aux.coverage[i].get_string(),
+                            inner.hist_aux.quorum[i].get_string(),
+                            m + 1,
+                            band.lower[m],
+                            band.median[m],
+                            band.upper[m],
+                        ));
+                    }
+                }
+            }
+            Ok(text)
         } else {
             Ok("".to_string())
         }
@@ -50,7 +84,11 @@ impl Analysis for OrderedHistgrowth {
     fn generate_report_section(
         &mut self,
         dm: Option<&GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
     ) -> anyhow::Result<Vec<AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
         self.set_inner(dm)?;
         let count = match self.parameter {
             AnalysisParameter::OrderedGrowth { count_type, .. } => count_type,
@@ -78,6 +116,41 @@ impl Analysis for OrderedHistgrowth {
                 .replace(&[' ', '|', '\\'], "-")
         );
         let labels = dm.unwrap().get_abacus_by_group().groups.clone();
+
+        // one Heaps'-law overlay curve per coverage/quorum growth curve, appended to the same
+        // `MultiBar` series rather than a separate report item, so the fit sits right next to
+        // the data it was fitted from; curves with fewer than three positive-delta points (see
+        // `fit_heaps`) are left out instead of plotting a meaningless NaN line
+        let mut names = growth_labels.clone();
+        let mut values = growths.clone();
+        for (label, g) in growth_labels.iter().zip(growths) {
+            let fit = fit_heaps(g);
+            if let Some(curve) = fit.fitted_curve(g) {
+                names.push(format!(
+                    "{} (heaps fit, \u{3b1}={:.2}, {})",
+                    label,
+                    fit.alpha,
+                    fit.openness_label()
+                ));
+                values.push(curve);
+            }
+        }
+
+        // bootstrap confidence bands, one lower/median/upper triple of series per curve, appended
+        // the same way as the Heaps' fit above: `ReportItem::MultiBar` has no dedicated "band"
+        // field, so the shaded region is just three more named series the HTML plot can group by
+        // their shared label prefix
+        if let Some(bands) = &self.inner.as_ref().unwrap().bands {
+            for (label, band) in growth_labels.iter().zip(bands) {
+                names.push(format!("{} (bootstrap median)", label));
+                values.push(band.median.clone());
+                names.push(format!("{} (bootstrap lower 2.5%)", label));
+                values.push(band.lower.clone());
+                names.push(format!("{} (bootstrap upper 97.5%)", label));
+                values.push(band.upper.clone());
+            }
+        }
+
         let growth_tabs = vec![AnalysisSection {
             id: format!("{id_prefix}"),
             analysis: "Ordered Growth".to_string(),
@@ -86,12 +159,12 @@ impl Analysis for OrderedHistgrowth {
             table: Some(table.clone()),
             items: vec![ReportItem::MultiBar {
                 id: format!("{id_prefix}"),
-                names: growth_labels.clone(),
+                names,
                 x_label: "taxa".to_string(),
                 y_label: format!("{}s", count),
                 //labels: (1..growths[0].len()).map(|i| i.to_string()).collect(),
                 labels,
-                values: growths.clone(),
+                values,
                 log_toggle: false,
             }],
         }];
@@ -148,6 +221,9 @@ impl OrderedHistgrowth {
             CountType::Bp => HashSet::from([InputRequirement::Bp]),
             CountType::Node => HashSet::from([InputRequirement::Node]),
             CountType::Edge => HashSet::from([InputRequirement::Edge]),
+            CountType::Kmer => HashSet::from([InputRequirement::Kmer]),
+            CountType::Minimizer => HashSet::from([InputRequirement::Minimizer]),
+            CountType::Branch => HashSet::from([InputRequirement::Branch]),
             CountType::All => HashSet::from([
                 InputRequirement::Bp,
                 InputRequirement::Node,
@@ -166,7 +242,15 @@ impl OrderedHistgrowth {
         }
 
         if let AnalysisParameter::OrderedGrowth {
-            coverage, quorum, ..
+            coverage,
+            quorum,
+            count_type,
+            permutations,
+            seed,
+            max_coverage,
+            normalize_paths,
+            normalize_threshold,
+            ..
         } = &self.parameter
         {
             let quorum = quorum.to_owned().unwrap_or("0".to_string());
@@ -176,26 +260,142 @@ impl OrderedHistgrowth {
             if gb.is_none() {
                 panic!("OrderedHistgrowth needs a graph in order to work");
             }
+            let gb = gb.unwrap();
+
+            // one optional upper-bound threshold per (coverage, quorum) pair; a single
+            // max-coverage value broadcasts across all pairs the same way a single coverage or
+            // quorum value already does in `ThresholdContainer::parse_params`
+            let max_coverage_thresholds: Vec<Option<Threshold>> = match max_coverage {
+                Some(s) if !s.is_empty() => {
+                    let mut parsed = parse_threshold_cli(s, RequireThreshold::Either)?;
+                    if parsed.len() == 1 {
+                        parsed = vec![parsed[0]; hist_aux.coverage.len()];
+                    } else if parsed.len() != hist_aux.coverage.len() {
+                        anyhow::bail!(
+                            "number of max-coverage thresholds must match the number of \
+                             coverage/quorum thresholds, or be a single value"
+                        );
+                    }
+                    parsed.into_iter().map(Some).collect()
+                }
+                _ => vec![None; hist_aux.coverage.len()],
+            };
+            let weight_by_bp = *count_type == CountType::Bp;
+
+            // `--normalize-paths` collapses groups with identical (or, above
+            // `--normalize-threshold`, Jaccard-similar) countable sets into one representative
+            // before the curve is computed, so duplicated content isn't counted once per
+            // duplicate. This operates on whatever groups `GraphBroker` already produced (see the
+            // `normalize_paths` doc comment in `analysis_parameter.rs`), and since the resulting
+            // cluster ids aren't the real per-group abacus, every pair is computed via
+            // `calc_growth_for_order` rather than the opaque `calc_growth` once normalization is on
+            let normalized = if normalize_paths.unwrap_or(false) {
+                let threshold = normalize_threshold.unwrap_or(1.0);
+                let cluster_of = cluster_groups_by_similarity(
+                    &gb.get_abacus_by_group().r,
+                    &gb.get_abacus_by_group().c,
+                    gb.get_group_count(),
+                    threshold,
+                );
+                let n_clusters = cluster_of.iter().copied().max().map_or(0, |m| m + 1);
+                log::info!(
+                    "normalize-paths collapsed {} groups into {} cluster(s) at threshold {}",
+                    gb.get_group_count(),
+                    n_clusters,
+                    threshold
+                );
+                let (r, c) = remap_csc_to_clusters(
+                    &gb.get_abacus_by_group().r,
+                    &gb.get_abacus_by_group().c,
+                    &cluster_of,
+                );
+                Some((r, c, n_clusters))
+            } else {
+                None
+            };
 
             let growths: Vec<Vec<f64>> = hist_aux
                 .coverage
                 .par_iter()
                 .zip(&hist_aux.quorum)
-                .map(|(c, q)| {
+                .zip(&max_coverage_thresholds)
+                .map(|((c, q), max_c)| {
                     log::info!(
                         "calculating ordered growth for coverage >= {} and quorum >= {}",
                         &c,
                         &q
                     );
-                    gb.unwrap()
-                        .get_abacus_by_group()
-                        .calc_growth(c, q, gb.unwrap().get_node_lens())
+                    match (&normalized, max_c) {
+                        // no cap, no normalization: keep using the opaque
+                        // AbacusByGroup::calc_growth exactly as before, rather than routing every
+                        // call through the reimplementation
+                        (None, None) => gb
+                            .get_abacus_by_group()
+                            .calc_growth(c, q, gb.get_node_lens()),
+                        // calc_growth has no upper-bound parameter and its body lives in a
+                        // submodule this tree doesn't ship, so a capped curve is computed via
+                        // the same from-scratch CSC walk the bootstrap bands already use,
+                        // fed the graph's natural (unpermuted) group order
+                        (None, Some(max_c)) => {
+                            let order: Vec<usize> = (0..gb.get_group_count()).collect();
+                            calc_growth_for_order(
+                                &gb.get_abacus_by_group().r,
+                                &gb.get_abacus_by_group().c,
+                                gb.get_node_lens(),
+                                weight_by_bp,
+                                &order,
+                                c,
+                                q,
+                                Some(max_c),
+                            )
+                        }
+                        (Some((r, nc, n_clusters)), max_c) => {
+                            let order: Vec<usize> = (0..*n_clusters).collect();
+                            calc_growth_for_order(
+                                r,
+                                nc,
+                                gb.get_node_lens(),
+                                weight_by_bp,
+                                &order,
+                                c,
+                                q,
+                                max_c,
+                            )
+                        }
+                    }
                 })
                 .collect();
+
+            let bands = match permutations {
+                Some(r) if *r > 0 => {
+                    let weight_by_bp = *count_type == CountType::Bp;
+                    let (band_r, band_c, band_group_count) = match &normalized {
+                        Some((r, c, n_clusters)) => (r.as_slice(), c.as_slice(), *n_clusters),
+                        None => (
+                            gb.get_abacus_by_group().r.as_slice(),
+                            gb.get_abacus_by_group().c.as_slice(),
+                            gb.get_group_count(),
+                        ),
+                    };
+                    Some(bootstrap_growth_bands(
+                        band_r,
+                        band_c,
+                        gb.get_node_lens(),
+                        band_group_count,
+                        &hist_aux,
+                        weight_by_bp,
+                        *r,
+                        seed.unwrap_or(0),
+                    ))
+                }
+                _ => None,
+            };
+
             self.inner = Some(InnerOrderedGrowth {
                 growths,
+                bands,
                 hist_aux,
-                graph: gb.unwrap().get_fname(),
+                graph: gb.get_fname(),
             });
             Ok(())
         } else {
@@ -206,6 +406,364 @@ impl OrderedHistgrowth {
 
 struct InnerOrderedGrowth {
     growths: Growths,
+    bands: Option<Vec<GrowthBand>>,
     hist_aux: ThresholdContainer,
     graph: String,
 }
+
+/// Column-wise 2.5/50/97.5 percentile bounds across `R` permuted-order growth curves for one
+/// (coverage, quorum) pair, each `Vec` the same length as the curve it bands.
+///
+/// `pub(crate)`, along with the rest of this permutation-bootstrap machinery below, so
+/// `analyses::growth::Growth` can reuse it for its own (unordered) bootstrap bands instead of
+/// duplicating it.
+pub(crate) struct GrowthBand {
+    pub(crate) median: Vec<f64>,
+    pub(crate) lower: Vec<f64>,
+    pub(crate) upper: Vec<f64>,
+}
+
+/// Deterministic splitmix64-style PRNG, in place of an unvendored `rand` dependency (this tree
+/// has no `Cargo.toml` to declare one against, the same reasoning `cache.rs`'s `fnv1a64_seeded`
+/// and `similarity.rs`'s `hash64` already rely on for their own hashing).
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform integer in `0..bound` via a plain `% bound` reduction (the tiny modulo bias is
+    /// acceptable here; this is Fisher-Yates shuffling of a few hundred groups, not
+    /// cryptography).
+    fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+/// Fisher-Yates shuffle of `0..n`, seeded so a given `--seed` reproduces the exact same set of
+/// permutations (and thus the exact same bands) across runs.
+pub(crate) fn shuffled_order(n: usize, rng: &mut SplitMix64) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..n).collect();
+    for i in (1..n).rev() {
+        let j = rng.next_below(i + 1);
+        order.swap(i, j);
+    }
+    order
+}
+
+/// Recomputes a growth curve for an arbitrary permutation of the group order, independent of
+/// [`crate::graph_broker::AbacusByGroup::calc_growth`] (whose group order is fixed at
+/// construction time and can't be re-supplied). Walks the same CSC `r`/`c` columns
+/// `similarity.rs`/`windowed_similarity.rs` already read directly, inverted into a group -> nodes
+/// index so each permutation step only touches the nodes newly covered by the group it adds.
+pub(crate) fn calc_growth_for_order(
+    r: &[u64],
+    c: &[u64],
+    node_lens: &[u32],
+    weight_by_bp: bool,
+    order: &[usize],
+    coverage: &Threshold,
+    quorum: &Threshold,
+    max_coverage: Option<&Threshold>,
+) -> Vec<f64> {
+    let group_count = order.len();
+    let mut group_to_nodes: Vec<Vec<usize>> = vec![Vec::new(); group_count];
+    for node in 0..r.len().saturating_sub(1) {
+        let (start, end) = (r[node] as usize, r[node + 1] as usize);
+        for &group in &c[start..end] {
+            group_to_nodes[group as usize].push(node);
+        }
+    }
+
+    let mut presence: HashMap<usize, usize> = HashMap::new();
+    let mut growth = Vec::with_capacity(group_count);
+    for (i, &group) in order.iter().enumerate() {
+        let m = i + 1;
+        for &node in &group_to_nodes[group] {
+            *presence.entry(node).or_insert(0) += 1;
+        }
+        let required = coverage.to_absolute(m).max(quorum.to_absolute(m));
+        let max_allowed = max_coverage.map(|t| t.to_absolute(m));
+        let value: f64 = presence
+            .iter()
+            .filter(|&(_, &count)| {
+                count >= required && max_allowed.is_none_or(|max_allowed| count <= max_allowed)
+            })
+            .map(|(&node, _)| if weight_by_bp { node_lens[node] as f64 } else { 1.0 })
+            .sum();
+        growth.push(value);
+    }
+    growth
+}
+
+/// Union-find over group indices with path compression, used to gather groups into
+/// similarity-clusters without materializing every pairwise link.
+struct UnionFind {
+    parent: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(n: usize) -> Self {
+        UnionFind {
+            parent: (0..n).collect(),
+        }
+    }
+
+    fn find(&mut self, i: usize) -> usize {
+        if self.parent[i] != i {
+            self.parent[i] = self.find(self.parent[i]);
+        }
+        self.parent[i]
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra != rb {
+            self.parent[ra] = rb;
+        }
+    }
+}
+
+/// Groups group-indices whose node sets are identical or Jaccard-similar at or above
+/// `threshold`, for `--normalize-paths`. Quadratic in `group_count`, which is fine here since
+/// it's the number of groups (e.g. samples/haplotypes) rather than the number of countables.
+/// Returns one cluster id per original group index, contiguous from 0 and assigned in order of
+/// first appearance so the lowest-index member of a cluster is its de facto representative.
+fn cluster_groups_by_similarity(
+    r: &[u64],
+    c: &[u64],
+    group_count: usize,
+    threshold: f64,
+) -> Vec<usize> {
+    let mut group_to_nodes: Vec<HashSet<usize>> = vec![HashSet::new(); group_count];
+    for node in 0..r.len().saturating_sub(1) {
+        let (start, end) = (r[node] as usize, r[node + 1] as usize);
+        for &group in &c[start..end] {
+            group_to_nodes[group as usize].insert(node);
+        }
+    }
+
+    let mut uf = UnionFind::new(group_count);
+    for i in 0..group_count {
+        for j in (i + 1)..group_count {
+            let (a, b) = (&group_to_nodes[i], &group_to_nodes[j]);
+            let union_size = a.union(b).count();
+            let similarity = if union_size == 0 {
+                1.0
+            } else {
+                a.intersection(b).count() as f64 / union_size as f64
+            };
+            if similarity >= threshold {
+                uf.union(i, j);
+            }
+        }
+    }
+
+    let mut cluster_of = vec![usize::MAX; group_count];
+    let mut next_id = 0;
+    for i in 0..group_count {
+        let root = uf.find(i);
+        if cluster_of[root] == usize::MAX {
+            cluster_of[root] = next_id;
+            next_id += 1;
+        }
+        cluster_of[i] = cluster_of[root];
+    }
+    cluster_of
+}
+
+/// Rebuilds a CSC `(r, c)` pair over cluster ids instead of raw group ids, for `--normalize-paths`:
+/// a node is now "present in" a cluster if any of its merged groups were, deduplicated so a node
+/// straddling two groups of the same cluster isn't double-counted.
+fn remap_csc_to_clusters(r: &[u64], c: &[u64], cluster_of: &[usize]) -> (Vec<u64>, Vec<u64>) {
+    let mut new_r = Vec::with_capacity(r.len());
+    let mut new_c = Vec::new();
+    new_r.push(0u64);
+    for node in 0..r.len().saturating_sub(1) {
+        let (start, end) = (r[node] as usize, r[node + 1] as usize);
+        let mut clusters: Vec<u64> = c[start..end]
+            .iter()
+            .map(|&g| cluster_of[g as usize] as u64)
+            .collect();
+        clusters.sort_unstable();
+        clusters.dedup();
+        new_c.extend(clusters);
+        new_r.push(new_c.len() as u64);
+    }
+    (new_r, new_c)
+}
+
+/// Draws `permutations` random group orders, recomputes a growth curve for each (in parallel,
+/// via the existing rayon setup) and returns per-(coverage, quorum) 2.5/50/97.5 percentile bands
+/// across the resulting `R x N` matrix, so the report can shade a confidence region around the
+/// single fixed-order curve instead of presenting it as the only possible shape.
+pub(crate) fn bootstrap_growth_bands(
+    r: &[u64],
+    c: &[u64],
+    node_lens: &[u32],
+    group_count: usize,
+    hist_aux: &ThresholdContainer,
+    weight_by_bp: bool,
+    permutations: usize,
+    seed: u64,
+) -> Vec<GrowthBand> {
+    let orders: Vec<Vec<usize>> = (0..permutations)
+        .map(|i| {
+            // distinct, reproducible per-permutation seed derived from the run seed so
+            // different `i` never collide on the same shuffle
+            let mut rng = SplitMix64::new(seed.wrapping_add(i as u64 + 1));
+            shuffled_order(group_count, &mut rng)
+        })
+        .collect();
+
+    hist_aux
+        .coverage
+        .par_iter()
+        .zip(&hist_aux.quorum)
+        .map(|(coverage, quorum)| {
+            let curves: Vec<Vec<f64>> = orders
+                .par_iter()
+                .map(|order| {
+                    calc_growth_for_order(
+                        r,
+                        c,
+                        node_lens,
+                        weight_by_bp,
+                        order,
+                        coverage,
+                        quorum,
+                        None,
+                    )
+                })
+                .collect();
+            percentile_bands(&curves, group_count)
+        })
+        .collect()
+}
+
+/// Column-wise 2.5/50/97.5 percentile across an `R x N` matrix of growth curves: sorts each
+/// column independently (order statistics, not a parametric assumption) and reads off the bounds.
+pub(crate) fn percentile_bands(curves: &[Vec<f64>], n: usize) -> GrowthBand {
+    let r = curves.len();
+    let mut median = Vec::with_capacity(n);
+    let mut lower = Vec::with_capacity(n);
+    let mut upper = Vec::with_capacity(n);
+    for m in 0..n {
+        let mut column: Vec<f64> = curves.iter().map(|curve| curve[m]).collect();
+        column.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let median_idx = ((0.5 * (r - 1) as f64).round() as usize).min(r - 1);
+        let lower_idx = ((0.025 * (r - 1) as f64).round() as usize).min(r - 1);
+        let upper_idx = ((0.975 * (r - 1) as f64).round() as usize).min(r - 1);
+        median.push(column[median_idx]);
+        lower.push(column[lower_idx]);
+        upper.push(column[upper_idx]);
+    }
+    GrowthBand {
+        median,
+        lower,
+        upper,
+    }
+}
+
+/// Tettelin/Heaps'-law fit of a growth curve's per-step increments to `delta[m] ~ kappa *
+/// m^-alpha`. `alpha < 1` means the curve keeps adding material at a slowing but nonzero rate
+/// (open pangenome); `alpha > 1` means increments decay fast enough that growth is petering out
+/// (closed).
+struct HeapsFit {
+    alpha: f64,
+    kappa: f64,
+    r_squared: f64,
+}
+
+impl HeapsFit {
+    fn openness_label(&self) -> &'static str {
+        if self.alpha.is_nan() {
+            "unknown"
+        } else if self.alpha < 1.0 {
+            "open"
+        } else {
+            "closed"
+        }
+    }
+
+    /// Reconstructs a fitted curve `g_hat[m] = g_hat[m-1] + kappa * m^-alpha`, anchored at the
+    /// actual `g[0]` so the overlay starts where the real curve does. `None` when the fit itself
+    /// is NaN (too few positive-delta points, see [`fit_heaps`]).
+    fn fitted_curve(&self, g: &[f64]) -> Option<Vec<f64>> {
+        if self.alpha.is_nan() || g.is_empty() {
+            return None;
+        }
+        let mut fitted = Vec::with_capacity(g.len());
+        fitted.push(g[0]);
+        for m in 2..=g.len() {
+            let delta = self.kappa * (m as f64).powf(-self.alpha);
+            let prev = *fitted.last().unwrap();
+            fitted.push(prev + delta);
+        }
+        Some(fitted)
+    }
+}
+
+/// Fits `delta[m] ~= kappa * m^-alpha` (the Tettelin/Heaps'-law model) to the per-step growth
+/// `delta[m] = g[m] - g[m-1]`, `m = 2..=g.len()` (treating `g`'s slice position as the genome
+/// count `m`, one-indexed), via ordinary least squares on `log delta[m] = log kappa - alpha *
+/// log m`, restricted to points with `delta[m] > 0` (a power law has no real logarithm at zero or
+/// negative increments). Fewer than three such points can't support a meaningful line fit, so
+/// this reports NaN for every field rather than fitting noise.
+fn fit_heaps(g: &[f64]) -> HeapsFit {
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+    for m in 2..=g.len() {
+        let delta = g[m - 1] - g[m - 2];
+        if delta > 0.0 {
+            xs.push((m as f64).ln());
+            ys.push(delta.ln());
+        }
+    }
+
+    if xs.len() < 3 {
+        return HeapsFit {
+            alpha: f64::NAN,
+            kappa: f64::NAN,
+            r_squared: f64::NAN,
+        };
+    }
+
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let mut covariance = 0.0;
+    let mut variance_x = 0.0;
+    for (&x, &y) in xs.iter().zip(&ys) {
+        covariance += (x - mean_x) * (y - mean_y);
+        variance_x += (x - mean_x).powi(2);
+    }
+    let slope = covariance / variance_x;
+    let intercept = mean_y - slope * mean_x;
+
+    let mut ss_res = 0.0;
+    let mut ss_tot = 0.0;
+    for (&x, &y) in xs.iter().zip(&ys) {
+        let predicted = intercept + slope * x;
+        ss_res += (y - predicted).powi(2);
+        ss_tot += (y - mean_y).powi(2);
+    }
+    let r_squared = if ss_tot > 0.0 { 1.0 - ss_res / ss_tot } else { 1.0 };
+
+    HeapsFit {
+        alpha: -slope,
+        kappa: intercept.exp(),
+        r_squared,
+    }
+}
@@ -5,11 +5,14 @@ use rayon::prelude::*;
 
 use crate::graph_broker::GraphBroker;
 use crate::{
-    analyses::InputRequirement, analysis_parameter::AnalysisParameter, html_report::ReportItem,
-    io::write_metadata_comments, util::CountType,
+    analyses::InputRequirement,
+    analysis_parameter::{AnalysisParameter, SimilarityMetric},
+    html_report::ReportItem,
+    io::write_metadata_comments,
+    util::CountType,
 };
 use core::panic;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::usize;
 
 use super::{Analysis, AnalysisSection, ConstructibleAnalysis};
@@ -19,6 +22,8 @@ pub struct Similarity {
     table: Option<Vec<Vec<f32>>>,
     labels: Option<Vec<String>>,
     count: CountType,
+    newick: Option<String>,
+    merges: Option<Vec<(usize, usize, f32, usize)>>,
 }
 
 impl Analysis for Similarity {
@@ -30,9 +35,18 @@ impl Analysis for Similarity {
             self.set_table(gb);
         }
         let mut text = write_metadata_comments()?;
+        if let Some(newick) = self.newick.as_ref() {
+            text.push_str(&format!("# dendrogram (newick): {}\n", newick));
+        }
         let table = self.table.as_ref().unwrap();
         let labels = self.labels.as_ref().unwrap();
         text.push_str(&get_table_string(table, labels));
+        let containment = match self.parameter {
+            AnalysisParameter::Similarity { containment, .. } => containment,
+            _ => panic!("Similarity analysis needs similarity parameter"),
+        };
+        text.push_str("\n# distance matrix (PHYLIP format)\n");
+        text.push_str(&get_phylip_string(table, labels, containment));
         Ok(text)
     }
 
@@ -49,7 +63,11 @@ impl Analysis for Similarity {
     fn generate_report_section(
         &mut self,
         gb: Option<&crate::graph_broker::GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
     ) -> anyhow::Result<Vec<AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
         if self.table.is_none() {
             self.set_table(gb);
         }
@@ -75,13 +93,41 @@ impl Analysis for Similarity {
             table: Some(table.clone()),
             run_name: self.get_run_name(gb),
             countable: k.to_string(),
-            items: vec![ReportItem::Heatmap {
-                id: format!("{id_prefix}-{k}"),
-                name: gb.get_fname(),
-                x_labels: self.labels.as_ref().unwrap().clone(),
-                y_labels: self.labels.as_ref().unwrap().clone(),
-                values: self.table.as_ref().unwrap().clone(),
-            }],
+            items: vec![
+                ReportItem::Heatmap {
+                    id: format!("{id_prefix}-{k}"),
+                    name: gb.get_fname(),
+                    x_labels: self.labels.as_ref().unwrap().clone(),
+                    y_labels: self.labels.as_ref().unwrap().clone(),
+                    values: self.table.as_ref().unwrap().clone(),
+                },
+                ReportItem::Table {
+                    id: format!("{id_prefix}-{k}-merges"),
+                    header: vec![
+                        "merge".to_string(),
+                        "cluster1".to_string(),
+                        "cluster2".to_string(),
+                        "height".to_string(),
+                        "size".to_string(),
+                    ],
+                    values: self
+                        .merges
+                        .as_ref()
+                        .unwrap()
+                        .iter()
+                        .enumerate()
+                        .map(|(i, (c1, c2, height, size))| {
+                            vec![
+                                i.to_string(),
+                                c1.to_string(),
+                                c2.to_string(),
+                                height.to_string(),
+                                size.to_string(),
+                            ]
+                        })
+                        .collect(),
+                },
+            ],
         }];
         Ok(tabs)
     }
@@ -97,16 +143,66 @@ impl ConstructibleAnalysis for Similarity {
             parameter,
             table: None,
             labels: None,
+            newick: None,
+            merges: None,
         }
     }
 }
 
 impl Similarity {
+    // below this many groups, the exact O(nodes * groups^2) pass is already cheap, so
+    // `sketch_k` is ignored and the exact path runs regardless
+    const MIN_GROUPS_FOR_SKETCH: usize = 64;
+
+    /// Bottom-k MinHash sketch per group: the `k` smallest distinct hash values over its item
+    /// set, as a sorted `Vec<u64>` of length `<= k`. `weight_by_bp` expands each node into one
+    /// distinct hashed item per base pair, so longer nodes contribute proportionally more items
+    /// to the sketch, the same way `weight` scales the exact intersection counts in `set_table`.
+    fn build_sketches(
+        r: &[u64],
+        c: &[u64],
+        node_lens: &[u32],
+        group_count: usize,
+        weight_by_bp: bool,
+        k: usize,
+    ) -> Vec<Vec<u64>> {
+        let tuples: Vec<(usize, usize)> = r.iter().map(|x| *x as usize).tuple_windows().collect();
+        let mut heaps: Vec<BinaryHeap<u64>> = (0..group_count).map(|_| BinaryHeap::new()).collect();
+        for (index, tuple) in tuples.iter().enumerate() {
+            let reps = if weight_by_bp {
+                node_lens[index] as u64
+            } else {
+                1
+            };
+            for rep in 0..reps {
+                let item = if weight_by_bp {
+                    (index as u64) << 32 | rep
+                } else {
+                    index as u64
+                };
+                let h = hash64(item);
+                for &group in &c[tuple.0..tuple.1] {
+                    let heap = &mut heaps[group as usize];
+                    if heap.len() < k {
+                        heap.push(h);
+                    } else if heap.peek().is_some_and(|&max| h < max) {
+                        heap.pop();
+                        heap.push(h);
+                    }
+                }
+            }
+        }
+        heaps.into_iter().map(BinaryHeap::into_sorted_vec).collect()
+    }
+
     fn count_to_input_req(count: CountType) -> HashSet<InputRequirement> {
         match count {
             CountType::Bp => HashSet::from([InputRequirement::Bp]),
             CountType::Node => HashSet::from([InputRequirement::Node]),
             CountType::Edge => HashSet::from([InputRequirement::Edge]),
+            CountType::Kmer => HashSet::from([InputRequirement::Kmer]),
+            CountType::Minimizer => HashSet::from([InputRequirement::Minimizer]),
+            CountType::Branch => HashSet::from([InputRequirement::Branch]),
             CountType::All => HashSet::from([
                 InputRequirement::Bp,
                 InputRequirement::Node,
@@ -121,49 +217,126 @@ impl Similarity {
         let c = &gb.get_abacus_by_group().c;
         let mut labels = gb.get_abacus_by_group().groups.clone();
 
-        let tuples: Vec<(_, _)> = r.iter().map(|x| *x as usize).tuple_windows().collect();
+        let (metric, sketch_k, containment) = match self.parameter {
+            AnalysisParameter::Similarity {
+                metric,
+                sketch_k,
+                containment,
+                ..
+            } => (metric, sketch_k, containment),
+            _ => panic!("Similarity analysis needs similarity parameter"),
+        };
+        // bp-weighted similarity uses `node_lens` as the per-item contribution regardless of
+        // `count`, since it's specifically about weighting shared items by their length
+        let weight_by_bp = self.count == CountType::Bp || metric == SimilarityMetric::BpWeighted;
 
-        let mut path_similarities: HashMap<u128, usize> = HashMap::new();
-        let mut path_lens: HashMap<u64, usize> = HashMap::new();
         let node_lens = gb.get_node_lens();
-        for (index, tuple) in tuples.iter().enumerate() {
-            let node_length = node_lens[index] as usize;
-            for x in &c[tuple.0..tuple.1] {
-                if self.count == CountType::Bp {
-                    *path_lens.entry(*x).or_insert(0) += node_length;
-                } else {
-                    *path_lens.entry(*x).or_insert(0) += 1;
+        let group_count = gb.get_group_count();
+        // sketching only estimates symmetric Jaccard (plain or bp-weighted); Dice/Cosine still
+        // need the exact sums, containment needs the exact per-pair intersection, and below
+        // `MIN_GROUPS_FOR_SKETCH` groups the exact O(nodes * groups^2) pass is already cheap
+        // enough that the approximation isn't worth the error it adds
+        let use_sketch = sketch_k.is_some()
+            && !containment
+            && group_count >= Self::MIN_GROUPS_FOR_SKETCH
+            && matches!(metric, SimilarityMetric::Jaccard | SimilarityMetric::BpWeighted);
+
+        let mut table: Vec<Vec<f32>> = vec![vec![0.0; group_count]; group_count];
+        if use_sketch {
+            let k = sketch_k.unwrap();
+            let sketches = Self::build_sketches(r, c, node_lens, group_count, weight_by_bp, k);
+            for i in 0..group_count {
+                for j in 0..group_count {
+                    table[i][j] = estimate_jaccard(&sketches[i], &sketches[j], k);
                 }
-                for y in &c[tuple.0..tuple.1] {
-                    if self.count == CountType::Bp {
-                        *path_similarities
-                            .entry((*x as u128) << 64 | *y as u128)
-                            .or_insert(0) += node_length;
-                    } else {
-                        *path_similarities
+            }
+        } else {
+            let tuples: Vec<(_, _)> = r.iter().map(|x| *x as usize).tuple_windows().collect();
+
+            // abundance-weighted similarity needs per-(node, group) occurrence counts, not just
+            // presence; `c` currently records one entry per distinct group touching a node
+            // (`AbacusByGroup::from_gfa` has no accumulate-multiplicities option in this
+            // snapshot), so `w_g` below degrades to the same 0/1 presence every other metric
+            // already sees -- counting duplicates in the slice rather than assuming they're
+            // absent means this picks up real copy-number weighting for free if that ever
+            // changes upstream
+            let track_abundance = metric == SimilarityMetric::AbundanceWeighted;
+
+            // accumulated in a single pass over the CSC columns, so cost scales with nonzeros
+            // (shared items) rather than with the number of groups squared
+            let mut intersections: HashMap<u128, usize> = HashMap::new();
+            let mut path_lens: HashMap<u64, usize> = HashMap::new();
+            let mut path_sumsq: HashMap<u64, usize> = HashMap::new();
+            let mut min_sums: HashMap<u128, usize> = HashMap::new();
+            for (index, tuple) in tuples.iter().enumerate() {
+                let weight = if weight_by_bp {
+                    node_lens[index] as usize
+                } else {
+                    1
+                };
+                for x in &c[tuple.0..tuple.1] {
+                    *path_lens.entry(*x).or_insert(0) += weight;
+                    *path_sumsq.entry(*x).or_insert(0) += weight * weight;
+                    for y in &c[tuple.0..tuple.1] {
+                        *intersections
                             .entry((*x as u128) << 64 | *y as u128)
-                            .or_insert(0) += 1;
+                            .or_insert(0) += weight;
+                    }
+                }
+                if track_abundance {
+                    let mut counts: HashMap<u64, usize> = HashMap::new();
+                    for x in &c[tuple.0..tuple.1] {
+                        *counts.entry(*x).or_insert(0) += 1;
+                    }
+                    for (&x, &wx) in &counts {
+                        for (&y, &wy) in &counts {
+                            *min_sums.entry((x as u128) << 64 | y as u128).or_insert(0) +=
+                                wx.min(wy) * weight;
+                        }
                     }
                 }
             }
-        }
 
-        eprintln!("path_lens: {:?}", path_lens);
-
-        let group_count = gb.get_group_count();
-        let mut table: Vec<Vec<f32>> = vec![vec![0.0; group_count]; group_count];
-        for i in 0..group_count {
-            for j in 0..group_count {
-                let intersection = path_similarities
-                    .get(&((i as u128) << 64 | j as u128))
-                    .copied()
-                    .unwrap_or_default();
-                table[i][j] = intersection as f32
-                    / (path_lens[&(i as u64)] + path_lens[&(j as u64)] - intersection) as f32;
+            for i in 0..group_count {
+                for j in 0..group_count {
+                    let intersection = intersections
+                        .get(&((i as u128) << 64 | j as u128))
+                        .copied()
+                        .unwrap_or_default() as f32;
+                    let len_i = path_lens.get(&(i as u64)).copied().unwrap_or_default() as f32;
+                    let len_j = path_lens.get(&(j as u64)).copied().unwrap_or_default() as f32;
+                    table[i][j] = if containment {
+                        // |A ∩ B| / |A| -- the fraction of group i contained in group j;
+                        // asymmetric, so a small group fully nested in a larger one reads 1.0
+                        // instead of being diluted by the size difference
+                        intersection / len_i
+                    } else {
+                        match metric {
+                            SimilarityMetric::Jaccard | SimilarityMetric::BpWeighted => {
+                                intersection / (len_i + len_j - intersection)
+                            }
+                            SimilarityMetric::Dice => 2.0 * intersection / (len_i + len_j),
+                            SimilarityMetric::AbundanceWeighted => {
+                                let min_sum = min_sums
+                                    .get(&((i as u128) << 64 | j as u128))
+                                    .copied()
+                                    .unwrap_or_default() as f32;
+                                min_sum / (len_i + len_j - min_sum)
+                            }
+                            SimilarityMetric::Cosine => {
+                                let sumsq_i =
+                                    path_sumsq.get(&(i as u64)).copied().unwrap_or_default() as f32;
+                                let sumsq_j =
+                                    path_sumsq.get(&(j as u64)).copied().unwrap_or_default() as f32;
+                                intersection / (sumsq_i.sqrt() * sumsq_j.sqrt())
+                            }
+                        }
+                    };
+                }
             }
         }
 
-        let mut distances = calculate_distances(&table);
+        let mut distances = calculate_distances(&table, containment);
 
         let method = match self.parameter {
             AnalysisParameter::Similarity { cluster_method, .. } => cluster_method,
@@ -171,6 +344,13 @@ impl Similarity {
         }
         .to_kodama();
         let dend = linkage(&mut distances, table.len(), method);
+        self.newick = Some(dendrogram_to_newick(&dend, &labels));
+        self.merges = Some(
+            dend.steps()
+                .iter()
+                .map(|step| (step.cluster1, step.cluster2, step.dissimilarity, step.size))
+                .collect(),
+        );
         let order = get_order_from_dendrogram(&dend);
         let mut order = order.into_iter().enumerate().collect::<Vec<_>>();
         order.sort_by_key(|el| el.1);
@@ -190,6 +370,47 @@ impl Similarity {
     }
 }
 
+/// A fixed 64-bit hash (splitmix64) used to turn item ids into MinHash sketch values; fixed
+/// rather than randomly seeded so a sketch is reproducible across runs on the same graph.
+fn hash64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Unbiased bottom-k Jaccard estimator: walk the `k` smallest distinct values of the union of
+/// two sorted sketches and report the fraction of those that occur in both.
+fn estimate_jaccard(a: &[u64], b: &[u64], k: usize) -> f32 {
+    let (mut i, mut j) = (0, 0);
+    let (mut taken, mut both) = (0, 0);
+    while taken < k && (i < a.len() || j < b.len()) {
+        let next = match (a.get(i), b.get(j)) {
+            (Some(&av), Some(&bv)) => av.min(bv),
+            (Some(&av), None) => av,
+            (None, Some(&bv)) => bv,
+            (None, None) => unreachable!(),
+        };
+        let in_a = a.get(i) == Some(&next);
+        let in_b = b.get(j) == Some(&next);
+        if in_a {
+            i += 1;
+        }
+        if in_b {
+            j += 1;
+        }
+        if in_a && in_b {
+            both += 1;
+        }
+        taken += 1;
+    }
+    if taken == 0 {
+        0.0
+    } else {
+        both as f32 / taken as f32
+    }
+}
+
 fn sort_by_indices<T>(list: &mut Vec<T>, indices: &Vec<usize>) {
     let mut indices = indices.clone();
     for i in 0..indices.len() {
@@ -201,6 +422,44 @@ fn sort_by_indices<T>(list: &mut Vec<T>, indices: &Vec<usize>) {
     }
 }
 
+/// Renders a `kodama` dendrogram as a Newick tree string, with branch lengths equal to the
+/// height (dissimilarity) at which each child was merged into its parent. Leaves are labelled
+/// with `labels` in their original (pre-clustering) order, matching the observation indices
+/// `kodama` assigns; merge `i` becomes cluster `observations + i`, same convention used by
+/// [`get_order_from_dendrogram`].
+fn dendrogram_to_newick(dend: &Dendrogram<f32>, labels: &[String]) -> String {
+    let observations = dend.observations();
+    let mut subtree: HashMap<usize, String> = labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| (i, label.clone()))
+        .collect();
+    let mut height: HashMap<usize, f32> = (0..observations).map(|i| (i, 0.0)).collect();
+
+    let steps: Vec<_> = dend.steps().iter().collect();
+    for (i, step) in steps.iter().enumerate() {
+        let h1 = *height.get(&step.cluster1).unwrap_or(&0.0);
+        let h2 = *height.get(&step.cluster2).unwrap_or(&0.0);
+        let s1 = subtree.remove(&step.cluster1).unwrap_or_default();
+        let s2 = subtree.remove(&step.cluster2).unwrap_or_default();
+        let cluster_id = observations + i;
+        subtree.insert(
+            cluster_id,
+            format!(
+                "({}:{},{}:{})",
+                s1,
+                (step.dissimilarity - h1).max(0.0),
+                s2,
+                (step.dissimilarity - h2).max(0.0)
+            ),
+        );
+        height.insert(cluster_id, step.dissimilarity);
+    }
+
+    let root = observations + steps.len().saturating_sub(1);
+    format!("{};", subtree.get(&root).cloned().unwrap_or_default())
+}
+
 fn get_order_from_dendrogram(dend: &Dendrogram<f32>) -> Vec<usize> {
     let observations = dend.observations();
     let mut indices = Vec::new();
@@ -232,20 +491,64 @@ fn get_table_string(table: &Vec<Vec<f32>>, groups: &Vec<String>) -> String {
     res
 }
 
-fn euclidean(row1: &Vec<f32>, row2: &Vec<f32>) -> f32 {
-    row1.iter()
-        .zip(row2.iter())
-        .map(|(v1, v2)| (v1 - v2).powf(2.0))
-        .sum::<f32>()
-        .sqrt()
+/// Renders the same pairwise distances fed into `kodama::linkage` as a PHYLIP-format distance
+/// matrix (taxa count on its own first line, then one row per group of a name padded/truncated
+/// to 10 characters followed by its distance to every other group), so the dendrogram this
+/// analysis already builds can be handed to external phylogenetics tools that expect that
+/// format rather than only this crate's own tsv/Newick output.
+fn get_phylip_string(table: &[Vec<f32>], labels: &[String], containment: bool) -> String {
+    let n = labels.len();
+    let mut res = String::new();
+    res.push_str(&format!("{}\n", n));
+    for row in 0..n {
+        res.push_str(&format_phylip_label(&labels[row]));
+        for col in 0..n {
+            let distance = if row == col {
+                0.0
+            } else if containment {
+                euclidean_distance(&table[row], &table[col])
+            } else {
+                1.0 - table[row][col]
+            };
+            res.push_str(&format!(" {:.6}", distance));
+        }
+        res.push('\n');
+    }
+    res
+}
+
+/// PHYLIP's strict format pads/truncates taxon names to exactly 10 characters.
+fn format_phylip_label(label: &str) -> String {
+    let mut truncated: String = label.chars().take(10).collect();
+    while truncated.len() < 10 {
+        truncated.push(' ');
+    }
+    truncated
 }
 
-fn calculate_distances(table: &Vec<Vec<f32>>) -> Vec<f32> {
+/// Condenses the group-by-group table into the upper-triangle distance array `kodama::linkage`
+/// expects. A symmetric `table` gives `distance = 1 - similarity` directly; an asymmetric
+/// containment matrix has no single `table[i][j]` to read a pairwise distance off of, so rows i
+/// and j are instead compared as containment profiles via Euclidean distance between them.
+fn calculate_distances(table: &Vec<Vec<f32>>, asymmetric: bool) -> Vec<f32> {
     let mut condensed = vec![];
     for row in 0..table.len() - 1 {
         for col in row + 1..table.len() {
-            condensed.push(euclidean(&table[row], &table[col]));
+            let distance = if asymmetric {
+                euclidean_distance(&table[row], &table[col])
+            } else {
+                1.0 - table[row][col]
+            };
+            condensed.push(distance);
         }
     }
     condensed
 }
+
+fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f32>()
+        .sqrt()
+}
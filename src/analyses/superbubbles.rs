@@ -0,0 +1,352 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::graph_broker::GraphBroker;
+use crate::{
+    analyses::InputRequirement, analysis_parameter::AnalysisParameter, html_report::ReportItem,
+    io::write_metadata_comments,
+};
+
+use super::{Analysis, AnalysisSection, ConstructibleAnalysis};
+
+/// One reported superbubble: `entrance`/`exit` are node ids (the same `usize` convention used
+/// by `Cycles`), `interior` is the number of nodes strictly between them that are only
+/// reachable through this entrance/exit pair, and `alleles` is the number of distinct branches
+/// of the bubble that converge on `exit`.
+#[derive(Debug, Clone)]
+struct Bubble {
+    entrance: usize,
+    exit: usize,
+    interior: usize,
+    alleles: usize,
+}
+
+/// A dominator tree, keyed by node id, built with the classical "simple" Lengauer-Tarjan
+/// algorithm: DFS numbering, semidominators computed from an ancestor/label forest with path
+/// compression (EVAL/LINK), and a second pass that resolves semidominators into immediate
+/// dominators.
+struct DominatorTree {
+    idom: HashMap<usize, usize>,
+    root: usize,
+    // dfnum-ordered list of the nodes reachable from `root`
+    vertex: Vec<usize>,
+}
+
+/// Returns a node with the greatest BFS shortest-path distance from `source` (ties broken by
+/// the smallest node id), used to pick a stand-in "opposite end" root for the reverse
+/// dominator tree -- plain DFS-tree depth is not a reliable proxy for this, since a node can
+/// be discovered late (and so appear "deep") along a long detour even though it sits right
+/// next to `source` in the underlying graph.
+fn bfs_farthest(adj: &HashMap<usize, Vec<usize>>, source: usize) -> usize {
+    let mut dist: HashMap<usize, usize> = HashMap::from([(source, 0)]);
+    let mut queue = std::collections::VecDeque::from([source]);
+    while let Some(v) = queue.pop_front() {
+        for &w in adj.get(&v).into_iter().flatten() {
+            if !dist.contains_key(&w) {
+                dist.insert(w, dist[&v] + 1);
+                queue.push_back(w);
+            }
+        }
+    }
+    let max_dist = *dist.values().max().unwrap_or(&0);
+    dist.into_iter()
+        .filter(|&(_, d)| d == max_dist)
+        .map(|(n, _)| n)
+        .min()
+        .unwrap_or(source)
+}
+
+impl DominatorTree {
+    fn build(adj: &HashMap<usize, Vec<usize>>, root: usize) -> Self {
+        let mut dfnum: HashMap<usize, usize> = HashMap::new();
+        let mut vertex: Vec<usize> = Vec::new();
+        let mut parent_df: Vec<usize> = Vec::new();
+        let mut pred_df: Vec<Vec<usize>> = Vec::new();
+
+        // iterative pre-order DFS (recursion would blow the stack on large graphs); a node's
+        // `parent_df` entry is fixed the first time it is popped, matching the tree edge a
+        // recursive DFS would have taken.
+        let mut stack = vec![(root, root)];
+        while let Some((v, par)) = stack.pop() {
+            if dfnum.contains_key(&v) {
+                continue;
+            }
+            let idx = vertex.len();
+            dfnum.insert(v, idx);
+            vertex.push(v);
+            parent_df.push(if idx == 0 { 0 } else { dfnum[&par] });
+            pred_df.push(Vec::new());
+            for &w in adj.get(&v).into_iter().flatten() {
+                if !dfnum.contains_key(&w) {
+                    stack.push((w, v));
+                }
+            }
+        }
+        // predecessor lists are filled in a second pass now that every reachable node has a
+        // dfnum, so self-loops / forward references during the DFS above don't matter
+        for &v in &vertex {
+            for &w in adj.get(&v).into_iter().flatten() {
+                if let Some(&wi) = dfnum.get(&w) {
+                    pred_df[wi].push(dfnum[&v]);
+                }
+            }
+        }
+
+        let n = vertex.len();
+        let mut semi: Vec<usize> = (0..n).collect();
+        let mut ancestor: Vec<Option<usize>> = vec![None; n];
+        let mut label: Vec<usize> = (0..n).collect();
+        let mut bucket: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut idom_df: Vec<usize> = vec![0; n];
+
+        for i in (1..n).rev() {
+            for &v in &pred_df[i] {
+                let u = Self::eval(&mut ancestor, &mut label, &semi, v);
+                if semi[u] < semi[i] {
+                    semi[i] = semi[u];
+                }
+            }
+            bucket[semi[i]].push(i);
+            let p = parent_df[i];
+            ancestor[i] = Some(p);
+            let bucketed = std::mem::take(&mut bucket[p]);
+            for v in bucketed {
+                let u = Self::eval(&mut ancestor, &mut label, &semi, v);
+                idom_df[v] = if semi[u] < semi[v] { u } else { p };
+            }
+        }
+        for i in 1..n {
+            if idom_df[i] != semi[i] {
+                idom_df[i] = idom_df[idom_df[i]];
+            }
+        }
+
+        let idom = (1..n)
+            .map(|i| (vertex[i], vertex[idom_df[i]]))
+            .collect::<HashMap<_, _>>();
+        DominatorTree { idom, root, vertex }
+    }
+
+    fn compress(ancestor: &mut [Option<usize>], label: &mut [usize], semi: &[usize], v: usize) {
+        if let Some(a) = ancestor[v] {
+            if ancestor[a].is_some() {
+                Self::compress(ancestor, label, semi, a);
+                if semi[label[a]] < semi[label[v]] {
+                    label[v] = label[a];
+                }
+                ancestor[v] = ancestor[a];
+            }
+        }
+    }
+
+    fn eval(ancestor: &mut [Option<usize>], label: &mut [usize], semi: &[usize], v: usize) -> usize {
+        if ancestor[v].is_none() {
+            v
+        } else {
+            Self::compress(ancestor, label, semi, v);
+            label[v]
+        }
+    }
+
+    /// Walks `node`'s immediate-dominator chain up to the root, returning `true` if it passes
+    /// through `ancestor` (a node dominates itself).
+    fn dominates(&self, ancestor: usize, node: usize) -> bool {
+        let mut cur = node;
+        loop {
+            if cur == ancestor {
+                return true;
+            }
+            if cur == self.root {
+                return false;
+            }
+            match self.idom.get(&cur) {
+                Some(&p) if p != cur => cur = p,
+                _ => return false,
+            }
+        }
+    }
+}
+
+pub struct Superbubbles {
+    parameter: AnalysisParameter,
+    bubbles: Option<Vec<Bubble>>,
+}
+
+impl Analysis for Superbubbles {
+    fn generate_table(&mut self, gb: Option<&GraphBroker>) -> anyhow::Result<String> {
+        if self.bubbles.is_none() {
+            self.set_bubbles(gb);
+        }
+        let mut text = write_metadata_comments()?;
+        text.push_str("entrance\texit\tinterior\talleles\n");
+        for b in self.bubbles.as_ref().unwrap() {
+            text.push_str(&format!(
+                "{}\t{}\t{}\t{}\n",
+                b.entrance, b.exit, b.interior, b.alleles
+            ));
+        }
+        Ok(text)
+    }
+
+    fn get_type(&self) -> String {
+        "Superbubbles".to_string()
+    }
+
+    fn get_graph_requirements(&self) -> HashSet<InputRequirement> {
+        HashSet::from([InputRequirement::Node, InputRequirement::Edge])
+    }
+
+    fn generate_report_section(
+        &mut self,
+        gb: Option<&GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
+    ) -> anyhow::Result<Vec<AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
+        if self.bubbles.is_none() {
+            self.set_bubbles(gb);
+        }
+        if gb.is_none() {
+            panic!("Superbubbles analysis needs a graph")
+        }
+        let gb = gb.unwrap();
+        let bubbles = self.bubbles.as_ref().unwrap();
+        let id_prefix = format!(
+            "superbubbles-{}",
+            self.get_run_name(gb)
+                .to_lowercase()
+                .replace(&[' ', '|', '\\'], "-")
+        );
+        let header = vec![
+            "entrance".to_string(),
+            "exit".to_string(),
+            "interior".to_string(),
+            "alleles".to_string(),
+        ];
+        let values = bubbles
+            .iter()
+            .map(|b| {
+                vec![
+                    b.entrance.to_string(),
+                    b.exit.to_string(),
+                    b.interior.to_string(),
+                    b.alleles.to_string(),
+                ]
+            })
+            .collect();
+        let countable = bubbles.len().to_string();
+        let tabs = vec![AnalysisSection {
+            id: id_prefix.clone(),
+            analysis: "Superbubbles".to_string(),
+            table: Some(self.generate_table(Some(gb))?),
+            run_name: self.get_run_name(gb),
+            countable,
+            items: vec![ReportItem::Table {
+                id: id_prefix,
+                header,
+                values,
+            }],
+        }];
+        Ok(tabs)
+    }
+}
+
+impl ConstructibleAnalysis for Superbubbles {
+    fn from_parameter(parameter: AnalysisParameter) -> Self {
+        Self {
+            parameter,
+            bubbles: None,
+        }
+    }
+}
+
+impl Superbubbles {
+    /// Reconstructs the node graph from the canonicalized `Edge` set -- every edge is added as
+    /// an arc in both directions, ignoring the endpoint orientations the `Edge` carries -- and
+    /// enumerates superbubbles over it.
+    ///
+    /// A node `s` is reported as the entrance of a bubble with exit `t` when `t`'s immediate
+    /// dominator in a forward dominator tree (rooted arbitrarily, once per connected component)
+    /// is `s`, and symmetrically `s`'s immediate dominator in a second dominator tree -- rooted
+    /// at the BFS-farthest node from the forward root, standing in for the "opposite end" of
+    /// the component -- is `t`. `interior` counts the nodes, excluding `s`
+    /// and `t`, that are simultaneously forward-dominated by `s` and "reverse"-dominated by
+    /// `t`; `alleles` counts `t`'s raw-graph neighbors that lie inside the bubble (i.e. the
+    /// number of branches merging at the exit).
+    fn set_bubbles(&mut self, gb: Option<&GraphBroker>) {
+        let gb = gb.as_ref().unwrap();
+        let min_interior = match self.parameter {
+            AnalysisParameter::Superbubbles { min_interior } => min_interior,
+            _ => panic!("Superbubbles analysis needs Superbubbles parameter"),
+        };
+
+        let mut adj: HashMap<usize, Vec<usize>> = HashMap::new();
+        for n in gb.get_nodes() {
+            adj.entry(n.0 as usize).or_default();
+        }
+        for e in gb.get_edges().keys() {
+            let u = e.0 .0 as usize;
+            let v = e.2 .0 as usize;
+            adj.entry(u).or_default().push(v);
+            adj.entry(v).or_default().push(u);
+        }
+
+        let mut visited: std::collections::HashSet<usize> = std::collections::HashSet::new();
+        let mut bubbles = Vec::new();
+        let mut nodes: Vec<usize> = adj.keys().copied().collect();
+        nodes.sort_unstable();
+
+        for &root in &nodes {
+            if visited.contains(&root) {
+                continue;
+            }
+            let fwd = DominatorTree::build(&adj, root);
+            for &n in &fwd.vertex {
+                visited.insert(n);
+            }
+            if fwd.vertex.len() < 3 {
+                continue;
+            }
+            let rev = DominatorTree::build(&adj, bfs_farthest(&adj, root));
+
+            for &t in &fwd.vertex {
+                let Some(&s) = fwd.idom.get(&t) else {
+                    continue;
+                };
+                if s == t {
+                    continue;
+                }
+                match rev.idom.get(&s) {
+                    Some(&t2) if t2 == t => {}
+                    _ => continue,
+                }
+                let interior = fwd
+                    .vertex
+                    .iter()
+                    .filter(|&&n| n != s && n != t && fwd.dominates(s, n) && rev.dominates(t, n))
+                    .count();
+                if interior < min_interior {
+                    continue;
+                }
+                let alleles = adj
+                    .get(&t)
+                    .into_iter()
+                    .flatten()
+                    .filter(|&&n| n == s || (fwd.dominates(s, n) && rev.dominates(t, n)))
+                    .count();
+                bubbles.push(Bubble {
+                    entrance: s,
+                    exit: t,
+                    interior,
+                    alleles,
+                });
+            }
+        }
+        bubbles.sort_by_key(|b| (b.entrance, b.exit));
+        self.bubbles = Some(bubbles);
+    }
+
+    fn get_run_name(&self, gb: &GraphBroker) -> String {
+        format!("{}-superbubbles", gb.get_run_name())
+    }
+}
@@ -3,10 +3,20 @@ use crate::{
     util::CountType,
 };
 use core::panic;
+use serde::{Deserialize, Serialize};
 use std::{collections::HashSet, io::BufWriter};
 
 use super::{Analysis, AnalysisSection, ConstructibleAnalysis};
 
+/// Which shape `Table::generate_table` writes: the default tab-delimited group/count matrix, or
+/// a `bedGraph`-style per-node coverage track for loading into genome-browser-style tooling.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum TableFormat {
+    #[default]
+    Tsv,
+    BedGraph,
+}
+
 pub struct Table {
     parameter: AnalysisParameter,
 }
@@ -17,12 +27,15 @@ impl Analysis for Table {
         gb: Option<&crate::graph_broker::GraphBroker>,
     ) -> anyhow::Result<String> {
         if let Some(gb) = gb {
-            let total = match self.parameter {
-                AnalysisParameter::Table { total, .. } => total,
+            let (total, format) = match self.parameter {
+                AnalysisParameter::Table { total, format, .. } => (total, format),
                 _ => {
                     panic!("Table analysis needs a table parameter")
                 }
             };
+            if format == TableFormat::BedGraph {
+                return Self::generate_bedgraph(gb);
+            }
             let mut buf = BufWriter::new(Vec::new());
             gb.write_abacus_by_group(total, &mut buf)?;
             let bytes = buf.into_inner()?;
@@ -39,9 +52,15 @@ impl Analysis for Table {
     }
 
     fn get_graph_requirements(&self) -> HashSet<InputRequirement> {
-        if let AnalysisParameter::Table { count_type, .. } = &self.parameter {
+        if let AnalysisParameter::Table {
+            count_type, format, ..
+        } = &self.parameter
+        {
             let mut req = HashSet::from([InputRequirement::AbacusByGroup(*count_type)]);
             req.extend(Self::count_to_input_req(*count_type));
+            if *format == TableFormat::BedGraph {
+                req.insert(InputRequirement::Node);
+            }
             req
         } else {
             HashSet::new()
@@ -51,6 +70,7 @@ impl Analysis for Table {
     fn generate_report_section(
         &mut self,
         _dm: Option<&crate::graph_broker::GraphBroker>,
+        _progress: Option<&crate::progress::Progress>,
     ) -> anyhow::Result<Vec<AnalysisSection>> {
         Ok(Vec::new())
     }
@@ -63,11 +83,45 @@ impl ConstructibleAnalysis for Table {
 }
 
 impl Table {
+    /// Per-node coverage as `seqid  start  end  coverage` rows, for downstream tooling that
+    /// reads indexed interval formats instead of the tab-delimited group/count matrix.
+    ///
+    /// `GraphBroker` exposes no per-path node-walk accessor -- only the aggregate start/end
+    /// `get_path_lens` per group, not the ordered sequence of nodes a reference path actually
+    /// traverses -- so there's no way to project coverage onto real reference-path coordinates.
+    /// Intervals are instead laid out by cumulative node length in `get_nodes()` order (the
+    /// order nodes were read from the GFA), with `get_fname` as a stand-in `seqid` since no
+    /// reference contig name is available either; coverage is the total per-node count from
+    /// `get_abacus_by_total(CountType::Node)`, independent of this table's own `count_type`.
+    fn generate_bedgraph(gb: &crate::graph_broker::GraphBroker) -> anyhow::Result<String> {
+        let node_lens = gb.get_node_lens();
+        let coverage = &gb.get_abacus_by_total(CountType::Node).countable;
+        let seqid = gb.get_fname();
+
+        let mut text = write_metadata_comments()?;
+        let mut offset: u64 = 0;
+        for node in gb.get_nodes() {
+            let id = node.0 as usize;
+            let len = node_lens[id] as u64;
+            let start = offset;
+            let end = offset + len;
+            text.push_str(&format!(
+                "{}\t{}\t{}\t{}\n",
+                seqid, start, end, coverage[id]
+            ));
+            offset = end;
+        }
+        Ok(text)
+    }
+
     fn count_to_input_req(count: CountType) -> HashSet<InputRequirement> {
         match count {
             CountType::Bp => HashSet::from([InputRequirement::Bp]),
             CountType::Node => HashSet::from([InputRequirement::Node]),
             CountType::Edge => HashSet::from([InputRequirement::Edge]),
+            CountType::Kmer => HashSet::from([InputRequirement::Kmer]),
+            CountType::Minimizer => HashSet::from([InputRequirement::Minimizer]),
+            CountType::Branch => HashSet::from([InputRequirement::Branch]),
             CountType::All => HashSet::from([
                 InputRequirement::Bp,
                 InputRequirement::Node,
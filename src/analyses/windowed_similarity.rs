@@ -0,0 +1,271 @@
+use itertools::Itertools;
+
+use crate::graph_broker::GraphBroker;
+use crate::{
+    analyses::InputRequirement, analysis_parameter::AnalysisParameter, html_report::ReportItem,
+    io::write_metadata_comments, util::CountType,
+};
+use std::collections::{HashMap, HashSet};
+
+use super::{Analysis, AnalysisSection, ConstructibleAnalysis};
+
+/// One fixed-size bp window along the node order `GraphBroker` reads nodes from the GFA in, with
+/// the per-group node counts and mean pairwise Jaccard similarity restricted to that window.
+struct Window {
+    start: u64,
+    end: u64,
+    n_nodes: usize,
+    mean_jaccard: f32,
+    group_counts: Vec<usize>,
+}
+
+pub struct WindowedSimilarity {
+    parameter: AnalysisParameter,
+    windows: Option<Vec<Window>>,
+    labels: Option<Vec<String>>,
+}
+
+impl Analysis for WindowedSimilarity {
+    fn generate_table(
+        &mut self,
+        gb: Option<&crate::graph_broker::GraphBroker>,
+    ) -> anyhow::Result<String> {
+        if self.windows.is_none() {
+            self.set_windows(gb);
+        }
+        let windows = self.windows.as_ref().unwrap();
+        let labels = self.labels.as_ref().unwrap();
+
+        let mut text = write_metadata_comments()?;
+        text.push_str("window_start\twindow_end\tn_nodes\tmean_jaccard");
+        for label in labels {
+            text.push_str(&format!("\t{}", label));
+        }
+        text.push('\n');
+        for window in windows {
+            text.push_str(&format!(
+                "{}\t{}\t{}\t{}",
+                window.start, window.end, window.n_nodes, window.mean_jaccard
+            ));
+            for count in &window.group_counts {
+                text.push_str(&format!("\t{}", count));
+            }
+            text.push('\n');
+        }
+        Ok(text)
+    }
+
+    fn get_type(&self) -> String {
+        "WindowedSimilarity".to_string()
+    }
+
+    fn get_graph_requirements(&self) -> HashSet<InputRequirement> {
+        let count = match self.parameter {
+            AnalysisParameter::WindowedSimilarity { count_type, .. } => count_type,
+            _ => panic!("WindowedSimilarity analysis needs WindowedSimilarity parameter"),
+        };
+        let mut req = HashSet::from([InputRequirement::AbacusByGroup(count)]);
+        req.extend(Self::count_to_input_req(count));
+        req
+    }
+
+    fn generate_report_section(
+        &mut self,
+        gb: Option<&crate::graph_broker::GraphBroker>,
+        progress: Option<&crate::progress::Progress>,
+    ) -> anyhow::Result<Vec<AnalysisSection>> {
+        if let Some(p) = progress {
+            p.stage(&format!("running {} analysis", self.get_type()));
+        }
+        if self.windows.is_none() {
+            self.set_windows(gb);
+        }
+        if gb.is_none() {
+            panic!("WindowedSimilarity analysis needs a graph")
+        }
+        let gb = gb.unwrap();
+        let table = self.generate_table(Some(gb))?;
+        let table = format!("`{}`", &table);
+        let id_prefix = format!(
+            "windowed-sim-{}",
+            self.get_run_name(gb)
+                .to_lowercase()
+                .replace(&[' ', '|', '\\'], "-")
+        );
+
+        let windows = self.windows.as_ref().unwrap();
+        let labels = self.labels.as_ref().unwrap();
+        let x_values: Vec<f32> = windows.iter().map(|w| w.start as f32).collect();
+        let y_values: Vec<f32> = windows.iter().map(|w| w.mean_jaccard).collect();
+        let heatmap_values: Vec<Vec<f32>> = windows
+            .iter()
+            .map(|w| w.group_counts.iter().map(|c| *c as f32).collect())
+            .collect();
+        let window_labels: Vec<String> = windows
+            .iter()
+            .map(|w| format!("{}-{}", w.start, w.end))
+            .collect();
+
+        Ok(vec![AnalysisSection {
+            id: id_prefix.clone(),
+            analysis: "Windowed Similarity".to_string(),
+            table: Some(table),
+            run_name: self.get_run_name(gb),
+            countable: "similarity".to_string(),
+            items: vec![
+                ReportItem::Line {
+                    id: format!("{id_prefix}-curve"),
+                    name: gb.get_fname(),
+                    x_label: "window start (bp)".to_string(),
+                    y_label: "mean pairwise Jaccard".to_string(),
+                    x_values,
+                    y_values,
+                    log_x: false,
+                    log_y: false,
+                },
+                ReportItem::Heatmap {
+                    id: format!("{id_prefix}-heatmap"),
+                    name: gb.get_fname(),
+                    x_labels: labels.clone(),
+                    y_labels: window_labels,
+                    values: heatmap_values,
+                },
+            ],
+        }])
+    }
+}
+
+impl ConstructibleAnalysis for WindowedSimilarity {
+    fn from_parameter(parameter: crate::analysis_parameter::AnalysisParameter) -> Self {
+        Self {
+            parameter,
+            windows: None,
+            labels: None,
+        }
+    }
+}
+
+impl WindowedSimilarity {
+    fn count_to_input_req(count: CountType) -> HashSet<InputRequirement> {
+        match count {
+            CountType::Bp => HashSet::from([InputRequirement::Bp]),
+            CountType::Node => HashSet::from([InputRequirement::Node]),
+            CountType::Edge => HashSet::from([InputRequirement::Edge]),
+            CountType::Kmer => HashSet::from([InputRequirement::Kmer]),
+            CountType::Minimizer => HashSet::from([InputRequirement::Minimizer]),
+            CountType::Branch => HashSet::from([InputRequirement::Branch]),
+            CountType::All => HashSet::from([
+                InputRequirement::Bp,
+                InputRequirement::Node,
+                InputRequirement::Edge,
+            ]),
+        }
+    }
+
+    /// Buckets nodes into fixed-size bp windows and runs the same per-node group-intersection
+    /// pass [`crate::analyses::similarity::Similarity`] runs genome-wide, but scoped to each
+    /// window, so divergence can be localized instead of averaged into one genome-wide number.
+    ///
+    /// `GraphBroker` exposes no per-path node-walk/offset accessor -- only the aggregate
+    /// start/end `get_path_lens` per group, not the ordered sequence of nodes a named reference
+    /// path actually traverses -- so there's no way to bucket nodes by real reference-path
+    /// coordinates. Windows are instead tiled over cumulative node length in `get_nodes()` order
+    /// (the order nodes were read from the GFA), the same proxy `Table::generate_bedgraph` uses.
+    /// The first window always starts at offset 0 and the last window is always emitted, clipped
+    /// to the graph's total node length even if shorter than `window_size`.
+    fn set_windows(&mut self, gb: Option<&crate::graph_broker::GraphBroker>) {
+        let gb = gb.as_ref().unwrap();
+        let r = &gb.get_abacus_by_group().r;
+        let c = &gb.get_abacus_by_group().c;
+        let labels = gb.get_abacus_by_group().groups.clone();
+        let group_count = gb.get_group_count();
+        let node_lens = gb.get_node_lens();
+
+        let (window_size, step) = match self.parameter {
+            AnalysisParameter::WindowedSimilarity {
+                window_size, step, ..
+            } => (window_size, step.unwrap_or(window_size).max(1)),
+            _ => panic!("WindowedSimilarity analysis needs WindowedSimilarity parameter"),
+        };
+
+        let tuples: Vec<(usize, usize)> = r.iter().map(|x| *x as usize).tuple_windows().collect();
+        let mut offsets = Vec::with_capacity(tuples.len());
+        let mut offset = 0u64;
+        for index in 0..tuples.len() {
+            offsets.push(offset);
+            offset += node_lens[index] as u64;
+        }
+        let total_len = offset;
+
+        let mut starts = vec![0u64];
+        let mut start = step;
+        while start < total_len {
+            starts.push(start);
+            start += step;
+        }
+
+        let mut windows = Vec::with_capacity(starts.len());
+        for win_start in starts {
+            let win_end = (win_start + window_size).min(total_len);
+            let mut intersections: HashMap<u128, usize> = HashMap::new();
+            let mut group_node_count: HashMap<u64, usize> = HashMap::new();
+            let mut n_nodes = 0;
+            for (index, tuple) in tuples.iter().enumerate() {
+                if offsets[index] < win_start || offsets[index] >= win_end {
+                    continue;
+                }
+                n_nodes += 1;
+                let occurring = &c[tuple.0..tuple.1];
+                for &x in occurring {
+                    *group_node_count.entry(x).or_insert(0) += 1;
+                }
+                for &x in occurring {
+                    for &y in occurring {
+                        *intersections.entry((x as u128) << 64 | y as u128).or_insert(0) += 1;
+                    }
+                }
+            }
+
+            let mut jaccard_sum = 0.0f32;
+            let mut pair_count = 0usize;
+            for i in 0..group_count as u64 {
+                for j in (i + 1)..group_count as u64 {
+                    let intersection = intersections
+                        .get(&((i as u128) << 64 | j as u128))
+                        .copied()
+                        .unwrap_or_default() as f32;
+                    let len_i = group_node_count.get(&i).copied().unwrap_or_default() as f32;
+                    let len_j = group_node_count.get(&j).copied().unwrap_or_default() as f32;
+                    let union = len_i + len_j - intersection;
+                    if union > 0.0 {
+                        jaccard_sum += intersection / union;
+                        pair_count += 1;
+                    }
+                }
+            }
+            let mean_jaccard = if pair_count > 0 {
+                jaccard_sum / pair_count as f32
+            } else {
+                0.0
+            };
+            let group_counts = (0..group_count as u64)
+                .map(|g| group_node_count.get(&g).copied().unwrap_or_default())
+                .collect();
+
+            windows.push(Window {
+                start: win_start,
+                end: win_end,
+                n_nodes,
+                mean_jaccard,
+                group_counts,
+            });
+        }
+
+        self.windows = Some(windows);
+        self.labels = Some(labels);
+    }
+
+    fn get_run_name(&self, gb: &GraphBroker) -> String {
+        format!("{}-windowed-similarity", gb.get_run_name())
+    }
+}
@@ -2,19 +2,32 @@ use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
+
+use clap::ArgMatches;
 use strum_macros::{EnumIter, EnumString, EnumVariantNames};
 
 use serde::{Deserialize, Serialize};
 
 use crate::analyses::ConstructibleAnalysis;
 use crate::analyses::{
-    coverage_line::CoverageLine, growth::Growth, info::Info, node_distribution::NodeDistribution,
-    ordered_histgrowth::OrderedHistgrowth, similarity::Similarity, table::Table,
+    components::Components,
+    counts::{Count, CountsHistogram},
+    coverage_line::CoverageLine,
+    cycles::Cycles,
+    export::Export,
+    geodesic::Geodesic,
+    growth::{Growth, ReportFormat},
+    info::Info,
+    node_distribution::NodeDistribution,
+    ordered_histgrowth::OrderedHistgrowth,
+    similarity::Similarity,
+    superbubbles::Superbubbles,
+    table::{Table, TableFormat},
 };
 use crate::Analysis;
 use crate::{
     analyses::{hist::Hist, InputRequirement},
-    util::CountType,
+    util::{CountType, OverlapMode},
 };
 
 macro_rules! get_analysis_task {
@@ -40,10 +53,6 @@ pub enum Task {
     },
     OrderChange(Option<String>),
     AbacusByGroupCSCChange,
-    CustomSection {
-        name: String,
-        file: String,
-    },
 }
 
 impl Debug for Task {
@@ -70,11 +79,6 @@ impl Debug for Task {
                 .finish(),
             Self::OrderChange(order) => f.debug_tuple("OrderChange").field(&order).finish(),
             Self::AbacusByGroupCSCChange => f.debug_tuple("AbacusByGroupCSCChange").finish(),
-            Self::CustomSection { name, file } => f
-                .debug_tuple("CustomSection")
-                .field(name)
-                .field(file)
-                .finish(),
         }
     }
 }
@@ -124,19 +128,55 @@ impl AnalysisRun {
             analyses: vec![
                 AnalysisParameter::Hist {
                     count_type: CountType::Bp,
+                    report_format: ReportFormat::Table,
+                    normalize: None,
+                    bins: None,
+                    bin_scale: BinScale::Linear,
+                    soft_core_cutoff: get_soft_core_cutoff(),
+                    interval: None,
+                    bounds: None,
+                    min_bucket_count: 0,
+                    cumulative: None,
                 },
                 AnalysisParameter::Growth {
                     coverage: Some("1,1,2".to_string()),
                     quorum: Some("0,0.9,0".to_string()),
                     add_hist: false,
+                    hist_file: None,
+                    report_format: ReportFormat::Table,
+                    bootstrap: None,
+                    seed: None,
+                },
+                AnalysisParameter::Info {
+                    clustering_sample_size: None,
+                },
+                AnalysisParameter::NodeDistribution {
+                    radius: 20,
+                    bin_mode: BinMode::Hex,
+                    log_density: false,
+                    knn_k: None,
+                    log_x: false,
+                    log_y: false,
+                    weight_by_length: false,
                 },
-                AnalysisParameter::Info,
-                AnalysisParameter::NodeDistribution { radius: 20 },
             ],
         }
     }
 
+    /// This is panacus' single-pass orchestrator: every [`AnalysisParameter`] in a run is turned
+    /// into a `Task::Analysis` by [`to_tasks`](Self::to_tasks), which also unions each one's
+    /// [`InputRequirement`] set into the `reqs` carried by the run's `Task::GraphStateChange` --
+    /// so when a user asks for, say, `hist`, `growth`, and `node_distribution` together, the
+    /// graph/abacus data those three jointly need is figured out once, up front, rather than each
+    /// analysis driving its own traversal. `execute_pipeline` then hands every analysis in the
+    /// batch the same already-materialized [`GraphBroker`](crate::graph_broker::GraphBroker)
+    /// through `gb: Option<&GraphBroker>` -- analyses consume that shared state in
+    /// `generate_report_section`/`generate_table` rather than owning any I/O themselves. And
+    /// across runs, `GraphBroker::change_graph_state` skips re-parsing the GFA entirely when
+    /// consecutive `GraphStateChange`s name the same graph file and cache dir, so stacking
+    /// multiple runs against one graph doesn't cost a second full read either.
     pub fn convert_to_tasks(mut runs: Vec<Self>) -> Vec<Task> {
+        crate::analyses::register_builtin_analyses();
         runs.sort();
         let mut tasks = Vec::new();
         for i in 0..runs.len() {
@@ -178,12 +218,59 @@ pub enum AnalysisParameter {
     Hist {
         #[serde(default)]
         count_type: CountType,
+        #[serde(default)]
+        report_format: ReportFormat,
+        #[serde(default)]
+        normalize: Option<NormalizeMode>,
+        #[serde(default)]
+        bins: Option<usize>,
+        #[serde(default)]
+        bin_scale: BinScale,
+        // fraction of groups a countable must appear in to count as "soft-core" rather than
+        // "shell"; countables present in every group are always "core" regardless of this cutoff
+        #[serde(default = "get_soft_core_cutoff")]
+        soft_core_cutoff: f64,
+        // fixed-width bucket size in coverage levels; takes priority over `bins` when set, since
+        // a fixed interval (unlike a fixed bin *count*) stays meaningful when comparing hists
+        // from graphs with different numbers of paths
+        #[serde(default)]
+        interval: Option<usize>,
+        // inclusive `[min, max]` coverage-level range `interval` buckets are tiled over; levels
+        // outside the range are dropped rather than folded into the first/last bucket. Ignored
+        // without `interval`; defaults to the hist's full observed range when `interval` is set
+        // but this isn't
+        #[serde(default)]
+        bounds: Option<(usize, usize)>,
+        // drop buckets whose summed count falls below this, so a long tail of near-empty
+        // buckets doesn't pad out an otherwise compact interval histogram
+        #[serde(default)]
+        min_bucket_count: usize,
+        // emits the running sum across coverage levels instead of the per-level count, turning
+        // the histogram into an empirical cumulative distribution; composes with `normalize`
+        // (applied first), since summing fractions rather than raw counts is what makes the
+        // result end at 1 (or 100%) regardless of graph size
+        #[serde(default)]
+        cumulative: Option<CumulativeDirection>,
     },
     Growth {
         coverage: Option<String>,
         quorum: Option<String>,
         #[serde(default)]
         add_hist: bool,
+        #[serde(default)]
+        hist_file: Option<String>,
+        #[serde(default)]
+        report_format: ReportFormat,
+        // bootstrap confidence bands: re-run the growth curve over this many random genome
+        // permutations, drawn from the per-group presence matrix (not the collapsed `Hist`,
+        // which has lost genome identity), and report per-point percentile bands alongside the
+        // exact expectation curve. `None`/`0` skips the bootstrap entirely, leaving the
+        // existing single-curve behavior unchanged; see `OrderedGrowth::permutations` for the
+        // equivalent knob on the ordered-growth curve, whose bootstrap machinery this reuses.
+        #[serde(default)]
+        bootstrap: Option<usize>,
+        #[serde(default)]
+        seed: Option<u64>,
     },
     Table {
         #[serde(default)]
@@ -191,12 +278,43 @@ pub enum AnalysisParameter {
 
         total: bool,
         order: Option<String>,
+        #[serde(default)]
+        normalize: Option<NormalizeMode>,
+        #[serde(default)]
+        format: TableFormat,
     },
     NodeDistribution {
         #[serde(default = "get_radius")]
         radius: u32,
+        #[serde(default)]
+        bin_mode: BinMode,
+        // log10-normalize each bin's point count before it's used as the color-scale value,
+        // so a few hot bins don't wash out the rest on large graphs
+        #[serde(default)]
+        log_density: bool,
+        // if set, replace each bin's raw point count with a k-nearest-neighbor density estimate
+        // (see `Bin::apply_knn_density`) instead of a raw count; takes precedence over
+        // `log_density` since it already adapts to local point density on its own
+        #[serde(default)]
+        knn_k: Option<u32>,
+        // bin the coverage/length axes in log10(1+value) space instead of linearly, so a
+        // heavy-tailed axis doesn't waste almost all bins on a handful of outliers
+        #[serde(default)]
+        log_x: bool,
+        #[serde(default)]
+        log_y: bool,
+        // sum each member node's length (bp) into its bin instead of just counting members, so
+        // a bin's shading reflects how much sequence it holds, not how many nodes fall in it
+        #[serde(default)]
+        weight_by_length: bool,
+    },
+    Info {
+        // number of random nodes to sample when estimating the average clustering
+        // coefficient; if unset, falls back to an exact computation below
+        // `Info::CLUSTERING_EXACT_NODE_THRESHOLD` nodes and to a default sample size above it
+        #[serde(default)]
+        clustering_sample_size: Option<usize>,
     },
-    Info,
     OrderedGrowth {
         coverage: Option<String>,
         quorum: Option<String>,
@@ -204,6 +322,41 @@ pub enum AnalysisParameter {
 
         #[serde(default)]
         count_type: CountType,
+
+        // bootstrap confidence bands: re-run the growth curve over this many random group
+        // permutations and report per-point percentile bands alongside the fixed-order curve;
+        // `None`/`0` skips the bootstrap entirely, leaving the single-curve behavior unchanged
+        #[serde(default)]
+        permutations: Option<usize>,
+        #[serde(default)]
+        seed: Option<u64>,
+        // resolution strategy for a `--subset`/`--exclude` BED interval that a countable
+        // straddles; see `crate::util::OverlapMode`. `None` keeps the existing `Union` behavior.
+        // The BED-region decision itself is made before an analysis ever sees the abacus (it's
+        // baked into which countables exist by the time `GraphBroker` hands one over), so this
+        // is threaded through here for the run that asked for it rather than applied in-place
+        #[serde(default)]
+        overlap_mode: Option<OverlapMode>,
+        // upper bound on a countable's coverage, parsed the same comma-separated, absolute/
+        // relative/percentage way as `coverage`/`quorum` (see `crate::util::parse_threshold_cli`
+        // with `RequireThreshold::Either`); a countable present in more groups than this is
+        // dropped from the growth curve just like `quorum` drops one present in too few, letting
+        // `coverage`/`max_coverage` together isolate accessory content. `None` leaves every
+        // countable's upper bound unrestricted
+        #[serde(default)]
+        max_coverage: Option<String>,
+        // collapse groups whose countable sets are identical (or, with `normalize_threshold` set,
+        // Jaccard-similar at or above that cutoff) into a single representative before computing
+        // the growth curve, so duplicated-content groups don't inflate the curve by being counted
+        // separately. `--subset`/`--groupby-*` resolution happens before an analysis sees the
+        // abacus (same caveat as `overlap_mode` above), so normalization here operates on whatever
+        // groups `GraphBroker` already handed over rather than raw paths
+        #[serde(default)]
+        normalize_paths: Option<bool>,
+        // Jaccard-similarity cutoff for `normalize_paths`, in `[0, 1]`. `None` defaults to `1.0`,
+        // i.e. only exactly-identical groups are merged
+        #[serde(default)]
+        normalize_threshold: Option<f64>,
     },
     CoverageLine {
         #[serde(default)]
@@ -215,10 +368,68 @@ pub enum AnalysisParameter {
         count_type: CountType,
         #[serde(default)]
         cluster_method: ClusterMethod,
+        #[serde(default)]
+        metric: SimilarityMetric,
+        // bottom-k MinHash sketch size; `None` always uses the exact O(nodes * groups^2)
+        // computation. When set, [`Similarity::set_table`] still falls back to the exact path
+        // itself below `Similarity::MIN_GROUPS_FOR_SKETCH` groups, where the approximation buys
+        // nothing.
+        #[serde(default)]
+        sketch_k: Option<usize>,
+        // report `|A ∩ B| / |A|` instead of a symmetric similarity, so a small group contained
+        // in a much larger one still shows up instead of being swamped by the size difference;
+        // forces the exact (non-sketched) computation, since containment isn't a MinHash-style
+        // Jaccard estimate
+        #[serde(default)]
+        containment: bool,
+    },
+    WindowedSimilarity {
+        #[serde(default)]
+        count_type: CountType,
+        window_size: u64,
+        // defaults to `window_size` (non-overlapping tiling) when unset
+        #[serde(default)]
+        step: Option<u64>,
     },
-    Custom {
-        name: String,
-        file: String,
+    Cycles {
+        #[serde(default = "get_cycles_min_size")]
+        min_size: usize,
+    },
+    Superbubbles {
+        // drop bubbles with fewer interior nodes than this from the report; 0 reports every
+        // dominator-pair that qualifies, including trivial single-edge bubbles
+        #[serde(default)]
+        min_interior: usize,
+    },
+    Components {
+        #[serde(default)]
+        count_type: CountType,
+    },
+    Geodesic {
+        from: u64,
+        to: u64,
+    },
+    Export {
+        // restrict the exported DOT graph to the subgraph reachable from `from` (with `to` set
+        // too, a warning is logged when `to` is not reachable); `to` without `from` is ignored
+        // and the whole graph is exported
+        #[serde(default)]
+        from: Option<u64>,
+        #[serde(default)]
+        to: Option<u64>,
+    },
+    /// Delegates to whatever backend is registered under `name` in the
+    /// `analyses::{register_analysis, construct_custom_analysis}` registry, instead of being a
+    /// fixed, built-in variant like `Hist`/`Growth`/etc. `params` is raw JSON text (kept as a
+    /// `String`, like every other free-form field on this enum, so `AnalysisParameter` can keep
+    /// deriving `Eq`/`Hash`/`Ord` -- `serde_json::Value` implements none of those); the backend
+    /// parses it into its own `AnalysisParameter` shape. Lets a new analysis become a
+    /// registration rather than edits across this enum, the `get_analysis_task!` sites, and
+    /// `Analysis` itself.
+    Custom { name: String, params: String },
+    Counts {
+        #[serde(default)]
+        histogram: Option<CountsHistogram>,
     },
 }
 
@@ -227,6 +438,11 @@ pub enum Grouping {
     Sample,
     Haplotype,
     Custom(String),
+    /// Group paths by a hand-rolled, anchored regex-lite pattern (literals, `.`, `\d`, `\w`,
+    /// `*`/`+` quantifiers -- see `util::pattern_match`) matched against the full path name,
+    /// e.g. `sample\d+#1#.*`. Each distinct matching substring captured by a `*`/`+` run forms
+    /// its own group, mirroring how `Custom` groups by the value in a mapping file.
+    Pattern(String),
 }
 
 impl Display for Grouping {
@@ -235,14 +451,125 @@ impl Display for Grouping {
             Self::Sample => write!(f, "Group By Sample"),
             Self::Haplotype => write!(f, "Group By Haplotype"),
             Self::Custom(file) => write!(f, "Group By {}", file),
+            Self::Pattern(pattern) => write!(f, "Group By Pattern {}", pattern),
         }
     }
 }
 
+/// The `--groupby`/`--groupby-haplotype`/`--groupby-sample` args shared verbatim by every
+/// grouping-aware subcommand (hist, histgrowth, ordered-histgrowth, table, growth, info,
+/// similarity). Centralized here instead of copy-pasted per command so their help text and
+/// flag names can't drift out of sync with each other.
+pub fn groupby_args() -> [clap::Arg; 3] {
+    [
+        clap::arg!(-g --groupby <FILE> "Merge counts from paths by path-group mapping from given tab-separated two-column file"),
+        clap::arg!(-H --"groupby-haplotype" "Merge counts from paths belonging to same haplotype"),
+        clap::arg!(-S --"groupby-sample" "Merge counts from paths belonging to same sample"),
+    ]
+}
+
+/// Wraps the `groupby_args` flags (plus any command-specific grouping flags in `extra_ids`,
+/// e.g. `growth`'s `--groupby-pattern`) into a required-at-most-once `ArgGroup`, so clap
+/// rejects conflicting combinations like `--groupby-haplotype --groupby-sample` at parse time
+/// instead of one silently winning -- borrowing alevin-fry's use of `clap::builder::ArgGroup`
+/// for the same kind of mutually-exclusive flag set.
+pub fn groupby_arggroup(extra_ids: &[&'static str]) -> clap::builder::ArgGroup {
+    clap::builder::ArgGroup::new("grouping")
+        .args(["groupby", "groupby-haplotype", "groupby-sample"])
+        .args(extra_ids)
+        .multiple(false)
+}
+
+/// Resolves the `groupby_args` flags into a `Grouping`, for subcommands with no grouping
+/// flags of their own beyond the shared three. Commands with an extra flag (e.g. `growth`'s
+/// `--groupby-pattern`) check that flag first and fall back to this for the shared three.
+pub fn parse_groupby(args: &ArgMatches) -> Option<Grouping> {
+    if args.get_flag("groupby-sample") {
+        Some(Grouping::Sample)
+    } else if args.get_flag("groupby-haplotype") {
+        Some(Grouping::Haplotype)
+    } else {
+        args.get_one::<String>("groupby")
+            .cloned()
+            .map(Grouping::Custom)
+    }
+}
+
 fn get_radius() -> u32 {
     20
 }
 
+fn get_cycles_min_size() -> usize {
+    2
+}
+
+fn get_soft_core_cutoff() -> f64 {
+    0.95
+}
+
+/// How a coverage histogram/table column should be rescaled before being written out: as a
+/// fraction of the column sum, or as that fraction expressed as a percentage.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum NormalizeMode {
+    Fraction,
+    Percentage,
+}
+
+impl Display for NormalizeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fraction => write!(f, "fraction"),
+            Self::Percentage => write!(f, "percentage"),
+        }
+    }
+}
+
+/// Which way `AnalysisParameter::Hist::cumulative` accumulates: low-to-high coverage levels
+/// (the usual "at least this many groups" reading) or high-to-low ("at most this many groups").
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum CumulativeDirection {
+    Ascending,
+    Descending,
+}
+
+impl Display for CumulativeDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ascending => write!(f, "cumulative-ascending"),
+            Self::Descending => write!(f, "cumulative-descending"),
+        }
+    }
+}
+
+/// How coverage levels are grouped into bins when `AnalysisParameter::Hist::bins` is set:
+/// equal-width intervals, or geometrically spaced ones that give low coverage levels more
+/// resolution.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum BinScale {
+    Linear,
+    Log,
+}
+
+impl Default for BinScale {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+/// How `AnalysisParameter::NodeDistribution` aggregates coverage-vs-log-length points into
+/// bins: a staggered hexagonal grid, or a plain rectangular grid.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum BinMode {
+    Hex,
+    Square,
+}
+
+impl Default for BinMode {
+    fn default() -> Self {
+        Self::Hex
+    }
+}
+
 impl AnalysisParameter {
     pub fn into_tasks(self) -> (Vec<Task>, HashSet<InputRequirement>) {
         match self {
@@ -255,7 +582,7 @@ impl AnalysisParameter {
             n @ Self::NodeDistribution { .. } => {
                 get_analysis_task!(NodeDistribution, n)
             }
-            i @ Self::Info => {
+            i @ Self::Info { .. } => {
                 get_analysis_task!(Info, i)
             }
             ref o @ Self::OrderedGrowth { ref order, .. } => {
@@ -270,11 +597,35 @@ impl AnalysisParameter {
             s @ Self::Similarity { .. } => {
                 get_analysis_task!(Similarity, s)
             }
+            c @ Self::Cycles { .. } => {
+                get_analysis_task!(Cycles, c)
+            }
+            s @ Self::Superbubbles { .. } => {
+                get_analysis_task!(Superbubbles, s)
+            }
+            c @ Self::Components { .. } => {
+                get_analysis_task!(Components, c)
+            }
+            g @ Self::Geodesic { .. } => {
+                get_analysis_task!(Geodesic, g)
+            }
+            e @ Self::Export { .. } => {
+                get_analysis_task!(Export, e)
+            }
             t @ Self::Table { .. } => {
                 get_analysis_task!(Table, t)
             }
-            Self::Custom { name, file } => {
-                (vec![Task::CustomSection { name, file }], HashSet::new())
+            Self::Custom { name, params } => {
+                let value: serde_json::Value = serde_json::from_str(&params).unwrap_or_else(|e| {
+                    panic!("custom analysis '{name}' has invalid params JSON: {e}")
+                });
+                let analysis = crate::analyses::construct_custom_analysis(&name, value)
+                    .unwrap_or_else(|e| panic!("failed to construct custom analysis '{name}': {e}"));
+                let reqs = analysis.get_graph_requirements();
+                (vec![Task::Analysis(analysis)], reqs)
+            }
+            c @ Self::Counts { .. } => {
+                get_analysis_task!(Count, c)
             }
         }
     }
@@ -326,6 +677,62 @@ impl ClusterMethod {
     }
 }
 
+/// How pairwise group-to-group similarity is defined over the sparse group-by-item matrix
+/// (`AbacusByGroup::to_csc`) before clustering: `Jaccard`/`Dice` on presence/absence overlap,
+/// `Cosine` over coverage-weighted vectors, `BpWeighted`, which reuses the `Jaccard` ratio but
+/// weights each shared item by its `node_lens` value rather than counting it as 1 regardless of
+/// the analysis's `count_type`, or `AbundanceWeighted`, a min/max generalization of `Jaccard`
+/// over per-group coverage counts instead of presence/absence.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    EnumString,
+    EnumVariantNames,
+    EnumIter,
+    Hash,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum SimilarityMetric {
+    Jaccard,
+    Dice,
+    Cosine,
+    BpWeighted,
+    // Jaccard generalized from set membership to per-group coverage multiplicities:
+    // `sum(min(w_i, w_j)) / sum(max(w_i, w_j))` over nodes, where `w_g` is how many times
+    // group g's paths touch a given node. See `Similarity::set_table`'s exact path for the
+    // caveat on how faithfully the current `AbacusByGroup` data actually captures `w_g`.
+    AbundanceWeighted,
+}
+
+impl Default for SimilarityMetric {
+    fn default() -> Self {
+        Self::Jaccard
+    }
+}
+
+impl fmt::Display for SimilarityMetric {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{}",
+            match self {
+                Self::Jaccard => "jaccard",
+                Self::Dice => "dice",
+                Self::Cosine => "cosine",
+                Self::BpWeighted => "bp-weighted",
+                Self::AbundanceWeighted => "abundance-weighted",
+            }
+        )
+    }
+}
+
 impl fmt::Display for ClusterMethod {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         write!(
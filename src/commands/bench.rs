@@ -0,0 +1,98 @@
+use std::io::Write;
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use serde::{Deserialize, Serialize};
+
+use crate::analysis_parameter::AnalysisRun;
+use crate::TaskBenchResult;
+
+pub fn get_subcommand() -> Command {
+    Command::new("bench")
+        .about("Run one or more JSON workload files and emit machine-readable per-task timing/memory results")
+        .args(&[
+            Arg::new("workload_files")
+                .required(true)
+                .num_args(1..)
+                .trailing_var_arg(true)
+                .help("One or more workload JSON files, each `{ \"name\": ..., \"runs\": [AnalysisRun, ...] }`"),
+            Arg::new("tsv")
+                .long("tsv")
+                .action(ArgAction::SetTrue)
+                .help("Instead of the JSON results document, print one tab-separated line per task"),
+        ])
+}
+
+/// A named pipeline to benchmark: a workload file is just the JSON array `runs` would take in a
+/// `report --json`-style config, wrapped with a `name` so a results document can say what ran.
+#[derive(Deserialize)]
+struct Workload {
+    name: String,
+    runs: Vec<AnalysisRun>,
+}
+
+#[derive(Serialize)]
+struct BenchResult {
+    workload_name: String,
+    commit: String,
+    tasks: Vec<TaskBenchResult>,
+}
+
+/// There is no `build.rs` in this tree to bake `git rev-parse HEAD` into the binary at compile
+/// time, so the commit a result document is attributed to is read from an environment variable
+/// CI is expected to set (e.g. `PANACUS_COMMIT=$(git rev-parse HEAD) panacus bench ...`),
+/// falling back to "unknown" for local ad-hoc runs.
+fn current_commit() -> String {
+    std::env::var("PANACUS_COMMIT").unwrap_or_else(|_| "unknown".to_string())
+}
+
+pub fn run<W: Write>(
+    args: &ArgMatches,
+    out: &mut std::io::BufWriter<W>,
+) -> Option<anyhow::Result<()>> {
+    let args = args.subcommand_matches("bench")?;
+    Some(run_bench(args, out))
+}
+
+fn run_bench<W: Write>(args: &ArgMatches, out: &mut std::io::BufWriter<W>) -> anyhow::Result<()> {
+    let workload_files: Vec<String> = args
+        .get_many::<String>("workload_files")
+        .expect("bench subcommand has workload_files")
+        .cloned()
+        .collect();
+    let tsv = args.get_flag("tsv");
+
+    let mut results = Vec::with_capacity(workload_files.len());
+    for file_path in &workload_files {
+        let contents = std::fs::read_to_string(file_path)?;
+        let workload: Workload = serde_json::from_str(&contents)?;
+        let tasks = crate::get_tasks(workload.runs)?;
+        let tasks = crate::execute_pipeline_benchmarked(tasks)?;
+        results.push(BenchResult {
+            workload_name: workload.name,
+            commit: current_commit(),
+            tasks,
+        });
+    }
+
+    if tsv {
+        writeln!(out, "workload\tcommit\ttask_type\tseconds\tpeak_bytes")?;
+        for result in &results {
+            for task in &result.tasks {
+                writeln!(
+                    out,
+                    "{}\t{}\t{}\t{}\t{}",
+                    result.workload_name,
+                    result.commit,
+                    task.task_type,
+                    task.seconds,
+                    task.peak_bytes
+                )?;
+            }
+        }
+    } else if results.len() == 1 {
+        writeln!(out, "{}", serde_json::to_string_pretty(&results[0])?)?;
+    } else {
+        writeln!(out, "{}", serde_json::to_string_pretty(&results)?)?;
+    }
+    Ok(())
+}
@@ -1,24 +1,64 @@
-use clap::{arg, ArgMatches, Command};
+use clap::{arg, Arg, ArgAction, ArgMatches, Command};
 
-use crate::analysis_parameter::AnalysisParameter;
+use crate::analyses::counts::CountsHistogram;
+use crate::analysis_parameter::{AnalysisParameter, AnalysisRun};
 
 pub fn get_subcommand() -> Command {
     Command::new("counts")
         .about("Return list of nodes with coverages and lenghts")
         .args(&[
             arg!(gfa_file: <GFA_FILE> "graph in GFA1 format, accepts also compressed (.gz) file"),
+            Arg::new("histogram")
+                .long("histogram")
+                .action(ArgAction::SetTrue)
+                .help("Group nodes into coverage buckets instead of emitting one row per node"),
+            Arg::new("bins")
+                .long("bins")
+                .value_name("N")
+                .help("Number of equal-width coverage buckets to use with --histogram"),
+            Arg::new("breakpoints")
+                .long("breakpoints")
+                .value_name("LIST")
+                .conflicts_with("bins")
+                .help("Comma-separated list of coverage breakpoints to use with --histogram instead of equal-width bins"),
         ])
 }
 
 pub fn get_instructions(
     args: &ArgMatches,
-) -> Option<Result<Vec<AnalysisParameter>, anyhow::Error>> {
+) -> Option<Result<Vec<AnalysisRun>, anyhow::Error>> {
     if let Some(args) = args.subcommand_matches("counts") {
         let graph = args
             .get_one::<String>("gfa_file")
-            .expect("info subcommand has gfa file")
+            .expect("counts subcommand has gfa file")
             .to_owned();
-        Some(Ok(vec![AnalysisParameter::Counts { graph }]))
+        let histogram = if args.get_flag("histogram") {
+            if let Some(breakpoints) = args.get_one::<String>("breakpoints") {
+                let points = breakpoints
+                    .split(',')
+                    .map(|x| x.parse::<usize>())
+                    .collect::<Result<Vec<_>, _>>()?;
+                Some(CountsHistogram::Breakpoints(points))
+            } else {
+                let bins = args
+                    .get_one::<String>("bins")
+                    .map(|x| x.parse::<usize>())
+                    .transpose()?
+                    .unwrap_or(10);
+                Some(CountsHistogram::EqualWidth { bins })
+            }
+        } else {
+            None
+        };
+        Some(Ok(vec![AnalysisRun::new(
+            graph,
+            None,
+            String::new(),
+            String::new(),
+            None,
+            false,
+            vec![AnalysisParameter::Counts { histogram }],
+        )]))
     } else {
         None
     }
@@ -0,0 +1,27 @@
+use clap::{arg, ArgMatches, Command};
+
+use crate::analysis_parameter::AnalysisParameter;
+
+pub fn get_subcommand() -> Command {
+    Command::new("cycles")
+        .about("Enumerate cycles (strongly-connected components and self-loops) in the graph")
+        .args(&[
+            arg!(gfa_file: <GFA_FILE> "graph in GFA1 format, accepts also compressed (.gz) file"),
+            arg!(-s --"min-size" <SIZE> "Only report cycles with at least this many nodes")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("2"),
+        ])
+}
+
+pub fn get_instructions(args: &ArgMatches) -> Option<anyhow::Result<Vec<AnalysisParameter>>> {
+    if let Some(args) = args.subcommand_matches("cycles") {
+        let min_size = *args
+            .get_one::<usize>("min-size")
+            .expect("cycles subcommand has min-size");
+        let parameters = vec![AnalysisParameter::Cycles { min_size }];
+        log::info!("{parameters:?}");
+        Some(Ok(parameters))
+    } else {
+        None
+    }
+}
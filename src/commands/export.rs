@@ -0,0 +1,28 @@
+use clap::{arg, ArgMatches, Command};
+
+use crate::analysis_parameter::AnalysisParameter;
+
+pub fn get_subcommand() -> Command {
+    Command::new("export")
+        .about("Export the graph to GraphViz DOT, optionally restricted to the subgraph connecting two nodes")
+        .args(&[
+            arg!(gfa_file: <GFA_FILE> "graph in GFA1 format, accepts also compressed (.gz) file"),
+            arg!(--from <NODE_ID> "Only export the subgraph reachable from this node")
+                .value_parser(clap::value_parser!(u64)),
+            arg!(--to <NODE_ID> "Only export the subgraph connecting --from to this node")
+                .value_parser(clap::value_parser!(u64))
+                .requires("from"),
+        ])
+}
+
+pub fn get_instructions(args: &ArgMatches) -> Option<anyhow::Result<Vec<AnalysisParameter>>> {
+    if let Some(args) = args.subcommand_matches("export") {
+        let from = args.get_one::<u64>("from").copied();
+        let to = args.get_one::<u64>("to").copied();
+        let parameters = vec![AnalysisParameter::Export { from, to }];
+        log::info!("{parameters:?}");
+        Some(Ok(parameters))
+    } else {
+        None
+    }
+}
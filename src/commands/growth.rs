@@ -1,28 +1,44 @@
 use clap::{arg, Arg, ArgMatches, Command};
 
-use crate::analysis_parameter::{AnalysisParameter, AnalysisRun, Grouping};
+use crate::analyses::growth::ReportFormat;
+use crate::analysis_parameter::{groupby_arggroup, groupby_args, parse_groupby, AnalysisParameter, AnalysisRun, Grouping};
 
 pub fn get_subcommand() -> Command {
     Command::new("growth")
         .about("Calculate growth curve from coverage histogram")
         .args(&[
             arg!(gfa_file: <GFA_FILE> "graph in GFA1 format, accepts also compressed (.gz) file"),
+            Arg::new("hist_file").long("hist-file").help("Recompute growth curves from a previously generated panacus histogram table instead of re-reading the graph"),
+            Arg::new("format").long("format").value_parser(["table", "summary", "html", "json", "term"]).default_value("table").help("Output format: a machine-readable tsv table, a human-readable terminal summary, an html snippet, a structured json document, or an ASCII bar chart for the terminal"),
             arg!(-s --subset <FILE> "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file)"),
             arg!(-e --exclude <FILE> "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file; all intersecting bp/node/edge will be exluded also in other paths not part of the given list"),
-            arg!(-g --groupby <FILE> "Merge counts from paths by path-group mapping from given tab-separated two-column file"),
-            arg!(-H --"groupby-haplotype" "Merge counts from paths belonging to same haplotype"),
-            arg!(-S --"groupby-sample" "Merge counts from paths belonging to same sample"),
+        ])
+        .args(groupby_args())
+        .args(&[
+            Arg::new("groupby-pattern").long("groupby-pattern").help("Merge counts from paths whose name matches the given regex-lite pattern (literals, '.', '\\d', '\\w', '*'/'+', anchored to the whole path name, e.g. sample\\d+#1#.*), instead of an explicit path-group mapping file"),
+        ])
+        .group(groupby_arggroup(&["groupby-pattern"]))
+        .args(&[
             arg!(-a --hist "Also include histogram in output"),
             Arg::new("coverage").help("Ignore all countables with a coverage lower than the specified threshold. The coverage of a countable corresponds to the number of path/walk that contain it. Repeated appearances of a countable in the same path/walk are counted as one. You can pass a comma-separated list of coverage thresholds, each one will produce a separated growth curve (e.g., --coverage 2,3). Use --quorum to set a threshold in conjunction with each coverage (e.g., --quorum 0.5,0.9)")
             .short('l').long("coverage").default_value("1"),
             Arg::new("quorum").help("Unlike the --coverage parameter, which specifies a minimum constant number of paths for all growth point m (1 <= m <= num_paths), --quorum adjust the threshold based on m. At each m, a countable is counted in the average growth if the countable is contained in at least floor(m*quorum) paths. Example: A quorum of 0.9 requires a countable to be in 90% of paths for each subset size m. At m=10, it must appear in at least 9 paths. At m=100, it must appear in at least 90 paths. A quorum of 1 (100%) requires presence in all paths of the subset, corresponding to the core. Default: 0, a countable counts if it is present in any path at each growth point. Specify multiple quorum values with a comma-separated list (e.g., --quorum 0.5,0.9). Use --coverage to set static path thresholds in conjunction with variable quorum percentages (e.g., --coverage 5,10).")
             .short('q').long("quorum").default_value("0"),
+            Arg::new("bootstrap").long("bootstrap").help("Recompute the growth curve over this many random genome permutations and report a 2.5/50/97.5 percentile band alongside the exact expectation curve. Needs a live graph (ignored together with --hist-file, since a previously exported histogram has already lost per-genome identity)").value_parser(clap::value_parser!(usize)),
+            Arg::new("seed").long("seed").help("Seed for --bootstrap's random genome permutations, for reproducible bands").value_parser(clap::value_parser!(u64)),
         ])
 }
 
 pub fn get_instructions(args: &ArgMatches) -> Option<Result<Vec<AnalysisRun>, anyhow::Error>> {
     if let Some(args) = args.subcommand_matches("growth") {
-        // let hist = args.get_one::<String>("hist_file").expect("").to_owned();
+        let hist_file = args.get_one::<String>("hist_file").cloned();
+        let report_format = match args.get_one::<String>("format").map(String::as_str) {
+            Some("summary") => ReportFormat::Summary,
+            Some("html") => ReportFormat::Html,
+            Some("json") => ReportFormat::Json,
+            Some("term") => ReportFormat::Term,
+            _ => ReportFormat::Table,
+        };
         let coverage = args.get_one::<String>("coverage").cloned();
         let quorum = args.get_one::<String>("quorum").cloned();
         let add_hist = args.get_flag("hist");
@@ -38,14 +54,13 @@ pub fn get_instructions(args: &ArgMatches) -> Option<Result<Vec<AnalysisRun>, an
             .get_one::<String>("exclude")
             .cloned()
             .unwrap_or_default();
-        let grouping = args.get_one::<String>("groupby").cloned();
-        let grouping = if args.get_flag("groupby-sample") {
-            Some(Grouping::Sample)
-        } else if args.get_flag("groupby-haplotype") {
-            Some(Grouping::Haplotype)
-        } else {
-            grouping.map(|g| Grouping::Custom(g))
-        };
+        let grouping = parse_groupby(args).or_else(|| {
+            args.get_one::<String>("groupby-pattern")
+                .cloned()
+                .map(Grouping::Pattern)
+        });
+        let bootstrap = args.get_one::<usize>("bootstrap").copied();
+        let seed = args.get_one::<u64>("seed").copied();
         Some(Ok(vec![AnalysisRun::new(
             graph,
             subset,
@@ -56,6 +71,10 @@ pub fn get_instructions(args: &ArgMatches) -> Option<Result<Vec<AnalysisRun>, an
                 coverage,
                 quorum,
                 add_hist,
+                hist_file,
+                report_format,
+                bootstrap,
+                seed,
             }],
         )]))
     } else {
@@ -1,7 +1,11 @@
 use crate::clap_enum_variants;
-use clap::{arg, Arg, ArgMatches, Command};
+use clap::{arg, Arg, ArgAction, ArgMatches, Command};
 
-use crate::analysis_parameter::{AnalysisParameter, Grouping};
+use crate::analyses::growth::ReportFormat;
+use crate::analysis_parameter::{
+    groupby_arggroup, groupby_args, parse_groupby, AnalysisParameter, AnalysisRun, BinScale,
+    NormalizeMode,
+};
 use crate::util::CountType;
 
 pub fn get_subcommand() -> Command {
@@ -11,16 +15,25 @@ pub fn get_subcommand() -> Command {
             arg!(gfa_file: <GFA_FILE> "graph in GFA1 format, accepts also compressed (.gz) file"),
             arg!(-s --subset <FILE> "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file)"),
             arg!(-e --exclude <FILE> "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file; all intersecting bp/node/edge will be exluded also in other paths not part of the given list"),
-            arg!(-g --groupby <FILE> "Merge counts from paths by path-group mapping from given tab-separated two-column file"),
-            arg!(-H --"groupby-haplotype" "Merge counts from paths belonging to same haplotype"),
-            arg!(-S --"groupby-sample" "Merge counts from paths belonging to same sample"),
+        ])
+        .args(groupby_args())
+        .group(groupby_arggroup(&[]))
+        .args(&[
             Arg::new("count").help("Graph quantity to be counted").default_value("node").ignore_case(true).short('c').long("count").value_parser(clap_enum_variants!(CountType)),
+            Arg::new("format").long("format").value_parser(["table", "json", "term"]).default_value("table").help("Output format: a machine-readable tsv table, a structured json document, or an ASCII bar chart for the terminal"),
+            Arg::new("normalize").short('n').long("normalize").action(ArgAction::SetTrue).help("Emit relative frequencies instead of raw counts, dividing each coverage level by the total count"),
+            Arg::new("normalize_as").long("as").value_parser(["fraction", "percentage"]).default_value("fraction").help("Whether --normalize reports a fraction that sums to 1 or a percentage that sums to 100"),
+            Arg::new("bins").long("bins").value_name("N").help("Aggregate coverage levels into N contiguous bins instead of emitting one row per level"),
+            Arg::new("bin_scale").long("bin-scale").value_parser(["linear", "log"]).default_value("linear").help("Use equal-width bins, or geometrically spaced ones that give low coverage levels more resolution, with --bins"),
+            Arg::new("soft_core_cutoff").long("soft-core-cutoff").value_name("FRACTION").default_value("0.95").help("Minimum fraction of groups a countable must appear in to be classified as soft-core rather than shell, in the core/soft-core/shell/cloud breakdown"),
         ])
 }
 
-pub fn get_instructions(
-    args: &ArgMatches,
-) -> Option<Result<Vec<AnalysisParameter>, anyhow::Error>> {
+// `subset`/`exclude`/`grouping` are not consumed by `AnalysisParameter::Hist` itself: they are
+// carried on the `AnalysisRun` and turned into a `Task::GraphStateChange` that subsets/excludes
+// the graph and applies the group mapping before any `Hist` analysis task runs, so the
+// histogram is already computed against the requested subset/grouping by the time it runs.
+pub fn get_instructions(args: &ArgMatches) -> Option<Result<Vec<AnalysisRun>, anyhow::Error>> {
     if let Some(args) = args.subcommand_matches("hist") {
         let graph = args
             .get_one::<String>("gfa_file")
@@ -30,17 +43,61 @@ pub fn get_instructions(
             .get_one::<CountType>("count")
             .expect("hist subcommand has count type")
             .to_owned();
-        let subset = args.get_one::<String>("subset").cloned();
-        let exclude = args.get_one::<String>("exclude").cloned();
-        let grouping = args.get_one::<String>("groupby").cloned();
-        let grouping = if args.get_flag("groupby-sample") {
-            Some(Grouping::Sample)
-        } else if args.get_flag("groupby-haplotype") {
-            Some(Grouping::Haplotype)
+        let report_format = match args.get_one::<String>("format").map(String::as_str) {
+            Some("json") => ReportFormat::Json,
+            Some("term") => ReportFormat::Term,
+            _ => ReportFormat::Table,
+        };
+        let normalize = if args.get_flag("normalize") {
+            match args.get_one::<String>("normalize_as").map(String::as_str) {
+                Some("percentage") => Some(NormalizeMode::Percentage),
+                _ => Some(NormalizeMode::Fraction),
+            }
         } else {
-            grouping.map(|g| Grouping::Custom(g))
+            None
+        };
+        let bins = args
+            .get_one::<String>("bins")
+            .map(|x| x.parse::<usize>())
+            .transpose()?;
+        let bin_scale = match args.get_one::<String>("bin_scale").map(String::as_str) {
+            Some("log") => BinScale::Log,
+            _ => BinScale::Linear,
         };
-        Some(Ok(vec![AnalysisParameter::Hist { count_type: count }]))
+        let soft_core_cutoff = args
+            .get_one::<String>("soft_core_cutoff")
+            .expect("soft_core_cutoff has a default value")
+            .parse::<f64>()
+            .ok()?;
+        let subset = args
+            .get_one::<String>("subset")
+            .cloned()
+            .unwrap_or_default();
+        let exclude = args
+            .get_one::<String>("exclude")
+            .cloned()
+            .unwrap_or_default();
+        let grouping = parse_groupby(args);
+        Some(Ok(vec![AnalysisRun::new(
+            graph,
+            None,
+            subset,
+            exclude,
+            grouping,
+            false,
+            vec![AnalysisParameter::Hist {
+                count_type: count,
+                report_format,
+                normalize,
+                bins,
+                bin_scale,
+                soft_core_cutoff,
+                interval: None,
+                bounds: None,
+                min_bucket_count: 0,
+                cumulative: None,
+            }],
+        )]))
     } else {
         None
     }
@@ -1,6 +1,6 @@
-use clap::{arg, ArgMatches, Command};
+use clap::{arg, Arg, ArgMatches, Command};
 
-use crate::analysis_parameter::{AnalysisParameter, Grouping};
+use crate::analysis_parameter::{groupby_arggroup, groupby_args, parse_groupby, AnalysisParameter, AnalysisRun};
 
 pub fn get_subcommand() -> Command {
     Command::new("info")
@@ -9,31 +9,44 @@ pub fn get_subcommand() -> Command {
             arg!(gfa_file: <GFA_FILE> "graph in GFA1 format, accepts also compressed (.gz) file"),
             arg!(-s --subset <FILE> "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file)"),
             arg!(-e --exclude <FILE> "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file; all intersecting bp/node/edge will be exluded also in other paths not part of the given list"),
-            arg!(-g --groupby <FILE> "Merge counts from paths by path-group mapping from given tab-separated two-column file"),
-            arg!(-H --"groupby-haplotype" "Merge counts from paths belonging to same haplotype"),
-            arg!(-S --"groupby-sample" "Merge counts from paths belonging to same sample"),
+        ])
+        .args(groupby_args())
+        .group(groupby_arggroup(&[]))
+        .args(&[
+            Arg::new("clustering_sample_size").long("clustering-sample-size").value_name("N").help("Estimate the average clustering coefficient from N randomly sampled nodes instead of computing it exactly; useful on very large graphs"),
         ])
 }
 
-pub fn get_instructions(
-    args: &ArgMatches,
-) -> Option<Result<Vec<AnalysisParameter>, anyhow::Error>> {
+pub fn get_instructions(args: &ArgMatches) -> Option<Result<Vec<AnalysisRun>, anyhow::Error>> {
     if let Some(args) = args.subcommand_matches("info") {
         let graph = args
             .get_one::<String>("gfa_file")
             .expect("info subcommand has gfa file")
             .to_owned();
-        let subset = args.get_one::<String>("subset").cloned();
-        let exclude = args.get_one::<String>("exclude").cloned();
-        let grouping = args.get_one::<String>("groupby").cloned();
-        let grouping = if args.get_flag("groupby-sample") {
-            Some(Grouping::Sample)
-        } else if args.get_flag("groupby-haplotype") {
-            Some(Grouping::Haplotype)
-        } else {
-            grouping.map(|g| Grouping::Custom(g))
-        };
-        Some(Ok(vec![AnalysisParameter::Info {}]))
+        let subset = args
+            .get_one::<String>("subset")
+            .cloned()
+            .unwrap_or_default();
+        let exclude = args
+            .get_one::<String>("exclude")
+            .cloned()
+            .unwrap_or_default();
+        let grouping = parse_groupby(args);
+        let clustering_sample_size = args
+            .get_one::<String>("clustering_sample_size")
+            .map(|x| x.parse::<usize>())
+            .transpose()?;
+        Some(Ok(vec![AnalysisRun::new(
+            graph,
+            None,
+            subset,
+            exclude,
+            grouping,
+            false,
+            vec![AnalysisParameter::Info {
+                clustering_sample_size,
+            }],
+        )]))
     } else {
         None
     }
@@ -0,0 +1,24 @@
+use clap::{Arg, ArgAction, Command};
+
+pub fn get_subcommand() -> Command {
+    Command::new("inspect")
+        .visible_alias("list")
+        .about("Summarize one or more JSON result files without rendering them")
+        .args(&[
+            Arg::new("json_files")
+                .required(true)
+                .num_args(1..)
+                .trailing_var_arg(true)
+                .help("Specifies one or more JSON files"),
+            Arg::new("format")
+                .long("format")
+                .value_parser(["text", "json"])
+                .default_value("text")
+                .help("Output format: `text` for a human-readable listing, `json` so scripts can decide which files to feed into `render --compare`"),
+            Arg::new("verbose")
+                .long("verbose")
+                .short('v')
+                .action(ArgAction::SetTrue)
+                .help("Also list each analysis' chart item kinds"),
+        ])
+}
@@ -1,6 +1,6 @@
-use clap::{arg, Arg, ArgMatches, Command};
+use clap::{arg, Arg, ArgAction, ArgMatches, Command};
 
-use crate::analysis_parameter::AnalysisParameter;
+use crate::analysis_parameter::{AnalysisParameter, BinMode};
 
 pub fn get_subcommand() -> Command {
     Command::new("node-distribution")
@@ -13,6 +13,31 @@ pub fn get_subcommand() -> Command {
                 .long("radius")
                 .value_parser(clap::value_parser!(u32))
                 .default_value("20"),
+            Arg::new("bin_mode")
+                .long("bin-mode")
+                .value_parser(["hex", "square"])
+                .default_value("hex")
+                .help("Aggregate coverage-vs-log-length points into a staggered hexagonal grid, or a plain rectangular grid"),
+            Arg::new("log_density")
+                .long("log-density")
+                .action(ArgAction::SetTrue)
+                .help("Log10-normalize each bin's point count before it is used as the color-scale value, so a few hot bins don't dominate on large graphs"),
+            Arg::new("knn_density")
+                .long("knn-density")
+                .value_parser(clap::value_parser!(u32))
+                .help("Replace each bin's raw point count with a k-nearest-neighbor density estimate using this many neighbors, instead of a raw count; takes precedence over --log-density"),
+            Arg::new("log_x")
+                .long("log-x")
+                .action(ArgAction::SetTrue)
+                .help("Bin the coverage axis in log10(1+coverage) space instead of linearly"),
+            Arg::new("log_y")
+                .long("log-y")
+                .action(ArgAction::SetTrue)
+                .help("Bin the length axis in log10(1+length) space instead of linearly"),
+            Arg::new("weight_by_length")
+                .long("weight-by-length")
+                .action(ArgAction::SetTrue)
+                .help("Sum each member node's length (bp) into its bin instead of just counting members, so a bin's shading reflects how much sequence it holds"),
         ])
 }
 
@@ -28,7 +53,24 @@ pub fn get_instructions(
             .get_one::<u32>("radius")
             .expect("node-distribution has radius")
             .to_owned();
-        Some(Ok(vec![AnalysisParameter::NodeDistribution { radius }]))
+        let bin_mode = match args.get_one::<String>("bin_mode").map(String::as_str) {
+            Some("square") => BinMode::Square,
+            _ => BinMode::Hex,
+        };
+        let log_density = args.get_flag("log_density");
+        let knn_k = args.get_one::<u32>("knn_density").copied();
+        let log_x = args.get_flag("log_x");
+        let log_y = args.get_flag("log_y");
+        let weight_by_length = args.get_flag("weight_by_length");
+        Some(Ok(vec![AnalysisParameter::NodeDistribution {
+            radius,
+            bin_mode,
+            log_density,
+            knn_k,
+            log_x,
+            log_y,
+            weight_by_length,
+        }]))
     } else {
         None
     }
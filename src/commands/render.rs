@@ -3,9 +3,49 @@ use clap::{Arg, ArgAction, ArgMatches, Command};
 pub fn get_subcommand() -> Command {
     Command::new("render")
         .about("Render an html report from one or more JSON result files")
-        .args(&[Arg::new("json_files")
-            .required(true)
-            .num_args(1..)
-            .trailing_var_arg(true)
-            .help("Specifies one or more JSON files")])
+        .args(&[
+            Arg::new("json_files")
+                .required_unless_present("json")
+                .num_args(1..)
+                .trailing_var_arg(true)
+                .help("Specifies one or more JSON files; pass `-` to read a JSON document from stdin"),
+            Arg::new("json")
+                .long("json")
+                .value_name("CONTENTS")
+                .help("Render the JSON document passed inline as a string, instead of reading it from a file"),
+            Arg::new("template")
+                .long("template")
+                .value_name("FILE")
+                .help("Render with a custom Handlebars report template instead of the bundled one, so labs can brand reports or embed them in existing dashboards"),
+            Arg::new("theme")
+                .long("theme")
+                .value_parser(["light", "dark", "auto"])
+                .default_value("auto")
+                .help("Initial color-mode of the rendered report; a toggle in the nav bar lets the viewer switch and persists their choice"),
+            Arg::new("tsv")
+                .long("tsv")
+                .action(ArgAction::SetTrue)
+                .help("Instead of an html report, concatenate each analysis' table into a single tsv document"),
+            Arg::new("format")
+                .long("format")
+                .value_parser(["html", "html-standalone", "svg", "png"])
+                .default_value("html")
+                .conflicts_with("tsv")
+                .help("Output format: `html`/`html-standalone` produce the same self-contained HTML page (all JS/CSS/image assets are already embedded inline, no CDN involved); `svg` renders each chart server-side from the parsed JSON datasets instead of in the browser; `png` is not yet implemented"),
+            Arg::new("verify")
+                .long("verify")
+                .visible_alias("check")
+                .action(ArgAction::SetTrue)
+                .help("Instead of rendering, check each JSON file's `_digest` against a freshly computed one and print an SFV-style OK/FAILED line per file, exiting non-zero on any mismatch"),
+            Arg::new("compare")
+                .long("compare")
+                .visible_alias("overlay")
+                .action(ArgAction::SetTrue)
+                .help("Keep each input's analyses distinguishable in the combined report by tagging them with a label keyed by source (the file path, `-`/`<inline>` for piped/inline input, or `--label`), instead of trusting the run names already baked into each JSON document"),
+            Arg::new("label")
+                .long("label")
+                .action(ArgAction::Append)
+                .requires("compare")
+                .help("With --compare, an explicit label for each input in order (one per json_files entry, inline --json counting as the first); defaults to the input's file path otherwise"),
+        ])
 }
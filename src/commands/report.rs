@@ -28,6 +28,35 @@ pub fn get_subcommand() -> Command {
                     "Instead of an HTML report, a json result will be delivered. These can later be combined and rendered as a single HTML.",
                 )
         ])
+        .args(&[Arg::new("tsv")
+                .required(false)
+                .long("tsv")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("json")
+                .help(
+                    "Instead of an HTML report, a single tsv document concatenating each analysis' table is delivered, for spreadsheets/notebooks that don't need the full json model.",
+                )
+        ])
+        .args(&[Arg::new("format")
+                .required(false)
+                .long("format")
+                .value_parser(["summary", "flat"])
+                .conflicts_with_all(["json", "tsv"])
+                .help(
+                    "Instead of an HTML report, print a compact terminal summary (`summary`) or one tab-separated record per analysis/countable (`flat`), without generating the HTML shell -- for a quick pass/fail view in CI.",
+                )
+        ])
+        .args(&[
+            Arg::new("template")
+                .long("template")
+                .value_name("FILE")
+                .help("Render with a custom Handlebars report template instead of the bundled one, so labs can brand reports or embed them in existing dashboards"),
+            Arg::new("theme")
+                .long("theme")
+                .value_parser(["light", "dark", "auto"])
+                .default_value("auto")
+                .help("Initial color-mode of the rendered report; a toggle in the nav bar lets the viewer switch and persists their choice"),
+        ])
 }
 
 pub fn get_instructions(args: &ArgMatches) -> Option<Result<Vec<AnalysisRun>, anyhow::Error>> {
@@ -2,7 +2,10 @@ use crate::clap_enum_variants_no_all;
 use clap::{arg, Arg, ArgMatches, Command};
 use strum::VariantNames;
 
-use crate::analysis_parameter::{AnalysisParameter, ClusterMethod, Grouping};
+use crate::analysis_parameter::{
+    groupby_arggroup, groupby_args, parse_groupby, AnalysisParameter, ClusterMethod,
+    SimilarityMetric,
+};
 use crate::util::CountType;
 
 pub fn get_subcommand() -> Command {
@@ -12,12 +15,14 @@ pub fn get_subcommand() -> Command {
             arg!(gfa_file: <GFA_FILE> "graph in GFA1 format, accepts also compressed (.gz) file"),
             arg!(-s --subset <FILE> "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file)"),
             arg!(-e --exclude <FILE> "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file; all intersecting bp/node/edge will be exluded also in other paths not part of the given list"),
-            arg!(-g --groupby <FILE> "Merge counts from paths by path-group mapping from given tab-separated two-column file"),
-            arg!(-H --"groupby-haplotype" "Merge counts from paths belonging to same haplotype"),
-            arg!(-S --"groupby-sample" "Merge counts from paths belonging to same sample"),
+        ])
+        .args(groupby_args())
+        .group(groupby_arggroup(&[]))
+        .args(&[
             arg!(-a --"total" "Summarize by totaling presence/absence over all groups"),
             Arg::new("count").help("Graph quantity to be counted").default_value("node").ignore_case(true).short('c').long("count").value_parser(clap_enum_variants_no_all!(CountType)),
             Arg::new("cluster_method").help("Method for clustering results").default_value("centroid").ignore_case(true).short('m').long("method").value_parser(clap_enum_variants_no_all!(ClusterMethod)),
+            Arg::new("metric").help("Measure used to compute pairwise group similarity").default_value("jaccard").ignore_case(true).long("metric").value_parser(clap_enum_variants_no_all!(SimilarityMetric)),
         ])
 }
 
@@ -35,16 +40,13 @@ pub fn get_instructions(args: &ArgMatches) -> Option<anyhow::Result<Vec<Analysis
             .get_one::<ClusterMethod>("cluster_method")
             .expect("hist subcommand has count type")
             .to_owned();
+        let metric = args
+            .get_one::<SimilarityMetric>("metric")
+            .expect("similarity subcommand has a metric")
+            .to_owned();
         let subset = args.get_one::<String>("subset").cloned();
         let exclude = args.get_one::<String>("exclude").cloned();
-        let grouping = args.get_one::<String>("groupby").cloned();
-        let grouping = if args.get_flag("groupby-sample") {
-            Some(Grouping::Sample)
-        } else if args.get_flag("groupby-haplotype") {
-            Some(Grouping::Haplotype)
-        } else {
-            grouping.map(|g| Grouping::Custom(g))
-        };
+        let grouping = parse_groupby(args);
         let parameters = vec![AnalysisParameter::Similarity {
             count_type: count,
             graph,
@@ -52,6 +54,7 @@ pub fn get_instructions(args: &ArgMatches) -> Option<anyhow::Result<Vec<Analysis
             exclude,
             grouping,
             cluster_method,
+            metric,
         }];
         log::info!("{parameters:?}");
         Some(Ok(parameters))
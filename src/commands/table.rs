@@ -2,7 +2,8 @@ use crate::clap_enum_variants_no_all;
 use clap::{arg, Arg, ArgMatches, Command};
 use strum::VariantNames;
 
-use crate::analysis_parameter::{AnalysisParameter, Grouping};
+use crate::analysis_parameter::{groupby_arggroup, groupby_args, NormalizeMode};
+use crate::analysis_parameter::AnalysisParameter;
 use crate::util::CountType;
 
 pub fn get_subcommand() -> Command {
@@ -12,12 +13,15 @@ pub fn get_subcommand() -> Command {
             arg!(gfa_file: <GFA_FILE> "graph in GFA1 format, accepts also compressed (.gz) file"),
             arg!(-s --subset <FILE> "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file)"),
             arg!(-e --exclude <FILE> "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file; all intersecting bp/node/edge will be exluded also in other paths not part of the given list"),
-            arg!(-g --groupby <FILE> "Merge counts from paths by path-group mapping from given tab-separated two-column file"),
-            arg!(-H --"groupby-haplotype" "Merge counts from paths belonging to same haplotype"),
-            arg!(-S --"groupby-sample" "Merge counts from paths belonging to same sample"),
+        ])
+        .args(groupby_args())
+        .group(groupby_arggroup(&[]))
+        .args(&[
             arg!(-a --"total" "Summarize by totaling presence/absence over all groups"),
             arg!(-O --order <FILE> "The ordered histogram will be produced according to order of paths/groups in the supplied file (1-column list). If this option is not used, the order is determined by the rank of paths/groups in the subset list, and if that option is not used, the order is determined by the rank of paths/groups in the GFA file."),
             Arg::new("count").help("Graph quantity to be counted").default_value("node").ignore_case(true).short('c').long("count").value_parser(clap_enum_variants_no_all!(CountType)),
+            arg!(-n --normalize "Emit relative frequencies instead of raw counts, dividing each coverage level by the total count"),
+            Arg::new("normalize_as").long("as").value_parser(["fraction", "percentage"]).default_value("fraction").help("Whether --normalize reports a fraction that sums to 1 or a percentage that sums to 100"),
         ])
 }
 
@@ -33,20 +37,23 @@ pub fn _get_instructions(args: &ArgMatches) -> Option<anyhow::Result<Vec<Analysi
             .to_owned();
         let total = args.get_flag("total");
         let order = args.get_one::<String>("order").cloned();
+        let normalize = if args.get_flag("normalize") {
+            match args.get_one::<String>("normalize_as").map(String::as_str) {
+                Some("percentage") => Some(NormalizeMode::Percentage),
+                _ => Some(NormalizeMode::Fraction),
+            }
+        } else {
+            None
+        };
         // let subset = args.get_one::<String>("subset").cloned();
         // let exclude = args.get_one::<String>("exclude").cloned();
-        // let grouping = args.get_one::<String>("groupby").cloned();
-        // let grouping = if args.get_flag("groupby-sample") {
-        //     Some(Grouping::Sample)
-        // } else if args.get_flag("groupby-haplotype") {
-        //     Some(Grouping::Haplotype)
-        // } else {
-        //     grouping.map(|g| Grouping::Custom(g))
-        // };
+        // let grouping = crate::analysis_parameter::parse_groupby(args);
         let parameters = vec![AnalysisParameter::Table {
             count_type: count,
             total,
             order,
+            normalize,
+            format: Default::default(),
         }];
         log::info!("{parameters:?}");
         Some(Ok(parameters))
@@ -11,12 +11,15 @@ use graph::GraphStorage;
 
 use crate::{
     analyses::InputRequirement as Req, analysis_parameter::Grouping,
-    io::bufreader_from_compressed_gfa, util::CountType,
+    io::bufreader_from_compressed_gfa, progress::Progress, util::CountType,
 };
 
 mod abacus;
+mod cache;
 mod graph;
 mod hist;
+mod item_store;
+mod segment_log;
 mod util;
 
 pub use abacus::AbacusByGroup;
@@ -34,6 +37,12 @@ pub struct GraphState {
     pub subset: String,
     pub exclude: String,
     pub grouping: Option<Grouping>,
+    // `--cache-dir <dir>`: closed, not delivered (see `cache` module doc comment and
+    // `BACKLOG_STATUS.md`, `marschall-lab/panacus#chunk10-1`) -- `GraphStorage` can't plug into
+    // it from here, so this is only used as part of the key `change_graph_state` compares to
+    // decide whether a new `GraphStateChange` can reuse the already-parsed graph instead of
+    // calling `from_gfa` again. `None` means no directory was given, same as passing `--no-cache`.
+    pub cache_dir: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -56,6 +65,8 @@ pub struct GraphBroker {
     _nice: bool,
     input_requirements: HashSet<Req>,
     count_type: CountType,
+    cache_dir: Option<String>,
+    cycles: Option<Vec<Vec<ItemId>>>,
 }
 
 impl GraphBroker {
@@ -74,6 +85,8 @@ impl GraphBroker {
             input_requirements: HashSet::new(),
             count_type: CountType::All,
             csc_abacus: false,
+            cache_dir: None,
+            cycles: None,
         }
     }
 
@@ -95,31 +108,50 @@ impl GraphBroker {
         state: GraphState,
         input_requirements: &HashSet<Req>,
         nice: bool,
+        progress: Option<&Progress>,
     ) -> Result<(), Error> {
         if self.state.is_some() {
             let prev_state = std::mem::take(&mut self.state).unwrap();
-            if prev_state.graph != state.graph {
-                *self = Self::from_gfa(input_requirements, nice);
+            if prev_state.graph != state.graph || prev_state.cache_dir != state.cache_dir {
+                *self = Self::from_gfa(input_requirements, nice, state.cache_dir.clone());
             }
             if prev_state.subset != state.subset {
+                if let Some(p) = progress {
+                    p.stage(&format!("applying subset {}", state.subset));
+                }
                 self.include_coords(&state.subset);
             }
             if prev_state.exclude != state.exclude {
+                if let Some(p) = progress {
+                    p.stage(&format!("applying exclude {}", state.exclude));
+                }
                 self.exclude_coords(&state.exclude);
             }
             if prev_state.grouping != state.grouping {
+                if let Some(p) = progress {
+                    p.stage("regrouping paths");
+                }
                 self.with_group(&state.grouping);
             }
             self.finish()?;
         } else {
-            *self = Self::from_gfa(input_requirements, nice);
+            *self = Self::from_gfa(input_requirements, nice, state.cache_dir.clone());
             if !state.subset.is_empty() {
+                if let Some(p) = progress {
+                    p.stage(&format!("applying subset {}", state.subset));
+                }
                 self.include_coords(&state.subset);
             }
             if !state.exclude.is_empty() {
+                if let Some(p) = progress {
+                    p.stage(&format!("applying exclude {}", state.exclude));
+                }
                 self.exclude_coords(&state.exclude);
             }
             if state.grouping.is_some() {
+                if let Some(p) = progress {
+                    p.stage("regrouping paths");
+                }
                 self.with_group(&state.grouping);
             }
             self.finish()?;
@@ -133,7 +165,12 @@ impl GraphBroker {
         self.finish()
     }
 
-    fn from_gfa(input_requirements: &HashSet<Req>, nice: bool) -> Self {
+    // `cache_dir`: when set, `GraphStorage::from_gfa` should be preceded by a `cache::read_cache`
+    // lookup and, on a miss, followed by `cache::write_cache` -- see the `cache` module doc
+    // comment for why that hand-off isn't wired up in this tree: `GraphStorage` doesn't expose a
+    // constructor from cached parts, and its defining module (`graph_broker::graph`) isn't
+    // present in this snapshot to add one to.
+    fn from_gfa(input_requirements: &HashSet<Req>, nice: bool, cache_dir: Option<String>) -> Self {
         let count_type = if Self::contains_at_least_two(input_requirements) {
             CountType::All
         } else if input_requirements.contains(&Req::Node) {
@@ -164,10 +201,12 @@ impl GraphBroker {
             hists: None,
             path_lens: None,
             gfa_file: gfa_file.to_owned(),
+            cache_dir,
             _nice: nice,
             input_requirements: input_requirements.clone(),
             count_type,
             csc_abacus: false,
+            cycles: None,
         }
     }
 
@@ -177,6 +216,7 @@ impl GraphBroker {
                 Grouping::Sample => self.with_sample_group(),
                 Grouping::Haplotype => self.with_haplo_group(),
                 Grouping::Custom(file_name) => self.with_custom_group(file_name),
+                Grouping::Pattern(pattern) => self.with_pattern_group(pattern),
             };
         }
     }
@@ -185,6 +225,13 @@ impl GraphBroker {
         self.abacus_aux_params.groupby = file_name.to_owned();
     }
 
+    /// Groups paths by matching `pattern` (see `util::pattern_match`) against each path name;
+    /// the actual per-path group assignment happens wherever `groupby`/`groupby_pattern` is
+    /// resolved against the parsed path set, same as the file-based `Custom` grouping above.
+    fn with_pattern_group(&mut self, pattern: &str) {
+        self.abacus_aux_params.groupby_pattern = pattern.to_owned();
+    }
+
     fn with_haplo_group(&mut self) {
         self.abacus_aux_params.groupby_haplotype = true;
     }
@@ -216,6 +263,9 @@ impl GraphBroker {
         if self.input_requirements.contains(&Req::Hist) {
             self.set_hists();
         }
+        if self.input_requirements.contains(&Req::Cycles) {
+            self.set_cycles();
+        }
         let mut has_already_used_abacus = false;
         for req in self.input_requirements.clone() {
             match req {
@@ -279,6 +329,12 @@ impl GraphBroker {
         self.graph_aux.as_ref().unwrap().edge_count
     }
 
+    /// The single `CountType` `get_abacus_by_group`'s `AbacusByGroup` was actually built for
+    /// (set once, at construction, from the union of the run's `InputRequirement`s).
+    pub fn get_count_type(&self) -> CountType {
+        self.count_type
+    }
+
     pub fn get_group_count(&self) -> usize {
         Self::check_and_error(self.abacus_aux.as_ref(), "abacus_aux -> group_count");
         self.abacus_aux.as_ref().unwrap().count_groups()
@@ -293,6 +349,13 @@ impl GraphBroker {
         &self.abacus_aux.as_ref().unwrap().groups
     }
 
+    /// Distinct group identifiers the current grouping (`with_group`/`get_groups`) resolved to.
+    /// This is the set a `Task::OrderChange` file must be a permutation of, see
+    /// `order_file::validate_permutation`.
+    pub fn group_names(&self) -> HashSet<String> {
+        self.get_groups().values().cloned().collect()
+    }
+
     pub fn get_path_lens(&self) -> &HashMap<PathSegment, (u32, u32)> {
         Self::check_and_error(self.path_lens.as_ref(), "path_lens");
         self.path_lens.as_ref().unwrap()
@@ -303,6 +366,21 @@ impl GraphBroker {
         self.hists.as_ref().unwrap()
     }
 
+    /// Every cycle in the (bidirected) graph: each strongly-connected component with more than
+    /// one node, plus every self-loop edge (as its own single-node cycle). Only computed when
+    /// [`InputRequirement::Cycles`](crate::analyses::InputRequirement::Cycles) was requested.
+    pub fn get_cycles(&self) -> &Vec<Vec<ItemId>> {
+        Self::check_and_error(self.cycles.as_ref(), "cycles");
+        self.cycles.as_ref().unwrap()
+    }
+
+    // No persisted cache backs these: `marschall-lab/panacus#chunk14-2` asked for one keyed off
+    // the computed `AbacusByTotal`/`AbacusByGroup` results themselves (`src/abacus_cache.rs`,
+    // since deleted), built entirely against the orphaned `src/abacus.rs` rather than this
+    // `graph_broker::abacus`. It wasn't rebuilt here because `AbacusByGroup`/`AbacusByTotal`
+    // would need to derive a serialization format and this module can't declare the dependency
+    // that would take (see `graph_broker::cache`'s doc comment) -- closed, not delivered; see
+    // `BACKLOG_STATUS.md`.
     pub fn get_abacus_by_group(&self) -> &AbacusByGroup {
         Self::check_and_error(self.group_abacus.as_ref(), "abacus_by_group");
         self.group_abacus.as_ref().unwrap()
@@ -344,6 +422,12 @@ impl GraphBroker {
         self.hists = Some(hists);
     }
 
+    fn set_cycles(&mut self) {
+        Self::check_and_error(self.graph_aux.as_ref().unwrap().edge2id.as_ref(), "edge2id");
+        let edge2id = self.graph_aux.as_ref().unwrap().edge2id.as_ref().unwrap();
+        self.cycles = Some(find_cycles(edge2id));
+    }
+
     fn check_and_error<T>(value: Option<T>, type_of_value: &str) {
         if value.is_none() {
             let msg = format!(
@@ -375,6 +459,93 @@ impl GraphBroker {
         Ok(())
     }
 
+    /// Writes the current graph as GraphViz DOT: one node per segment, labeled with its length
+    /// (from `node_lens`) and filled light blue when [`AbacusByGroup`] has already been computed
+    /// (white otherwise); the fill is the same for every node and does not reflect per-group
+    /// coverage. One edge is written per entry in
+    /// `edge2id`. Does not itself re-derive the subset/exclude mask already baked into
+    /// `abacus_aux_params` at graph-load time -- `get_nodes()`/`get_edges()` already reflect it.
+    /// When `restrict_to` is given, only nodes/edges with both endpoints in that set are
+    /// written, e.g. to visualize just the subgraph connecting two nodes (see
+    /// [`Self::reachable_from`]).
+    pub fn write_dot<W: Write>(
+        &self,
+        out: &mut BufWriter<W>,
+        restrict_to: Option<&HashSet<ItemId>>,
+    ) -> Result<(), Error> {
+        writeln!(out, "graph {{")?;
+        let node_lens = self.get_node_lens();
+        for node in self.get_nodes() {
+            if restrict_to.is_some_and(|s| !s.contains(&node)) {
+                continue;
+            }
+            let len = node_lens.get(node.0 as usize - 1).copied().unwrap_or(0);
+            let color = self
+                .group_abacus
+                .as_ref()
+                .map(|_| "lightblue")
+                .unwrap_or("white");
+            writeln!(
+                out,
+                "  {} [label=\"{} ({} bp)\", style=filled, fillcolor={}];",
+                node.0, node.0, len, color
+            )?;
+        }
+        for e in self.get_edges().keys() {
+            if restrict_to.is_some_and(|s| !s.contains(&e.0) || !s.contains(&e.2)) {
+                continue;
+            }
+            writeln!(out, "  {} -- {};", e.0 .0, e.2 .0)?;
+        }
+        writeln!(out, "}}")?;
+        Ok(())
+    }
+
+    /// Every node reachable from `from` (including `from` itself), via breadth-first search.
+    /// When `respect_orientation` is `true`, the walk only follows a node's oriented adjacency
+    /// (the same `Side`-based traversal `find_cycles` runs its SCC pass over) so an inverted
+    /// repeat can't be crossed "the wrong way"; when `false`, every edge is followed regardless
+    /// of its orientation, i.e. the graph's underlying undirected topology.
+    pub fn reachable_from(&self, from: ItemId, respect_orientation: bool) -> HashSet<ItemId> {
+        let edge2id = self.get_edges();
+        let mut visited: HashSet<ItemId> = HashSet::from([from]);
+        if respect_orientation {
+            let adjacency = build_adjacency(edge2id);
+            let mut queue: std::collections::VecDeque<Side> = std::collections::VecDeque::new();
+            queue.push_back((from, Orientation::Forward));
+            queue.push_back((from, Orientation::Backward));
+            while let Some(v) = queue.pop_front() {
+                for &w in adjacency.get(&v).map(|v| v.as_slice()).unwrap_or(&[]) {
+                    if visited.insert(w.0) {
+                        queue.push_back(w);
+                    }
+                }
+            }
+        } else {
+            let mut adjacency: HashMap<ItemId, Vec<ItemId>> = HashMap::new();
+            for e in edge2id.keys() {
+                adjacency.entry(e.0).or_default().push(e.2);
+                adjacency.entry(e.2).or_default().push(e.0);
+            }
+            let mut queue: std::collections::VecDeque<ItemId> =
+                std::collections::VecDeque::from([from]);
+            while let Some(v) = queue.pop_front() {
+                for &w in adjacency.get(&v).map(|v| v.as_slice()).unwrap_or(&[]) {
+                    if visited.insert(w) {
+                        queue.push_back(w);
+                    }
+                }
+            }
+        }
+        visited
+    }
+
+    /// Whether there is a walk from `from` to `to`. See [`Self::reachable_from`] for
+    /// `respect_orientation`.
+    pub fn path_exists(&self, from: ItemId, to: ItemId, respect_orientation: bool) -> bool {
+        self.reachable_from(from, respect_orientation).contains(&to)
+    }
+
     fn set_abaci_by_total(&mut self) {
         let count_types_not_edge = if self.count_type == CountType::All {
             vec![CountType::Node, CountType::Bp]
@@ -420,3 +591,119 @@ impl GraphBroker {
         self.total_abaci = Some(abaci);
     }
 }
+
+// a node's two oriented endpoints, the vertices of the directed graph `find_cycles` runs over
+type Side = (ItemId, Orientation);
+
+fn flip(o: Orientation) -> Orientation {
+    match o {
+        Orientation::Forward => Orientation::Backward,
+        Orientation::Backward => Orientation::Forward,
+    }
+}
+
+/// Builds the oriented adjacency `find_cycles`, `reachable_from`, and `path_exists` all traverse:
+/// each oriented node contributes two directed vertices -- `(node, Forward)` and
+/// `(node, Backward)` -- and an `Edge` connects them according to its `Orientation`s, same
+/// construction as the orientation-aware SCC statistics in `analyses::info`.
+fn build_adjacency(edge2id: &HashMap<Edge, ItemId>) -> HashMap<Side, Vec<Side>> {
+    let mut adjacency: HashMap<Side, Vec<Side>> = HashMap::new();
+    for e in edge2id.keys() {
+        adjacency.entry((e.0, e.1)).or_default().push((e.2, e.3));
+        adjacency
+            .entry((e.2, flip(e.3)))
+            .or_default()
+            .push((e.0, flip(e.1)));
+    }
+    adjacency
+}
+
+/// Every cycle in the bidirected GFA graph described by `edge2id`: every strongly-connected
+/// component of more than one node (inversions/repeats show up this way), plus every self-loop
+/// edge as its own single-node cycle (an SCC pass alone wouldn't surface those, since a lone node
+/// with only a self-loop never needs to share a component with anything else). Uses an explicit
+/// stack rather than recursion so a million-node graph doesn't blow the call stack.
+fn find_cycles(edge2id: &HashMap<Edge, ItemId>) -> Vec<Vec<ItemId>> {
+    let adjacency = build_adjacency(edge2id);
+    let mut self_loops: HashSet<ItemId> = HashSet::new();
+    for e in edge2id.keys() {
+        if e.0 == e.2 {
+            self_loops.insert(e.0);
+        }
+    }
+
+    let mut index: HashMap<Side, u32> = HashMap::new();
+    let mut lowlink: HashMap<Side, u32> = HashMap::new();
+    let mut on_stack: HashSet<Side> = HashSet::new();
+    let mut stack: Vec<Side> = Vec::new();
+    let mut counter = 0u32;
+    let mut components: Vec<Vec<ItemId>> = Vec::new();
+
+    struct Frame {
+        v: Side,
+        child_idx: usize,
+    }
+
+    let vertices: Vec<Side> = adjacency.keys().cloned().collect();
+    for &start in &vertices {
+        if index.contains_key(&start) {
+            continue;
+        }
+        let mut call_stack = vec![Frame {
+            v: start,
+            child_idx: 0,
+        }];
+        index.insert(start, counter);
+        lowlink.insert(start, counter);
+        counter += 1;
+        stack.push(start);
+        on_stack.insert(start);
+
+        while let Some(frame) = call_stack.last_mut() {
+            let v = frame.v;
+            let neighbors = adjacency.get(&v).cloned().unwrap_or_default();
+            if frame.child_idx < neighbors.len() {
+                let w = neighbors[frame.child_idx];
+                frame.child_idx += 1;
+                if !index.contains_key(&w) {
+                    index.insert(w, counter);
+                    lowlink.insert(w, counter);
+                    counter += 1;
+                    stack.push(w);
+                    on_stack.insert(w);
+                    call_stack.push(Frame { v: w, child_idx: 0 });
+                } else if on_stack.contains(&w) {
+                    let v_low = lowlink[&v];
+                    lowlink.insert(v, v_low.min(index[&w]));
+                }
+            } else {
+                call_stack.pop();
+                if let Some(parent_frame) = call_stack.last() {
+                    let p = parent_frame.v;
+                    let v_low = lowlink[&v];
+                    let p_low = lowlink[&p];
+                    lowlink.insert(p, p_low.min(v_low));
+                }
+                if lowlink[&v] == index[&v] {
+                    let mut nodes: HashSet<ItemId> = HashSet::new();
+                    loop {
+                        let w = stack.pop().unwrap();
+                        on_stack.remove(&w);
+                        nodes.insert(w.0);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    if nodes.len() > 1 {
+                        components.push(nodes.into_iter().collect());
+                    }
+                }
+            }
+        }
+    }
+
+    for node in self_loops {
+        components.push(vec![node]);
+    }
+    components
+}
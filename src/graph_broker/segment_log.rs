@@ -0,0 +1,401 @@
+//! A disk-backed, append-only segment log for spilling per-path item ranges out of RAM.
+//!
+//! `parse_path_seq_update_tables_multiple` and friends (see `graph_broker::util`) accumulate
+//! every path's item ids directly into an in-memory `ItemTable`. On a pangenome large enough
+//! that the combined tables don't fit in RAM, there is no spill path -- the process either swaps
+//! or is killed partway through a parse that may have taken hours to get that far. This module
+//! is the spill target: [`SegmentLog::append`] writes one path's item range to an append-only
+//! segment file on disk and records its location in an in-memory offset index keyed by
+//! `num_path`, so [`SegmentLog::get`] can fetch it back later without holding the whole table in
+//! memory at once.
+//!
+//! Segments are capped at a configurable byte budget; once a segment would exceed it, the
+//! current segment is finalized (its trailing checksum written) and a new one with the next
+//! sequence number is started. On [`SegmentLog::open`], every existing segment is replayed in
+//! sequence-number order to rebuild the offset index. A segment that was only partially written
+//! -- the process was killed mid-`append`, or mid-finalize -- is detected via its header sequence
+//! number and trailing checksum (FNV-1a, not xxh3: there is no `Cargo.toml` in this tree to
+//! declare an `xxh3`/`twox-hash` dependency in, the same constraint noted in `graph_broker::cache`
+//! and `graph_broker::item_store`) and truncated back to its last fully-written record rather than
+//! aborting the whole reopen; the log resumes appending from that point.
+//!
+//! This operates purely as a standalone key-value spill store over `&[ItemId]` ranges rather than
+//! being wired into `ItemTable` itself, for the same reason `graph_broker::item_store` stops short
+//! of `ItemTable::items`: that would mean changing `update_tables`'s push-based API to go through
+//! a storage trait, which is a larger change than this module's scope.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use super::ItemId;
+
+/// Default byte budget for a single segment file before it is finalized and a new one started.
+/// Chosen as a middle ground: large enough that the fixed per-segment header/footer overhead is
+/// negligible, small enough that a crash only ever loses (at most) one segment's worth of
+/// not-yet-finalized records.
+pub const DEFAULT_SEGMENT_SIZE: usize = 64 * 1024 * 1024;
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    let mut hash = FNV_OFFSET;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+fn segment_path(dir: &Path, sequence: u64) -> PathBuf {
+    dir.join(format!("segment-{:020}.log", sequence))
+}
+
+/// Where one path's item range lives: which segment file, and the byte offset of its
+/// length-prefixed record within that segment.
+#[derive(Debug, Clone, Copy)]
+struct RecordLocation {
+    sequence: u64,
+    offset: u64,
+}
+
+/// An append-only segment log of per-path item ranges, with an in-memory index for lookup and
+/// crash-recovery on reopen.
+///
+/// On-disk layout per segment file `segment-<sequence>.log`:
+/// `[8 bytes sequence][record]*[8 bytes FNV-1a checksum of everything before it]`, where the
+/// trailing checksum is only present once a segment has been finalized (rolled over, or the log
+/// was closed cleanly). Each record is itself length-prefixed:
+/// `[8 bytes record length][8 bytes num_path][8 bytes item count][items, 8 bytes each]`.
+pub struct SegmentLog {
+    dir: PathBuf,
+    segment_size: usize,
+    index: HashMap<usize, RecordLocation>,
+    active_sequence: u64,
+    active_file: File,
+    active_len: u64,
+    next_sequence: u64,
+}
+
+impl SegmentLog {
+    /// Opens (creating if necessary) a segment log rooted at `dir`, replaying any existing
+    /// segments to rebuild the offset index and truncating a torn final write, if any, before
+    /// resuming appends.
+    pub fn open(dir: &Path, segment_size: usize) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+
+        let mut sequences: Vec<u64> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name();
+                let name = name.to_str()?;
+                let seq = name.strip_prefix("segment-")?.strip_suffix(".log")?;
+                seq.parse::<u64>().ok()
+            })
+            .collect();
+        sequences.sort_unstable();
+
+        let mut index = HashMap::new();
+        let mut last_sequence: Option<u64> = None;
+        let mut torn_tail: Option<(u64, u64)> = None; // (sequence, valid_len)
+
+        for sequence in sequences {
+            // a reopen after a crashed segment rotation could have left a stray segment with a
+            // sequence number that doesn't continue the run; stop replay there rather than trust
+            // it, since everything after it is unordered with respect to what came before
+            if let Some(last) = last_sequence {
+                if sequence != last + 1 {
+                    break;
+                }
+            }
+            let path = segment_path(dir, sequence);
+            let bytes = fs::read(&path)?;
+            let (valid_len, complete) = replay_segment(&bytes, &mut index, sequence)?;
+            last_sequence = Some(sequence);
+            if !complete {
+                torn_tail = Some((sequence, valid_len));
+                break;
+            }
+        }
+
+        let (active_sequence, active_len, next_sequence) = match torn_tail {
+            Some((sequence, valid_len)) => (sequence, valid_len, sequence + 1),
+            None => match last_sequence {
+                Some(sequence) => {
+                    // the last segment on disk was cleanly finalized; start a fresh one rather
+                    // than reopening it for appends (its footer checksum would otherwise need
+                    // recomputing on every further write)
+                    (sequence + 1, 0, sequence + 2)
+                }
+                None => (0, 0, 1),
+            },
+        };
+
+        let active_path = segment_path(dir, active_sequence);
+        let mut active_file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .truncate(false)
+            .open(&active_path)?;
+        active_file.set_len(active_len)?;
+        if active_len == 0 {
+            active_file.write_all(&active_sequence.to_le_bytes())?;
+            active_file.flush()?;
+            active_file.seek(SeekFrom::Start(8))?;
+        } else {
+            active_file.seek(SeekFrom::Start(active_len))?;
+        }
+        let active_len = active_len.max(8);
+
+        Ok(SegmentLog {
+            dir: dir.to_path_buf(),
+            segment_size,
+            index,
+            active_sequence,
+            active_file,
+            active_len,
+            next_sequence,
+        })
+    }
+
+    /// Appends `items` as the item range for `num_path`, rolling over to a new segment first if
+    /// the active one would exceed `segment_size`.
+    pub fn append(&mut self, num_path: usize, items: &[ItemId]) -> io::Result<()> {
+        let record_len = 16 + items.len() * 8;
+        if self.active_len as usize + 8 + record_len > self.segment_size && self.active_len > 8 {
+            self.roll_segment()?;
+        }
+
+        let offset = self.active_len;
+        let mut buf = Vec::with_capacity(8 + record_len);
+        buf.extend_from_slice(&(record_len as u64).to_le_bytes());
+        buf.extend_from_slice(&(num_path as u64).to_le_bytes());
+        buf.extend_from_slice(&(items.len() as u64).to_le_bytes());
+        for item in items {
+            buf.extend_from_slice(&item.0.to_le_bytes());
+        }
+        self.active_file.write_all(&buf)?;
+        self.active_len += buf.len() as u64;
+
+        self.index.insert(
+            num_path,
+            RecordLocation {
+                sequence: self.active_sequence,
+                offset,
+            },
+        );
+        Ok(())
+    }
+
+    /// Fetches back the item range previously stored for `num_path`, or `None` if nothing has
+    /// been appended for it.
+    pub fn get(&self, num_path: usize) -> io::Result<Option<Vec<ItemId>>> {
+        let location = match self.index.get(&num_path) {
+            Some(location) => *location,
+            None => return Ok(None),
+        };
+
+        // always reopen by path rather than `try_clone`-ing `active_file`: on a dup'd fd the
+        // underlying file offset is shared with the original, so seeking here to read would
+        // silently corrupt the write cursor `append` relies on for the active segment
+        let mut file = File::open(segment_path(&self.dir, location.sequence))?;
+        file.seek(SeekFrom::Start(location.offset))?;
+
+        let mut len_buf = [0u8; 8];
+        file.read_exact(&mut len_buf)?;
+        let record_len = u64::from_le_bytes(len_buf) as usize;
+        let mut record = vec![0u8; record_len];
+        file.read_exact(&mut record)?;
+
+        let count = u64::from_le_bytes(record[8..16].try_into().unwrap()) as usize;
+        let mut items = Vec::with_capacity(count);
+        for chunk in record[16..].chunks_exact(8) {
+            items.push(ItemId(u64::from_le_bytes(chunk.try_into().unwrap())));
+        }
+        Ok(Some(items))
+    }
+
+    /// Finalizes the active segment (writes its trailing checksum) and starts a fresh one with
+    /// the next sequence number.
+    fn roll_segment(&mut self) -> io::Result<()> {
+        self.active_file.flush()?;
+        self.active_file.seek(SeekFrom::Start(0))?;
+        let mut body = vec![0u8; self.active_len as usize];
+        self.active_file.read_exact(&mut body)?;
+        let checksum = fnv1a64(&body);
+        self.active_file.seek(SeekFrom::End(0))?;
+        self.active_file.write_all(&checksum.to_le_bytes())?;
+        self.active_file.flush()?;
+
+        self.active_sequence = self.next_sequence;
+        self.next_sequence += 1;
+        let path = segment_path(&self.dir, self.active_sequence);
+        self.active_file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(&path)?;
+        self.active_file
+            .write_all(&self.active_sequence.to_le_bytes())?;
+        self.active_file.flush()?;
+        self.active_len = 8;
+        Ok(())
+    }
+
+    /// Finalizes the active segment so a subsequent [`SegmentLog::open`] sees it as clean rather
+    /// than replaying it as a torn tail.
+    pub fn close(mut self) -> io::Result<()> {
+        self.active_file.flush()?;
+        self.active_file.seek(SeekFrom::Start(0))?;
+        let mut body = vec![0u8; self.active_len as usize];
+        self.active_file.read_exact(&mut body)?;
+        let checksum = fnv1a64(&body);
+        self.active_file.seek(SeekFrom::End(0))?;
+        self.active_file.write_all(&checksum.to_le_bytes())?;
+        self.active_file.flush()
+    }
+}
+
+/// Replays a single segment's bytes into `index`, returning `(valid_len, complete)`:
+/// `valid_len` is the byte offset of the last fully-parsed record boundary, and `complete` is
+/// true only if that boundary is immediately followed by a checksum footer that matches the
+/// bytes before it -- i.e. the segment was cleanly finalized and needs no truncation.
+fn replay_segment(
+    bytes: &[u8],
+    index: &mut HashMap<usize, RecordLocation>,
+    sequence: u64,
+) -> io::Result<(u64, bool)> {
+    if bytes.len() < 8 {
+        // a crash right after creating the segment file, before its header was even fully
+        // written -- nothing is recoverable, so treat it as an empty, torn segment
+        return Ok((0, false));
+    }
+    let stored_sequence = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+    if stored_sequence != sequence {
+        // the header itself is torn/corrupt -- nothing in this segment can be trusted
+        return Ok((0, false));
+    }
+
+    let mut pos = 8usize;
+    while pos + 8 <= bytes.len() {
+        let record_len = u64::from_le_bytes(bytes[pos..pos + 8].try_into().unwrap()) as usize;
+        let record_start = pos + 8;
+        if record_len < 16 || record_start + record_len > bytes.len() {
+            break;
+        }
+        let record = &bytes[record_start..record_start + record_len];
+        let num_path = u64::from_le_bytes(record[0..8].try_into().unwrap()) as usize;
+        index.insert(
+            num_path,
+            RecordLocation {
+                sequence,
+                offset: pos as u64,
+            },
+        );
+        pos = record_start + record_len;
+    }
+
+    // `pos` is now the offset right after the last fully-parsed record; check whether it's
+    // immediately followed by a valid checksum footer over everything before it
+    if pos + 8 == bytes.len() {
+        let stored_checksum = u64::from_le_bytes(bytes[pos..pos + 8].try_into().unwrap());
+        if fnv1a64(&bytes[..pos]) == stored_checksum {
+            return Ok((bytes.len() as u64, true));
+        }
+    }
+    Ok((pos as u64, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(values: &[u64]) -> Vec<ItemId> {
+        values.iter().map(|&v| ItemId(v)).collect()
+    }
+
+    #[test]
+    fn test_append_and_get_round_trip() {
+        let dir = std::env::temp_dir().join("panacus_segment_log_test_round_trip");
+        fs::remove_dir_all(&dir).ok();
+
+        let mut log = SegmentLog::open(&dir, DEFAULT_SEGMENT_SIZE).unwrap();
+        log.append(0, &items(&[1, 2, 3])).unwrap();
+        log.append(1, &items(&[4, 5])).unwrap();
+        log.append(2, &items(&[])).unwrap();
+
+        assert_eq!(log.get(0).unwrap(), Some(items(&[1, 2, 3])));
+        assert_eq!(log.get(1).unwrap(), Some(items(&[4, 5])));
+        assert_eq!(log.get(2).unwrap(), Some(items(&[])));
+        assert_eq!(log.get(3).unwrap(), None);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_reopen_replays_existing_segments() {
+        let dir = std::env::temp_dir().join("panacus_segment_log_test_reopen");
+        fs::remove_dir_all(&dir).ok();
+
+        {
+            let mut log = SegmentLog::open(&dir, DEFAULT_SEGMENT_SIZE).unwrap();
+            log.append(0, &items(&[10, 20])).unwrap();
+            log.append(1, &items(&[30])).unwrap();
+            log.close().unwrap();
+        }
+
+        let log = SegmentLog::open(&dir, DEFAULT_SEGMENT_SIZE).unwrap();
+        assert_eq!(log.get(0).unwrap(), Some(items(&[10, 20])));
+        assert_eq!(log.get(1).unwrap(), Some(items(&[30])));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_rolls_over_to_new_segment_when_full() {
+        let dir = std::env::temp_dir().join("panacus_segment_log_test_rollover");
+        fs::remove_dir_all(&dir).ok();
+
+        // a tiny segment budget forces a rollover after the very first record
+        let mut log = SegmentLog::open(&dir, 32).unwrap();
+        log.append(0, &items(&[1, 2, 3, 4])).unwrap();
+        log.append(1, &items(&[5])).unwrap();
+
+        assert_eq!(log.get(0).unwrap(), Some(items(&[1, 2, 3, 4])));
+        assert_eq!(log.get(1).unwrap(), Some(items(&[5])));
+        assert!(segment_path(&dir, 1).exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_torn_final_write_is_truncated_not_fatal() {
+        let dir = std::env::temp_dir().join("panacus_segment_log_test_torn");
+        fs::remove_dir_all(&dir).ok();
+
+        {
+            let mut log = SegmentLog::open(&dir, DEFAULT_SEGMENT_SIZE).unwrap();
+            log.append(0, &items(&[1, 2])).unwrap();
+            log.append(1, &items(&[3, 4, 5])).unwrap();
+            // deliberately skip close(): no checksum footer is written, simulating a crash
+        }
+
+        // simulate a crash mid-write of a third record: valid header + two full records,
+        // followed by a partial length-prefixed record
+        let path = segment_path(&dir, 0);
+        let mut bytes = fs::read(&path).unwrap();
+        bytes.extend_from_slice(&999u64.to_le_bytes());
+        bytes.extend_from_slice(&[0xaa; 5]);
+        fs::write(&path, &bytes).unwrap();
+
+        let log = SegmentLog::open(&dir, DEFAULT_SEGMENT_SIZE).unwrap();
+        assert_eq!(log.get(0).unwrap(), Some(items(&[1, 2])));
+        assert_eq!(log.get(1).unwrap(), Some(items(&[3, 4, 5])));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
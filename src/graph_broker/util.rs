@@ -5,7 +5,6 @@ use std::time::Instant;
 use std::{
     collections::HashMap,
     io::{BufRead, BufReader, Read},
-    sync::{atomic::AtomicU32, Arc, Mutex},
 };
 
 use rayon::prelude::*;
@@ -13,8 +12,8 @@ use rayon::prelude::*;
 use crate::{
     graph_broker::Edge,
     util::{
-        intersects, is_contained, ActiveTable, CountType, IntervalContainer, ItemTable, Wrap,
-        SIZE_T,
+        intersects, is_contained, merge_interval, ActiveTable, CountType, DepthTable, FxHashMap,
+        IntervalContainer, IntervalIndex, IntervalTree, ItemTable, ShardedMap, SIZE_T,
     },
 };
 
@@ -22,17 +21,102 @@ use super::{abacus::GraphMask, graph::GraphStorage, ItemId, Orientation, PathSeg
 
 const CHUNK_SIZE: usize = 4096;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GfaParseErrorKind {
+    UnknownNode,
+    UnknownEdge,
+    MalformedField,
+    NonUtf8,
+}
+
+impl std::fmt::Display for GfaParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GfaParseErrorKind::UnknownNode => write!(f, "unknown node"),
+            GfaParseErrorKind::UnknownEdge => write!(f, "unknown edge"),
+            GfaParseErrorKind::MalformedField => write!(f, "malformed field"),
+            GfaParseErrorKind::NonUtf8 => write!(f, "non-UTF8 token"),
+        }
+    }
+}
+
+// a single malformed P/W record, carrying enough context for a caller to report it
+// programmatically instead of just aborting the process; see `crate::io::ParseError` for the
+// analogous type used by the BED/group-file parsers
+#[derive(Debug, Clone)]
+pub struct GfaParseError {
+    pub kind: GfaParseErrorKind,
+    pub line: usize,
+    pub byte_offset: Option<usize>,
+    pub reason: String,
+}
+
+impl std::fmt::Display for GfaParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.byte_offset {
+            Some(o) => write!(
+                f,
+                "{} at GFA line {}, byte {}: {}",
+                self.kind, self.line, o, self.reason
+            ),
+            None => write!(f, "{} at GFA line {}: {}", self.kind, self.line, self.reason),
+        }
+    }
+}
+
+impl std::error::Error for GfaParseError {}
+
+/// Per-path tally of `+`/`-` (forward/backward) oriented node visits, accumulated alongside
+/// `(num_nodes_path, bp_len)` by `parse_path_seq_update_tables`/`parse_walk_seq_update_tables`
+/// and carried in `paths_len`'s value type by the `parse_gfa_paths_walks*` orchestrators.
+/// `inversion_fraction` is a cheap per-path signal for assembly-orientation bias: a value near
+/// 0.5 means the path alternates strand often (e.g. spans a large inversion relative to the
+/// graph's reference orientation), while a value near 0.0 or 1.0 means it's overwhelmingly
+/// one strand, as an unrearranged haplotype typically is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StrandComposition {
+    pub plus: u32,
+    pub minus: u32,
+}
+
+impl StrandComposition {
+    pub fn inversion_fraction(&self) -> f64 {
+        let total = self.plus + self.minus;
+        if total == 0 {
+            0.0
+        } else {
+            self.minus as f64 / total as f64
+        }
+    }
+}
+
+impl std::ops::Add for StrandComposition {
+    type Output = StrandComposition;
+    fn add(self, other: StrandComposition) -> StrandComposition {
+        StrandComposition {
+            plus: self.plus + other.plus,
+            minus: self.minus + other.minus,
+        }
+    }
+}
+
 pub fn parse_gfa_paths_walks_multiple<R: Read>(
     data: &mut BufReader<R>,
     graph_mask: &GraphMask,
     graph_storage: &GraphStorage,
     count_types: &Vec<CountType>,
-) -> (
-    Vec<ItemTable>,
-    Vec<Option<ActiveTable>>,
-    Option<IntervalContainer>,
-    HashMap<PathSegment, (u32, u32)>,
-) {
+    lenient: bool,
+    min_covered_fraction: f64,
+    end_exclusion: usize,
+) -> Result<
+    (
+        Vec<ItemTable>,
+        Vec<Option<ActiveTable>>,
+        Option<IntervalContainer>,
+        FxHashMap<PathSegment, (u32, u32, StrandComposition)>,
+    ),
+    GfaParseError,
+> {
     log::info!("parsing path + walk sequences");
     let mut item_tables =
         vec![ItemTable::new(graph_storage.path_segments.len()); count_types.len()];
@@ -42,17 +126,37 @@ pub fn parse_gfa_paths_walks_multiple<R: Read>(
 
     let mut num_path = 0;
     let complete: Vec<(usize, usize)> = vec![(0, usize::MAX)];
-    let mut paths_len: HashMap<PathSegment, (u32, u32)> = HashMap::new();
+    let mut paths_len: FxHashMap<PathSegment, (u32, u32, StrandComposition)> = FxHashMap::default();
 
     let mut buf = vec![];
+    let mut line = 0;
     let timer = Instant::now();
+    // reused across every `P`/`W` record that takes the slow path below, so its `items` `Vec`
+    // keeps its capacity between records; a `P` record's parse still allocates its own result,
+    // which is copied in (the per-count-type redundant re-parsing of the same
+    // record this loop already does, tracked separately, is unaffected either way)
+    let mut scratch = PathWalkScratch::default();
     while data.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+        line += 1;
         if buf[0] == b'P' || buf[0] == b'W' {
-            let (path_seg, buf_path_seg) = match buf[0] {
-                b'P' => parse_path_identifier(&buf),
-                b'W' => parse_walk_identifier(&buf),
+            let identifier = match buf[0] {
+                b'P' => parse_path_identifier(&buf, line),
+                b'W' => parse_walk_identifier(&buf, line),
                 _ => unreachable!(),
             };
+            let (path_seg, buf_path_seg) = match identifier {
+                Ok(v) => v,
+                Err(e) if lenient => {
+                    log::warn!("skipping malformed record: {}", e);
+                    for item_table in &mut item_tables {
+                        item_table.id_prefsum[num_path + 1] += item_table.id_prefsum[num_path];
+                    }
+                    num_path += 1;
+                    buf.clear();
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
 
             log::debug!("processing path {}", &path_seg);
 
@@ -108,7 +212,7 @@ pub fn parse_gfa_paths_walks_multiple<R: Read>(
             }
 
             // TODO: separate this step and do it twice (?)
-            let mut indices: HashMap<CountType, Vec<usize>> = HashMap::new();
+            let mut indices: FxHashMap<CountType, Vec<usize>> = FxHashMap::default();
             for (i, count_type) in count_types
                 .iter()
                 .map(|c| match c {
@@ -123,7 +227,7 @@ pub fn parse_gfa_paths_walks_multiple<R: Read>(
                     indices.insert(*count_type, vec![i]);
                 }
             }
-            indices.into_iter().for_each(|(count, is)| {
+            let record_result: Result<(), GfaParseError> = indices.into_iter().try_for_each(|(count, is)| {
                 if count != CountType::Edge
                     && (graph_mask.include_coords.is_none()
                         || is_contained(include_coords, &(start, end)))
@@ -137,61 +241,99 @@ pub fn parse_gfa_paths_walks_multiple<R: Read>(
                     } else {
                         exclude_tables.iter_mut().enumerate().filter(|(i, _)| is.contains(i)).map(|(_, e)| e).collect()
                     };
-                    let (num_added_nodes, bp_len) = match buf[0] {
-                        b'P' => parse_path_seq_update_tables_multiple(
-                            buf_path_seg,
-                            graph_storage,
-                            &mut item_tables[is[0]],
-                            ex,
-                            num_path,
-                        ),
+                    // `parse_path_seq_update_tables_multiple` doesn't track per-node orientation
+                    // (it's the multi-count-type fast path, which fills one item table and flags
+                    // several exclude tables at once from a plain id list), so P-lines taking
+                    // this branch report a default, all-zero `StrandComposition` here.
+                    let (num_added_nodes, bp_len, strand) = match buf[0] {
+                        b'P' => {
+                            let (n, b) = parse_path_seq_update_tables_multiple(
+                                buf_path_seg,
+                                graph_storage,
+                                &mut item_tables[is[0]],
+                                ex,
+                                num_path,
+                                line,
+                            )?;
+                            (n, b, StrandComposition::default())
+                        }
                         b'W' => parse_walk_seq_update_tables(
                             buf_path_seg,
                             graph_storage,
                             &mut item_tables[is[0]],
                             ex[0].as_mut(),
                             num_path,
-                        ),
+                            line,
+                        )?,
                         _ => unreachable!(),
                     };
-                    paths_len.insert(path_seg.clone(), (num_added_nodes, bp_len));
+                    paths_len.insert(path_seg.clone(), (num_added_nodes, bp_len, strand));
                 } else {
-                    let sids = match buf[0] {
-                        b'P' => parse_path_seq_to_item_vec(buf_path_seg, graph_storage),
-                        b'W' => parse_walk_seq_to_item_vec(buf_path_seg, graph_storage),
+                    match buf[0] {
+                        b'P' => {
+                            let mut unused_overlaps = Vec::new();
+                            parse_path_seq_to_item_vec_into(
+                                buf_path_seg,
+                                graph_storage,
+                                line,
+                                &mut scratch.items,
+                                &mut unused_overlaps,
+                            )
+                        }
+                        b'W' => parse_walk_seq_to_item_vec_into(
+                            buf_path_seg,
+                            graph_storage,
+                            line,
+                            &mut scratch.items,
+                        ),
                         _ => unreachable!(),
-                    };
+                    }?;
                     let mut exclude_tables_red = exclude_tables.iter_mut().enumerate().filter(|(i, _)| is.contains(i)).map(|(_, e)| e).collect();
                     match count {
                         CountType::Node | CountType::Bp => {
                             //eprintln!("{:?}, {:?}", count, exclude_tables[i]);
+                            let strand = strand_composition(&scratch.items);
+                            let include_tree = IntervalTree::build(include_coords);
+                            let exclude_tree = IntervalTree::build(exclude_coords);
                             let (node_len, bp_len) = update_tables_multiple(
                                 &mut item_tables[is[0]],
                                 &mut subset_covered_bps.as_mut(),
                                 exclude_tables_red,
                                 num_path,
                                 graph_storage,
-                                sids,
-                                include_coords,
-                                exclude_coords,
+                                &scratch.items,
+                                &include_tree,
+                                &exclude_tree,
                                 start,
+                                min_covered_fraction,
                             );
-                            paths_len.insert(path_seg.clone(), (node_len as u32, bp_len as u32));
+                            paths_len.insert(path_seg.clone(), (node_len as u32, bp_len as u32, strand));
                         }
                         CountType::Edge => update_tables_edgecount(
                             &mut item_tables[is[0]],
                             &mut exclude_tables_red[0].as_mut(),
                             num_path,
                             graph_storage,
-                            sids,
+                            &scratch.items,
                             include_coords,
                             exclude_coords,
                             start,
-                        ),
+                            end_exclusion,
+                            line,
+                        )?,
                         CountType::All => unreachable!("inadmissable count type"),
+                        CountType::Kmer => unreachable!("k-mer counting not yet wired through update_tables"),
+                        CountType::Minimizer => unreachable!("minimizer counting not yet wired through update_tables"),
+                        CountType::Branch => unreachable!("branch counting not yet wired through update_tables"),
                     };
                 }
+                Ok(())
             });
+            match record_result {
+                Ok(()) => {}
+                Err(e) if lenient => log::warn!("skipping malformed record: {}", e),
+                Err(e) => return Err(e),
+            }
             num_path += 1;
         }
         buf.clear();
@@ -208,41 +350,125 @@ pub fn parse_gfa_paths_walks_multiple<R: Read>(
     //        i, count_types[i], item_tables[i], exclude_tables[i]
     //    );
     //}
-    (item_tables, exclude_tables, subset_covered_bps, paths_len)
+    Ok((item_tables, exclude_tables, subset_covered_bps, paths_len))
 }
 
+/// Tallies `StrandComposition` directly from an already-parsed `(ItemId, Orientation)` path, for
+/// the slow-path branches of the `parse_gfa_paths_walks*` orchestrators that go through
+/// `update_tables`/`update_tables_multiple` rather than the fast per-byte path/walk parsers (which
+/// tally strand composition themselves -- see `parse_path_seq_update_tables` and
+/// `parse_walk_seq_update_tables`).
+fn strand_composition(path: &[(ItemId, Orientation)]) -> StrandComposition {
+    let plus = path
+        .iter()
+        .filter(|(_, o)| *o == Orientation::Forward)
+        .count() as u32;
+    StrandComposition {
+        plus,
+        minus: path.len() as u32 - plus,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn parse_gfa_paths_walks<R: Read>(
     data: &mut BufReader<R>,
     graph_mask: &GraphMask,
     graph_storage: &GraphStorage,
     count: &CountType,
-) -> (
-    ItemTable,
-    Option<ActiveTable>,
-    Option<IntervalContainer>,
-    HashMap<PathSegment, (u32, u32)>,
-) {
+    lenient: bool,
+    overlap_adjusted_bp: bool,
+    sort_shards: bool,
+    min_covered_fraction: f64,
+    end_exclusion: usize,
+    mut depth_table: Option<&mut DepthTable>,
+) -> Result<
+    (
+        ItemTable,
+        Option<ActiveTable>,
+        Option<IntervalContainer>,
+        FxHashMap<PathSegment, (u32, u32, StrandComposition)>,
+    ),
+    GfaParseError,
+> {
     log::info!("parsing path + walk sequences");
-    // TODO: item_table will be returned
     let mut item_table = ItemTable::new(graph_storage.path_segments.len());
 
-    // TODO: subset_covered_bps and exclude_table will be returned
-    let (mut subset_covered_bps, mut exclude_table, include_map, exclude_map) =
+    let (subset_covered_bps, exclude_table, include_map, exclude_map) =
         graph_mask.load_optional_subsetting(graph_storage, count);
 
-    let mut num_path = 0;
     let complete: Vec<(usize, usize)> = vec![(0, usize::MAX)];
-    let mut paths_len: HashMap<PathSegment, (u32, u32)> = HashMap::new();
+    let timer = Instant::now();
+
+    // The fast path below reads every P/W record up front and then farms the (CPU-heavy)
+    // table updates out to a rayon pool, one path per task, merging results back in the
+    // original path order at the end -- see `parse_gfa_paths_walks_parallel`. That merge
+    // relies on each worker owning a *local*, path-sized `ActiveTable`/`IntervalContainer`,
+    // which is cheap for `subset_covered_bps` (a sparse map) but would mean allocating a
+    // full node-sized `ActiveTable` per path when an exclude list is in play -- for graphs
+    // with many paths that multiplies memory by the path count. Edge counting also keeps
+    // its own offset bookkeeping in `update_tables_edgecount`, which this pipeline doesn't
+    // cover yet. `depth_table` likewise needs every path's contribution folded into one shared
+    // map in path order, which the fast path's per-task-local tables aren't set up to do. All
+    // three cases therefore keep taking the original one-record-at-a-time route;
+    // `parse_gfa_paths_walks_multiple` (the multi-count-type sibling of this function) is
+    // left on that route entirely for the same reason.
+    if exclude_table.is_some() || count == &CountType::Edge || depth_table.is_some() {
+        return parse_gfa_paths_walks_sequential(
+            data,
+            graph_mask,
+            graph_storage,
+            count,
+            item_table,
+            subset_covered_bps,
+            exclude_table,
+            include_map,
+            exclude_map,
+            &complete,
+            timer,
+            lenient,
+            overlap_adjusted_bp,
+            sort_shards,
+            min_covered_fraction,
+            end_exclusion,
+            depth_table.as_deref_mut(),
+        );
+    }
 
+    enum Step {
+        // the path was skipped, but still occupies a `num_path` slot whose prefix sum must
+        // be carried forward
+        Skip,
+        Work {
+            tag: u8,
+            path_seg: PathSegment,
+            buf_path_seg: Vec<u8>,
+            include_coords: Vec<(usize, usize)>,
+            fast_path: bool,
+            line: usize,
+        },
+    }
+
+    let mut steps = Vec::new();
     let mut buf = vec![];
-    let timer = Instant::now();
+    let mut line = 0;
     while data.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+        line += 1;
         if buf[0] == b'P' || buf[0] == b'W' {
-            let (path_seg, buf_path_seg) = match buf[0] {
-                b'P' => parse_path_identifier(&buf),
-                b'W' => parse_walk_identifier(&buf),
+            let identifier = match buf[0] {
+                b'P' => parse_path_identifier(&buf, line),
+                b'W' => parse_walk_identifier(&buf, line),
                 _ => unreachable!(),
             };
+            let (path_seg, buf_path_seg) = match identifier {
+                Ok(v) => v,
+                Err(e) if lenient => {
+                    log::warn!("skipping malformed record: {}", e);
+                    steps.push(Step::Skip);
+                    buf.clear();
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
 
             log::debug!("processing path {}", &path_seg);
 
@@ -261,6 +487,300 @@ pub fn parse_gfa_paths_walks<R: Read>(
                     }
                 }
             };
+
+            let (start, end) = path_seg.coords().unwrap_or((0, usize::MAX));
+
+            // exclude_coords is always empty here: `exclude_table` being `None` means
+            // `graph_mask.exclude_coords` is `None` too (see `load_optional_subsetting`),
+            // so `exclude_map` never holds anything to look up.
+            if graph_mask.include_coords.is_some() && !intersects(include_coords, &(start, end)) {
+                log::debug!(
+                    "path {} does not intersect with subset coordinates {:?} and therefore is skipped from processing",
+                    &path_seg, &include_coords
+                );
+                steps.push(Step::Skip);
+            } else {
+                let fast_path = graph_mask.include_coords.is_none()
+                    || is_contained(include_coords, &(start, end));
+                if fast_path {
+                    log::debug!("path {} is fully contained within subset coordinates {:?} and is eligible for full parallel processing", path_seg, include_coords);
+                }
+                steps.push(Step::Work {
+                    tag: buf[0],
+                    path_seg,
+                    buf_path_seg: buf_path_seg.to_vec(),
+                    include_coords: include_coords.to_vec(),
+                    fast_path,
+                    line,
+                });
+            }
+            buf.clear();
+        } else {
+            buf.clear();
+        }
+    }
+
+    // Each worker below owns a `local_item_table`/`local_subset_covered_bps` it fills with no
+    // synchronization at all -- no per-bucket mutex, no shared atomic counter -- and hands back
+    // as a `WorkResult`; the merge loop after `.collect()` then folds every worker's result into
+    // `item_table` strictly in `num_path` order, so the reduction is both lock-free and
+    // deterministic regardless of which order workers actually finish in.
+    struct WorkResult {
+        item_table: ItemTable,
+        subset_covered_bps: Option<IntervalContainer>,
+    }
+
+    // path lengths don't need the strict `num_path` ordering the item-table/prefix-sum merge
+    // below does, so workers insert them concurrently into a sharded map instead of going
+    // through the serial merge loop like `item_table` has to
+    let paths_len_concurrent: ShardedMap<PathSegment, (u32, u32, StrandComposition)> =
+        ShardedMap::new(rayon::current_num_threads());
+
+    let track_subset_covered_bps = subset_covered_bps.is_some();
+    let results: Vec<Result<WorkResult, GfaParseError>> = steps
+        .par_iter()
+        .filter_map(|step| match step {
+            Step::Skip => None,
+            Step::Work {
+                tag,
+                path_seg,
+                buf_path_seg,
+                include_coords,
+                fast_path,
+                line,
+            } => {
+                let mut local_item_table = ItemTable::new(1);
+                if *fast_path {
+                    let result = match tag {
+                        b'P' => parse_path_seq_update_tables(
+                            buf_path_seg,
+                            graph_storage,
+                            &mut local_item_table,
+                            None,
+                            0,
+                            overlap_adjusted_bp,
+                            *line,
+                        ),
+                        b'W' => parse_walk_seq_update_tables(
+                            buf_path_seg,
+                            graph_storage,
+                            &mut local_item_table,
+                            None,
+                            0,
+                            *line,
+                        ),
+                        _ => unreachable!(),
+                    };
+                    let (num_added_nodes, bp_len, strand) = match result {
+                        Ok(v) => v,
+                        Err(e) => return Some(Err(e)),
+                    };
+                    paths_len_concurrent.insert(path_seg.clone(), (num_added_nodes, bp_len, strand));
+                    Some(Ok(WorkResult {
+                        item_table: local_item_table,
+                        subset_covered_bps: None,
+                    }))
+                } else {
+                    let (sids, overlaps) = match match tag {
+                        b'P' => parse_path_seq_to_item_vec(buf_path_seg, graph_storage, *line),
+                        b'W' => parse_walk_seq_to_item_vec(buf_path_seg, graph_storage, *line)
+                            .map(|sids| {
+                                let overlaps = vec![0; sids.len().saturating_sub(1)];
+                                (sids, overlaps)
+                            }),
+                        _ => unreachable!(),
+                    } {
+                        Ok(sids) => sids,
+                        Err(e) => return Some(Err(e)),
+                    };
+                    let start = path_seg.coords().unwrap_or((0, usize::MAX)).0;
+                    let strand = strand_composition(&sids);
+                    let mut local_subset_covered_bps =
+                        track_subset_covered_bps.then(IntervalContainer::new);
+                    let include_tree = IntervalTree::build(include_coords);
+                    let exclude_tree = IntervalTree::default();
+                    let (node_len, bp_len) = match count {
+                        CountType::Node | CountType::Bp => update_tables(
+                            &mut local_item_table,
+                            &mut local_subset_covered_bps.as_mut(),
+                            &mut None,
+                            0,
+                            graph_storage,
+                            &sids,
+                            &include_tree,
+                            &exclude_tree,
+                            start,
+                            min_covered_fraction,
+                            end_exclusion,
+                            &overlaps,
+                            overlap_adjusted_bp,
+                            sort_shards,
+                            &mut None,
+                        ),
+                        CountType::Edge => unreachable!(
+                            "edge counting is handled via parse_gfa_paths_walks_sequential"
+                        ),
+                        CountType::All => unreachable!("inadmissable count type"),
+                        CountType::Kmer => {
+                            unreachable!("k-mer counting not yet wired through update_tables")
+                        }
+                        CountType::Minimizer => unreachable!(
+                            "minimizer counting not yet wired through update_tables"
+                        ),
+                        CountType::Branch => {
+                            unreachable!("branch counting not yet wired through update_tables")
+                        }
+                    };
+                    paths_len_concurrent
+                        .insert(path_seg.clone(), (node_len as u32, bp_len as u32, strand));
+                    Some(Ok(WorkResult {
+                        item_table: local_item_table,
+                        subset_covered_bps: local_subset_covered_bps,
+                    }))
+                }
+            }
+        })
+        .collect();
+
+    if !lenient {
+        if let Some(e) = results.iter().find_map(|r| r.as_ref().err().cloned()) {
+            return Err(e);
+        }
+    }
+
+    // merge results back in, strictly in `num_path` order: the prefix sum of a path's
+    // bucket can only be computed once every earlier path's contribution to that bucket is
+    // already final, regardless of which order the workers above actually finished in.
+    let mut subset_covered_bps = subset_covered_bps;
+    let mut results = results.into_iter();
+    for (num_path, step) in steps.into_iter().enumerate() {
+        match step {
+            Step::Skip => {
+                for bucket in 0..SIZE_T {
+                    item_table.id_prefsum[bucket][num_path + 1] =
+                        item_table.id_prefsum[bucket][num_path];
+                }
+            }
+            Step::Work { .. } => {
+                let result = results
+                    .next()
+                    .expect("one WorkResult per Step::Work, in order");
+                let result = match result {
+                    Ok(result) => result,
+                    Err(e) => {
+                        // `lenient` already guaranteed above; treat the failed record like a
+                        // skipped one so its `num_path` slot still carries the prefix sum forward
+                        log::warn!("skipping malformed record: {}", e);
+                        for bucket in 0..SIZE_T {
+                            item_table.id_prefsum[bucket][num_path + 1] =
+                                item_table.id_prefsum[bucket][num_path];
+                        }
+                        continue;
+                    }
+                };
+                for bucket in 0..SIZE_T {
+                    let added = result.item_table.id_prefsum[bucket][1];
+                    item_table.id_prefsum[bucket][num_path + 1] =
+                        item_table.id_prefsum[bucket][num_path] + added;
+                    item_table.items[bucket].extend(result.item_table.items[bucket].iter());
+                }
+                if let Some(local) = result.subset_covered_bps {
+                    subset_covered_bps.as_mut().unwrap().merge_from(local);
+                }
+            }
+        }
+    }
+    let paths_len = paths_len_concurrent.into_inner();
+
+    let duration = timer.elapsed();
+    log::info!(
+        "func done; count: {:?}; time elapsed: {:?}",
+        count,
+        duration
+    );
+    Ok((item_table, exclude_table, subset_covered_bps, paths_len))
+}
+
+// the original, one-record-at-a-time implementation of `parse_gfa_paths_walks`, kept around
+// as the fallback for the cases the rayon-parallel pipeline above doesn't cover (an active
+// exclude list, edge counting, or a depth table -- see the doc comment at its call site)
+#[allow(clippy::too_many_arguments)]
+fn parse_gfa_paths_walks_sequential<R: Read>(
+    data: &mut BufReader<R>,
+    graph_mask: &GraphMask,
+    graph_storage: &GraphStorage,
+    count: &CountType,
+    mut item_table: ItemTable,
+    mut subset_covered_bps: Option<IntervalContainer>,
+    mut exclude_table: Option<ActiveTable>,
+    include_map: HashMap<String, Vec<(usize, usize)>>,
+    exclude_map: HashMap<String, Vec<(usize, usize)>>,
+    complete: &[(usize, usize)],
+    timer: Instant,
+    lenient: bool,
+    overlap_adjusted_bp: bool,
+    sort_shards: bool,
+    min_covered_fraction: f64,
+    end_exclusion: usize,
+    mut depth_table: Option<&mut DepthTable>,
+) -> Result<
+    (
+        ItemTable,
+        Option<ActiveTable>,
+        Option<IntervalContainer>,
+        FxHashMap<PathSegment, (u32, u32, StrandComposition)>,
+    ),
+    GfaParseError,
+> {
+    let mut num_path = 0;
+    let mut paths_len: FxHashMap<PathSegment, (u32, u32, StrandComposition)> = FxHashMap::default();
+
+    let mut buf = vec![];
+    let mut line = 0;
+    // reused across every `P`/`W` record that takes the slow path below, so the `items`/`overlaps`
+    // `Vec`s keep their capacity between records; a `P` record's parse still allocates its own
+    // result, which is copied in
+    let mut scratch = PathWalkScratch::default();
+    while data.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+        line += 1;
+        if buf[0] == b'P' || buf[0] == b'W' {
+            let identifier = match buf[0] {
+                b'P' => parse_path_identifier(&buf, line),
+                b'W' => parse_walk_identifier(&buf, line),
+                _ => unreachable!(),
+            };
+            let (path_seg, buf_path_seg) = match identifier {
+                Ok(v) => v,
+                Err(e) if lenient => {
+                    log::warn!("skipping malformed record: {}", e);
+                    for bucket in 0..SIZE_T {
+                        item_table.id_prefsum[bucket][num_path + 1] =
+                            item_table.id_prefsum[bucket][num_path];
+                    }
+                    num_path += 1;
+                    buf.clear();
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            log::debug!("processing path {}", &path_seg);
+
+            let include_coords = if graph_mask.include_coords.is_none() {
+                complete
+            } else {
+                match include_map.get(&path_seg.id()) {
+                    None => &[],
+                    Some(coords) => {
+                        log::debug!(
+                            "found include coords {:?} for path segment {}",
+                            &coords[..],
+                            &path_seg.id()
+                        );
+                        &coords[..]
+                    }
+                }
+            };
             let exclude_coords = if graph_mask.exclude_coords.is_none() {
                 &[]
             } else {
@@ -288,15 +808,16 @@ pub fn parse_gfa_paths_walks<R: Read>(
                     &path_seg, &include_coords, &exclude_coords);
 
                 // update prefix sum
-                // TODO: do this for all 3 tables
-                item_table.id_prefsum[num_path + 1] += item_table.id_prefsum[num_path];
+                for bucket in 0..SIZE_T {
+                    item_table.id_prefsum[bucket][num_path + 1] =
+                        item_table.id_prefsum[bucket][num_path];
+                }
 
                 num_path += 1;
                 buf.clear();
                 continue;
             }
 
-            // TODO: separate this step and do it twice (?)
             if count != &CountType::Edge
                 && (graph_mask.include_coords.is_none()
                     || is_contained(include_coords, &(start, end)))
@@ -309,13 +830,15 @@ pub fn parse_gfa_paths_walks<R: Read>(
                 } else {
                     exclude_table.as_mut()
                 };
-                let (num_added_nodes, bp_len) = match buf[0] {
+                let update_result = match buf[0] {
                     b'P' => parse_path_seq_update_tables(
                         buf_path_seg,
                         graph_storage,
                         &mut item_table,
                         ex,
                         num_path,
+                        overlap_adjusted_bp,
+                        line,
                     ),
                     b'W' => parse_walk_seq_update_tables(
                         buf_path_seg,
@@ -323,43 +846,103 @@ pub fn parse_gfa_paths_walks<R: Read>(
                         &mut item_table,
                         ex,
                         num_path,
+                        line,
                     ),
                     _ => unreachable!(),
                 };
-                paths_len.insert(path_seg, (num_added_nodes, bp_len));
+                let (num_added_nodes, bp_len, strand) = match update_result {
+                    Ok(v) => v,
+                    Err(e) if lenient => {
+                        log::warn!("skipping malformed record: {}", e);
+                        num_path += 1;
+                        buf.clear();
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
+                paths_len.insert(path_seg, (num_added_nodes, bp_len, strand));
             } else {
-                let sids = match buf[0] {
-                    b'P' => parse_path_seq_to_item_vec(buf_path_seg, graph_storage),
-                    b'W' => parse_walk_seq_to_item_vec(buf_path_seg, graph_storage),
+                let sids_result = match buf[0] {
+                    b'P' => {
+                        scratch.overlaps.clear();
+                        parse_path_seq_to_item_vec_into(
+                            buf_path_seg,
+                            graph_storage,
+                            line,
+                            &mut scratch.items,
+                            &mut scratch.overlaps,
+                        )
+                    }
+                    b'W' => parse_walk_seq_to_item_vec_into(
+                        buf_path_seg,
+                        graph_storage,
+                        line,
+                        &mut scratch.items,
+                    )
+                    .map(|()| {
+                        scratch.overlaps.clear();
+                        scratch
+                            .overlaps
+                            .resize(scratch.items.len().saturating_sub(1), 0);
+                    }),
                     _ => unreachable!(),
                 };
+                if let Err(e) = sids_result {
+                    if lenient {
+                        log::warn!("skipping malformed record: {}", e);
+                        num_path += 1;
+                        buf.clear();
+                        continue;
+                    }
+                    return Err(e);
+                }
 
                 match count {
                     CountType::Node | CountType::Bp => {
+                        let strand = strand_composition(&scratch.items);
+                        let include_tree = IntervalTree::build(include_coords);
+                        let exclude_tree = IntervalTree::build(exclude_coords);
                         let (node_len, bp_len) = update_tables(
                             &mut item_table,
                             &mut subset_covered_bps.as_mut(),
                             &mut exclude_table.as_mut(),
                             num_path,
                             graph_storage,
-                            sids,
+                            &scratch.items,
+                            &include_tree,
+                            &exclude_tree,
+                            start,
+                            min_covered_fraction,
+                            end_exclusion,
+                            &scratch.overlaps,
+                            overlap_adjusted_bp,
+                            sort_shards,
+                            &mut depth_table.as_deref_mut(),
+                        );
+                        paths_len.insert(path_seg, (node_len as u32, bp_len as u32, strand));
+                    }
+                    CountType::Edge => {
+                        match update_tables_edgecount(
+                            &mut item_table,
+                            &mut exclude_table.as_mut(),
+                            num_path,
+                            graph_storage,
+                            &scratch.items,
                             include_coords,
                             exclude_coords,
                             start,
-                        );
-                        paths_len.insert(path_seg, (node_len as u32, bp_len as u32));
+                            end_exclusion,
+                            line,
+                        ) {
+                            Ok(()) => {}
+                            Err(e) if lenient => log::warn!("skipping malformed record: {}", e),
+                            Err(e) => return Err(e),
+                        }
                     }
-                    CountType::Edge => update_tables_edgecount(
-                        &mut item_table,
-                        &mut exclude_table.as_mut(),
-                        num_path,
-                        graph_storage,
-                        sids,
-                        include_coords,
-                        exclude_coords,
-                        start,
-                    ),
                     CountType::All => unreachable!("inadmissable count type"),
+                    CountType::Kmer => unreachable!("k-mer counting not yet wired through update_tables"),
+                    CountType::Minimizer => unreachable!("minimizer counting not yet wired through update_tables"),
+                    CountType::Branch => unreachable!("branch counting not yet wired through update_tables"),
                 };
             }
             num_path += 1;
@@ -372,28 +955,52 @@ pub fn parse_gfa_paths_walks<R: Read>(
         count,
         duration
     );
-    (item_table, exclude_table, subset_covered_bps, paths_len)
+    Ok((item_table, exclude_table, subset_covered_bps, paths_len))
 }
 
-pub fn parse_walk_identifier(data: &[u8]) -> (PathSegment, &[u8]) {
+pub fn parse_walk_identifier(
+    data: &[u8],
+    line: usize,
+) -> Result<(PathSegment, &[u8]), GfaParseError> {
     let mut six_col: Vec<&str> = Vec::with_capacity(6);
 
     let mut it = data.iter();
     let mut i = 0;
     for _ in 0..6 {
-        let j = it.position(|x| x == &b'\t').unwrap();
-        six_col.push(str::from_utf8(&data[i..i + j]).unwrap());
+        let j = it.position(|x| x == &b'\t').ok_or_else(|| GfaParseError {
+            kind: GfaParseErrorKind::MalformedField,
+            line,
+            byte_offset: Some(i),
+            reason: "W line has fewer than the required 6 tab-separated fields".to_string(),
+        })?;
+        let field = str::from_utf8(&data[i..i + j]).map_err(|_| GfaParseError {
+            kind: GfaParseErrorKind::NonUtf8,
+            line,
+            byte_offset: Some(i),
+            reason: "W line field is not valid UTF-8".to_string(),
+        })?;
+        six_col.push(field);
         i += j + 1;
     }
 
     let seq_start = match six_col[4] {
         "*" => None,
-        a => Some(usize::from_str(a).unwrap()),
+        a => Some(usize::from_str(a).map_err(|_| GfaParseError {
+            kind: GfaParseErrorKind::MalformedField,
+            line,
+            byte_offset: None,
+            reason: format!("W line seq-start field '{}' is not a valid integer", a),
+        })?),
     };
 
     let seq_end = match six_col[5] {
         "*" => None,
-        a => Some(usize::from_str(a).unwrap()),
+        a => Some(usize::from_str(a).map_err(|_| GfaParseError {
+            kind: GfaParseErrorKind::MalformedField,
+            line,
+            byte_offset: None,
+            reason: format!("W line seq-end field '{}' is not a valid integer", a),
+        })?),
     };
 
     let path_seg = PathSegment::new(
@@ -404,34 +1011,195 @@ pub fn parse_walk_identifier(data: &[u8]) -> (PathSegment, &[u8]) {
         seq_end,
     );
 
-    (path_seg, &data[i..])
+    Ok((path_seg, &data[i..]))
 }
 
-pub fn parse_path_identifier(data: &[u8]) -> (PathSegment, &[u8]) {
+pub fn parse_path_identifier(
+    data: &[u8],
+    line: usize,
+) -> Result<(PathSegment, &[u8]), GfaParseError> {
     let mut iter = data.iter();
 
-    let start = iter.position(|&x| x == b'\t').unwrap() + 1;
-    let offset = iter.position(|&x| x == b'\t').unwrap();
-    let path_name = str::from_utf8(&data[start..start + offset]).unwrap();
-    (
+    let start = iter.position(|&x| x == b'\t').ok_or_else(|| GfaParseError {
+        kind: GfaParseErrorKind::MalformedField,
+        line,
+        byte_offset: Some(0),
+        reason: "P line is missing the path-name field".to_string(),
+    })? + 1;
+    let offset = iter.position(|&x| x == b'\t').ok_or_else(|| GfaParseError {
+        kind: GfaParseErrorKind::MalformedField,
+        line,
+        byte_offset: Some(start),
+        reason: "P line is missing the segment-list field".to_string(),
+    })?;
+    let path_name = str::from_utf8(&data[start..start + offset]).map_err(|_| GfaParseError {
+        kind: GfaParseErrorKind::NonUtf8,
+        line,
+        byte_offset: Some(start),
+        reason: "P line path name is not valid UTF-8".to_string(),
+    })?;
+    Ok((
         PathSegment::from_str(path_name),
         &data[start + offset + 1..],
-    )
+    ))
+}
+
+// returns the number of reference bases a CIGAR overlap string consumes, i.e. the combined
+// length of its M/D/N/=/X ops -- the convention GFA uses for how much of the next segment a
+// P line's overlap column has already accounted for
+fn cigar_overlap_length(cigar: &[u8], line: usize) -> Result<usize, GfaParseError> {
+    if cigar == b"*" {
+        return Ok(0);
+    }
+    let mut total = 0usize;
+    let mut num = 0usize;
+    let mut has_digit = false;
+    for &b in cigar {
+        if b.is_ascii_digit() {
+            num = num * 10 + (b - b'0') as usize;
+            has_digit = true;
+        } else {
+            if !has_digit {
+                return Err(GfaParseError {
+                    kind: GfaParseErrorKind::MalformedField,
+                    line,
+                    byte_offset: None,
+                    reason: format!(
+                        "malformed CIGAR overlap '{}'",
+                        String::from_utf8_lossy(cigar)
+                    ),
+                });
+            }
+            if matches!(b, b'M' | b'D' | b'N' | b'=' | b'X') {
+                total += num;
+            }
+            num = 0;
+            has_digit = false;
+        }
+    }
+    if has_digit {
+        return Err(GfaParseError {
+            kind: GfaParseErrorKind::MalformedField,
+            line,
+            byte_offset: None,
+            reason: format!(
+                "malformed CIGAR overlap '{}'",
+                String::from_utf8_lossy(cigar)
+            ),
+        });
+    }
+    Ok(total)
+}
+
+/// Parses the comma-separated CIGAR overlap column that follows a P line's segment list --
+/// `tail` starts right at the tab after that list, as passed in (`&data[end..]`) by
+/// `parse_path_seq_to_item_vec`. Returns one overlap length per junction between consecutive
+/// segments (`num_segments - 1` entries); a missing or `*` overlap column -- the common case
+/// for blunt-ended GFA -- yields all zeros, which is exactly what "raw segment-sum" bp
+/// counting (`overlap_adjusted_bp = false`) expects.
+pub fn parse_path_overlaps(
+    tail: &[u8],
+    num_segments: usize,
+    line: usize,
+) -> Result<Vec<usize>, GfaParseError> {
+    let expected = num_segments.saturating_sub(1);
+    let field_start = match tail.iter().position(|&x| x == b'\t') {
+        Some(i) => i + 1,
+        None => return Ok(vec![0; expected]),
+    };
+    let field_end = tail[field_start..]
+        .iter()
+        .position(|&x| x == b'\n' || x == b'\r')
+        .map_or(tail.len(), |i| field_start + i);
+    let field = &tail[field_start..field_end];
+    if field.is_empty() || field == b"*" {
+        return Ok(vec![0; expected]);
+    }
+    let overlaps: Vec<usize> = field
+        .split(|&x| x == b',')
+        .map(|cigar| cigar_overlap_length(cigar, line))
+        .collect::<Result<_, _>>()?;
+    if overlaps.len() != expected {
+        return Err(GfaParseError {
+            kind: GfaParseErrorKind::MalformedField,
+            line,
+            byte_offset: None,
+            reason: format!(
+                "P line has {} overlap entries for {} segments (expected {})",
+                overlaps.len(),
+                num_segments,
+                expected
+            ),
+        });
+    }
+    Ok(overlaps)
 }
 
+// Given a node's forward-strand span `[p, p + l)` and the set of (possibly overlapping,
+// possibly unsorted) intervals from `tree` that stab it, returns the union of the clipped
+// overlaps as node-local, forward-strand coordinates in `[0, l)`, sorted and merged so that a
+// node is only ever represented once regardless of how many input intervals touched it.
+fn clipped_overlaps(tree: &IntervalTree, p: usize, l: usize) -> Vec<(usize, usize)> {
+    let mut segs: Vec<(usize, usize)> = tree
+        .overlaps(p, p + l)
+        .map(|(start, end)| (start.max(p) - p, end.min(p + l) - p))
+        .collect();
+    segs.sort_unstable_by_key(|&(a, _)| a);
+    let mut merged = Vec::new();
+    for (a, b) in segs {
+        merge_interval(&mut merged, a, b);
+    }
+    merged
+}
+
+// the node-local window coverage is measured against once `end_exclusion` is in effect: contig
+// ends are where mapping/alignment artifacts concentrate, so read-coverage estimators commonly
+// ignore the first/last few bases of a sequence entirely when deciding what counts as "covered".
+// `(l, l)` (an empty window) is returned for nodes too short to have any interior left once both
+// ends are trimmed, which downstream treats as "never covered" rather than dividing by zero.
+fn trimmed_span(l: usize, end_exclusion: usize) -> (usize, usize) {
+    if end_exclusion == 0 {
+        (0, l)
+    } else if l < 2 * end_exclusion {
+        (l, l)
+    } else {
+        (end_exclusion, l - end_exclusion)
+    }
+}
+
+// intersects every interval in `intervals` with `[lo, hi)`, dropping any that become empty --
+// used to restrict already-clipped, node-local coverage intervals to the `trimmed_span` window.
+fn clip_to_span(intervals: &[(usize, usize)], lo: usize, hi: usize) -> Vec<(usize, usize)> {
+    intervals
+        .iter()
+        .filter_map(|&(a, b)| {
+            let a = a.max(lo);
+            let b = b.min(hi);
+            (a < b).then_some((a, b))
+        })
+        .collect()
+}
+
+/// `min_covered_fraction` is the node-counting threshold a `GraphMask` would normally select,
+/// borrowed from the min-covered-fraction idea in read-coverage estimators: a node counts (and
+/// is pushed into `item_table.items`) once the union of clipped include sub-segments covers at
+/// least that fraction of the node's length. `0.0` (any-overlap, the historical default) counts
+/// a node as soon as one base of it falls inside an include interval; `1.0` requires the union
+/// to cover the node completely, same as the old `count_full_coverage_only = true` did.
+/// `included_bp`/`subset_covered_bps` are populated from the exact clipped union regardless of
+/// the threshold.
 pub fn update_tables_multiple(
     item_table: &mut ItemTable,
     subset_covered_bps: &mut Option<&mut IntervalContainer>,
     mut exclude_tables: Vec<&mut Option<ActiveTable>>,
     num_path: usize,
     graph_storage: &GraphStorage,
-    path: Vec<(ItemId, Orientation)>,
-    include_coords: &[(usize, usize)],
-    exclude_coords: &[(usize, usize)],
+    path: &[(ItemId, Orientation)],
+    include_coords: &IntervalTree,
+    exclude_coords: &IntervalTree,
     offset: usize,
+    min_covered_fraction: f64,
 ) -> (usize, usize) {
-    let mut i = 0;
-    let mut j = 0;
     let mut p = offset;
 
     let mut included = 0;
@@ -447,62 +1215,37 @@ pub fn update_tables_multiple(
     }
 
     let rexclude_tables = &mut exclude_tables;
-    for (sid, o) in &path {
+    for (sid, o) in path {
         let l = graph_storage.node_len(&sid) as usize;
 
-        // this implementation of include coords for bps is *not exact* as illustrated by the
-        // following scenario:
-        //
-        //   subset intervals:           ____________________________
-        //                ______________|_____________________________
-        //               |
-        //      ___________________________________________     ____
-        //  ---|                some node                  |---|
-        //      -------------------------------------------     ----
-        //
-        //
-        //   what the following code does:
-        //                ___________________________________________
-        //               |
-        //               |             coverage count
-        //      ___________________________________________     ____
-        //  ---|                some node                  |---|
-        //      -------------------------------------------     ----
-        //
-        //
-        // node count handling: node is only counted if *completely* covered by subset
+        // `clipped_overlaps` walks every include interval that stabs this node's span (not just
+        // the first/last one touching it), clips each to the node's local coordinates, and merges
+        // the result -- so `included_bp`/`subset_covered_bps` always get the exact union of
+        // overlapping sub-ranges, never an approximation from collapsing to one span.
         //
-        //
-        // update current pointer in include_coords list
-
-        // end is not inclusive, so if end <= p (=offset) then advance to the next interval
-        let mut stop_here = false;
-        while i < include_coords.len() && include_coords[i].0 < p + l && !stop_here {
-            if include_coords[i].1 > p {
-                let mut a = if include_coords[i].0 > p {
-                    include_coords[i].0 - p
-                } else {
-                    0
-                };
-                let mut b = if include_coords[i].1 < p + l {
-                    // advance to the next interval
-                    i += 1;
-                    include_coords[i - 1].1 - p
-                } else {
-                    stop_here = true;
-                    l
-                };
-
+        // node count handling: a node counts (and is pushed into `item_table.items`) once the
+        // union of clipped sub-segments covers at least `min_covered_fraction` of its length --
+        // `included_bp`/`subset_covered_bps` stay exact regardless, summing the precise union of
+        // clipped overlaps
+
+        let merged = clipped_overlaps(include_coords, p, l);
+        if !merged.is_empty() {
+            let covered: usize = merged.iter().map(|&(a, b)| b - a).sum();
+            let fully_covered = covered == l;
+            if covered as f64 >= min_covered_fraction * l as f64 {
+                item_table.items.push(sid.0);
+                item_table.id_prefsum[num_path + 1] += 1;
+                included += 1;
+            }
+            for (mut a, mut b) in merged {
                 // reverse coverage interval in case of backward orientation
                 if o == &Orientation::Backward {
                     (a, b) = (l - b, l - a);
                 }
-
-                item_table.items.push(sid.0);
-                item_table.id_prefsum[num_path + 1] += 1;
+                included_bp += b - a;
                 if let Some(int) = subset_covered_bps.as_mut() {
                     // if fully covered, we do not need to store anything in the map
-                    if b - a == l {
+                    if fully_covered {
                         if int.contains(sid) {
                             int.remove(sid);
                         }
@@ -510,54 +1253,32 @@ pub fn update_tables_multiple(
                         int.add(*sid, a, b);
                     }
                 }
-                included += 1;
-                included_bp += b - a;
-            } else {
-                // advance to the next interval
-                i += 1;
             }
         }
 
-        let mut stop_here = false;
-        while j < exclude_coords.len() && exclude_coords[j].0 < p + l && !stop_here {
-            if exclude_coords[j].1 > p {
-                let mut a = if exclude_coords[j].0 > p {
-                    exclude_coords[j].0 - p
-                } else {
-                    0
-                };
-                let mut b = if exclude_coords[j].1 < p + l {
-                    // advance to the next interval for the next iteration
-                    j += 1;
-                    exclude_coords[j - 1].1 - p
-                } else {
-                    stop_here = true;
-                    l
-                };
-
-                // reverse coverage interval in case of backward orientation
-                if o == &Orientation::Backward {
-                    (a, b) = (l - b, l - a);
-                }
-
-                for exclude_table in rexclude_tables.iter_mut() {
-                    if let Some(map) = exclude_table {
-                        if map.with_annotation() {
-                            map.activate_n_annotate(*sid, l, a, b)
-                                .expect("this error should never occur");
-                        } else {
-                            map.activate(&sid);
-                        }
-                        excluded += 1;
+        for (mut a, mut b) in clipped_overlaps(exclude_coords, p, l) {
+            // reverse coverage interval in case of backward orientation
+            if o == &Orientation::Backward {
+                (a, b) = (l - b, l - a);
+            }
+            for exclude_table in rexclude_tables.iter_mut() {
+                if let Some(map) = exclude_table {
+                    if map.with_annotation() {
+                        map.activate_n_annotate(*sid, l, a, b)
+                            .expect("this error should never occur");
+                    } else {
+                        map.activate(&sid);
                     }
+                    excluded += 1;
                 }
-            } else {
-                j += 1;
             }
         }
 
-        if i >= include_coords.len() && j >= exclude_coords.len() {
-            // terminate parse if all "include" and "exclude" coords are processed
+        // terminate parse once neither tree has any interval left that could reach a
+        // later, higher-offset node
+        let include_done = include_coords.max_end().map_or(true, |m| p + l >= m);
+        let exclude_done = exclude_coords.max_end().map_or(true, |m| p + l >= m);
+        if include_done && exclude_done {
             break;
         }
         p += l;
@@ -576,19 +1297,51 @@ pub fn update_tables_multiple(
     (included, included_bp)
 }
 
+/// See `update_tables_multiple` for the meaning of `min_covered_fraction`.
+///
+/// `overlaps` is the per-junction CIGAR overlap length produced by `parse_path_overlaps`
+/// (`overlaps[i - 1]` is node `i`'s overlap with its predecessor; empty/all-zero for
+/// blunt-ended GFA and for walks, which don't have an overlap column at all). When
+/// `overlap_adjusted_bp` is set, that overlap is clipped off the *start* of a node's
+/// forward-strand span before it is added to `included_bp`/`subset_covered_bps`, so a
+/// shared prefix isn't counted by both segments; when unset (the default, reproducing prior
+/// behavior exactly), `overlaps` is ignored and every base of every node is counted once,
+/// same as "raw segment-sum" bp counting always has.
+///
+/// `sort_shard` opts a path's segment of `item_table.items` into being sorted in place once
+/// every node of the path has been pushed, instead of staying in the path-traversal order the
+/// node loop below pushes it in. Rust's `sort_unstable`
+/// already *is* a pattern-defeating quicksort -- introspective, falling back to insertion sort
+/// on small runs and to heapsort once recursion depth exceeds its `2 * log2(n)` bound -- so no
+/// custom sort needs writing here; `sort_shard` just decides whether to pay for it. A sorted
+/// segment lets a caller use `item_table_contains_sorted` (binary search) instead of a linear
+/// scan for "is node X on this path", and makes the segment's serialized byte layout depend
+/// only on its node-id set, not on parse order -- both only hold when `sort_shard` is set, since
+/// leaving it unset (the default) reproduces prior behavior and prior performance exactly.
+///
+/// `depth_table`, when given, gets every clipped sub-segment's `(sid, a, b)` fed into it
+/// alongside `subset_covered_bps` -- same node-local, post-orientation-flip coordinates --
+/// building up the per-base "how many paths cover this base" profile [`DepthTable`] exposes via
+/// `per_base_depths`/`mean_and_trimmed_mean`. Unlike `subset_covered_bps`, fully-covered segments
+/// are still recorded here: depth needs every path's contribution kept, not just the union.
+#[allow(clippy::too_many_arguments)]
 pub fn update_tables(
     item_table: &mut ItemTable,
     subset_covered_bps: &mut Option<&mut IntervalContainer>,
     exclude_table: &mut Option<&mut ActiveTable>,
     num_path: usize,
     graph_storage: &GraphStorage,
-    path: Vec<(ItemId, Orientation)>,
-    include_coords: &[(usize, usize)],
-    exclude_coords: &[(usize, usize)],
+    path: &[(ItemId, Orientation)],
+    include_coords: &IntervalTree,
+    exclude_coords: &IntervalTree,
     offset: usize,
+    min_covered_fraction: f64,
+    end_exclusion: usize,
+    overlaps: &[usize],
+    overlap_adjusted_bp: bool,
+    sort_shard: bool,
+    depth_table: &mut Option<&mut DepthTable>,
 ) -> (usize, usize) {
-    let mut i = 0;
-    let mut j = 0;
     let mut p = offset;
 
     let mut included = 0;
@@ -603,63 +1356,62 @@ pub fn update_tables(
         return (included, included_bp);
     }
 
-    for (sid, o) in &path {
+    for (i, (sid, o)) in path.iter().enumerate() {
         let l = graph_storage.node_len(&sid) as usize;
-
-        // this implementation of include coords for bps is *not exact* as illustrated by the
-        // following scenario:
-        //
-        //   subset intervals:           ____________________________
-        //                ______________|_____________________________
-        //               |
-        //      ___________________________________________     ____
-        //  ---|                some node                  |---|
-        //      -------------------------------------------     ----
+        let incoming_overlap = if overlap_adjusted_bp && i > 0 {
+            overlaps.get(i - 1).copied().unwrap_or(0).min(l)
+        } else {
+            0
+        };
+
+        // `clipped_overlaps` walks every include interval that stabs this node's span (not just
+        // the first/last one touching it), clips each to the node's local coordinates, and merges
+        // the result -- so `included_bp`/`subset_covered_bps` always get the exact union of
+        // overlapping sub-ranges, never an approximation from collapsing to one span.
         //
+        // node count handling: a node counts (and is pushed into `item_table.items`) once the
+        // union of clipped sub-segments covers at least `min_covered_fraction` of its length --
+        // `included_bp`/`subset_covered_bps` stay exact regardless, summing the precise union of
+        // clipped overlaps
         //
-        //   what the following code does:
-        //                ___________________________________________
-        //               |
-        //               |             coverage count
-        //      ___________________________________________     ____
-        //  ---|                some node                  |---|
-        //      -------------------------------------------     ----
-        //
-        //
-        // node count handling: node is only counted if *completely* covered by subset
-        //
-        //
-        // update current pointer in include_coords list
-
-        // end is not inclusive, so if end <= p (=offset) then advance to the next interval
-        let mut stop_here = false;
-        while i < include_coords.len() && include_coords[i].0 < p + l && !stop_here {
-            if include_coords[i].1 > p {
-                let mut a = if include_coords[i].0 > p {
-                    include_coords[i].0 - p
-                } else {
-                    0
-                };
-                let mut b = if include_coords[i].1 < p + l {
-                    // advance to the next interval
-                    i += 1;
-                    include_coords[i - 1].1 - p
-                } else {
-                    stop_here = true;
-                    l
-                };
-
+        // `end_exclusion` shrinks the window that fraction is measured against to
+        // `trimmed_span(l, end_exclusion)` first -- nodes too short to have any window left are
+        // never counted, regardless of `min_covered_fraction`
+
+        let (lo, hi) = trimmed_span(l, end_exclusion);
+        let merged_raw = clipped_overlaps(include_coords, p, l);
+        let merged = if end_exclusion > 0 {
+            clip_to_span(&merged_raw, lo, hi)
+        } else {
+            merged_raw
+        };
+        let effective_len = hi - lo;
+        if !merged.is_empty() && effective_len > 0 {
+            let covered: usize = merged.iter().map(|&(a, b)| b - a).sum();
+            let fully_covered = covered == effective_len;
+            if covered as f64 >= min_covered_fraction * effective_len as f64 {
+                item_table.items.push(sid.0);
+                item_table.id_prefsum[num_path + 1] += 1;
+                included += 1;
+            }
+            for (mut a, mut b) in merged {
+                // clip away the part of this node's start already accounted for by the
+                // previous segment's overlap, in forward-strand node-local coordinates --
+                // i.e. before the backward-orientation flip below
+                if incoming_overlap > 0 {
+                    a = a.max(incoming_overlap);
+                    if a >= b {
+                        continue;
+                    }
+                }
                 // reverse coverage interval in case of backward orientation
                 if o == &Orientation::Backward {
                     (a, b) = (l - b, l - a);
                 }
-
-                let idx = (sid.0 as usize) % SIZE_T;
-                item_table.items.push(sid.0);
-                item_table.id_prefsum[num_path + 1] += 1;
+                included_bp += b - a;
                 if let Some(int) = subset_covered_bps.as_mut() {
                     // if fully covered, we do not need to store anything in the map
-                    if b - a == l {
+                    if fully_covered && incoming_overlap == 0 {
                         if int.contains(sid) {
                             int.remove(sid);
                         }
@@ -667,52 +1419,33 @@ pub fn update_tables(
                         int.add(*sid, a, b);
                     }
                 }
-                included += 1;
-                included_bp += b - a;
-            } else {
-                // advance to the next interval
-                i += 1;
+                if let Some(depth) = depth_table.as_mut() {
+                    depth.add(*sid, a, b);
+                }
             }
         }
 
-        let mut stop_here = false;
-        while j < exclude_coords.len() && exclude_coords[j].0 < p + l && !stop_here {
-            if exclude_coords[j].1 > p {
-                let mut a = if exclude_coords[j].0 > p {
-                    exclude_coords[j].0 - p
-                } else {
-                    0
-                };
-                let mut b = if exclude_coords[j].1 < p + l {
-                    // advance to the next interval for the next iteration
-                    j += 1;
-                    exclude_coords[j - 1].1 - p
+        for (mut a, mut b) in clipped_overlaps(exclude_coords, p, l) {
+            // reverse coverage interval in case of backward orientation
+            if o == &Orientation::Backward {
+                (a, b) = (l - b, l - a);
+            }
+            if let Some(map) = exclude_table {
+                if map.with_annotation() {
+                    map.activate_n_annotate(*sid, l, a, b)
+                        .expect("this error should never occur");
                 } else {
-                    stop_here = true;
-                    l
-                };
-
-                // reverse coverage interval in case of backward orientation
-                if o == &Orientation::Backward {
-                    (a, b) = (l - b, l - a);
-                }
-
-                if let Some(map) = exclude_table {
-                    if map.with_annotation() {
-                        map.activate_n_annotate(*sid, l, a, b)
-                            .expect("this error should never occur");
-                    } else {
-                        map.activate(&sid);
-                    }
-                    excluded += 1;
+                    map.activate(&sid);
                 }
-            } else {
-                j += 1;
+                excluded += 1;
             }
         }
 
-        if i >= include_coords.len() && j >= exclude_coords.len() {
-            // terminate parse if all "include" and "exclude" coords are processed
+        // terminate parse once neither tree has any interval left that could reach a
+        // later, higher-offset node
+        let include_done = include_coords.max_end().map_or(true, |m| p + l >= m);
+        let exclude_done = exclude_coords.max_end().map_or(true, |m| p + l >= m);
+        if include_done && exclude_done {
             break;
         }
         p += l;
@@ -725,22 +1458,53 @@ pub fn update_tables(
         excluded,
     );
 
+    if sort_shard {
+        let start = item_table.id_prefsum[num_path] as usize;
+        let end = start + item_table.id_prefsum[num_path + 1] as usize;
+        item_table.items[start..end].sort_unstable();
+    }
+
     // Compute prefix sum
     item_table.id_prefsum[num_path + 1] += item_table.id_prefsum[num_path];
     log::debug!("..done");
     (included, included_bp)
 }
 
+/// Binary-search membership check for the node-id segment `update_tables` filled for path
+/// `num_path`. Only valid when that segment was produced with `sort_shard` set -- an unsorted
+/// segment (the default) must still be checked with a linear scan, since `binary_search` on
+/// unsorted input gives no correctness guarantee at all.
+pub fn item_table_contains_sorted(item_table: &ItemTable, num_path: usize, item: ItemId) -> bool {
+    let start = item_table.id_prefsum[num_path] as usize;
+    let end = item_table.id_prefsum[num_path + 1] as usize;
+    item_table.items[start..end].binary_search(&item).is_ok()
+}
+
+/// `end_exclusion` is the same contig-end-exclusion idea [`update_tables`] applies to node
+/// coverage, borrowed for edges: the window an edge's trailing node is tested against is shrunk
+/// to `trimmed_span(l, end_exclusion)` before the include/exclude check, so an edge anchored in
+/// the trimmed-off tail of a node is treated as neither included nor excluded.
 pub fn update_tables_edgecount(
     item_table: &mut ItemTable,
     exclude_table: &mut Option<&mut ActiveTable>,
     num_path: usize,
     graph_storage: &GraphStorage,
-    path: Vec<(ItemId, Orientation)>,
+    path: &[(ItemId, Orientation)],
     include_coords: &[(usize, usize)],
     exclude_coords: &[(usize, usize)],
     offset: usize,
-) {
+    end_exclusion: usize,
+    line: usize,
+) -> Result<(), GfaParseError> {
+    // the plain two-cursor walk below only ever moves its cursors forward, which is only correct
+    // when `include_coords`/`exclude_coords` are sorted by start and pairwise non-overlapping;
+    // build a binary-searchable index instead of silently producing wrong results when that
+    // doesn't hold, and keep the cheap linear walk otherwise
+    let include_index = (!IntervalIndex::is_disjoint_sorted(include_coords))
+        .then(|| IntervalIndex::build(include_coords));
+    let exclude_index = (!IntervalIndex::is_disjoint_sorted(exclude_coords))
+        .then(|| IntervalIndex::build(exclude_coords));
+
     let mut i = 0;
     let mut j = 0;
     let mut p = offset;
@@ -752,66 +1516,99 @@ pub fn update_tables_edgecount(
 
     log::debug!("checking inclusion/exclusion criteria on {} nodes, inserting successful candidates to corresponding data structures..", path.len());
 
-    for ((sid1, o1), (sid2, o2)) in path.into_iter().tuple_windows() {
-        // update current pointer in include_coords list
-        while i < include_coords.len() && include_coords[i].1 <= p {
-            i += 1;
+    for ((sid1, o1), (sid2, o2)) in path.iter().cloned().tuple_windows() {
+        // update current pointer in include_coords list (only meaningful on the linear fast path)
+        if include_index.is_none() {
+            while i < include_coords.len() && include_coords[i].1 <= p {
+                i += 1;
+            }
         }
 
-        // update current pointer in exclude_coords list
-        while j < exclude_coords.len() && exclude_coords[j].1 <= p {
-            j += 1;
+        // update current pointer in exclude_coords list (only meaningful on the linear fast path)
+        if exclude_index.is_none() {
+            while j < exclude_coords.len() && exclude_coords[j].1 <= p {
+                j += 1;
+            }
         }
 
         let l = graph_storage.node_len(&sid2) as usize;
 
         let e = Edge::canonical(sid1, o1, sid2, o2);
-        let eid = graph_storage
+        let edge2id = graph_storage
             .edge2id
             .as_ref()
-            .expect("update_tables_edgecount requires edge2id map in GraphStorage")
-            .get(&e)
-            .unwrap_or_else(|| {
-                panic!(
-                    "unknown edge {}. Is flipped edge known? {}",
-                    &e,
-                    if graph_storage
-                        .edge2id
-                        .as_ref()
-                        .unwrap()
-                        .contains_key(&e.flip())
-                    {
-                        "Yes"
-                    } else {
-                        "No"
-                    }
-                )
-            });
-        // check if the current position fits within active segment
-        if i < include_coords.len() && include_coords[i].0 < p + l {
+            .expect("update_tables_edgecount requires edge2id map in GraphStorage");
+        let eid = edge2id.get(&e).ok_or_else(|| GfaParseError {
+            kind: GfaParseErrorKind::UnknownEdge,
+            line,
+            byte_offset: None,
+            reason: format!(
+                "unknown edge {}. Is flipped edge known? {}",
+                &e,
+                if edge2id.contains_key(&e.flip()) {
+                    "Yes"
+                } else {
+                    "No"
+                }
+            ),
+        })?;
+        // check if the current position fits within the (possibly end-exclusion-trimmed) window
+        let (lo, hi) = trimmed_span(l, end_exclusion);
+        let (w_start, w_end) = (p + lo, p + hi);
+        let included = w_start < w_end
+            && match &include_index {
+                Some(index) => index.overlaps_any(w_start, w_end),
+                None => i < include_coords.len() && include_coords[i].0 < w_end,
+            };
+        let excluded = w_start < w_end
+            && match &exclude_index {
+                Some(index) => index.overlaps_any(w_start, w_end),
+                None => j < exclude_coords.len() && exclude_coords[j].0 < w_end,
+            };
+
+        if included {
             item_table.items.push(eid.0);
             item_table.id_prefsum[num_path + 1] += 1;
         }
-        if exclude_table.is_some() && j < exclude_coords.len() && exclude_coords[j].0 < p + l {
+        if exclude_table.is_some() && excluded {
             exclude_table.as_mut().unwrap().activate(eid);
-        } else if i >= include_coords.len() && j >= exclude_coords.len() {
-            // terminate parse if all "include" and "exclude" coords are processed
-            break;
+        } else {
+            let include_exhausted = match &include_index {
+                Some(index) => index.first_overlap_from(p) >= index.len(),
+                None => i >= include_coords.len(),
+            };
+            let exclude_exhausted = match &exclude_index {
+                Some(index) => index.first_overlap_from(p) >= index.len(),
+                None => j >= exclude_coords.len(),
+            };
+            if include_exhausted && exclude_exhausted {
+                // terminate parse if all "include" and "exclude" coords are processed
+                break;
+            }
         }
         p += l;
     }
     // Compute prefix sum
     item_table.id_prefsum[num_path + 1] += item_table.id_prefsum[num_path];
     log::debug!("..done");
+    Ok(())
 }
 
-pub fn parse_walk_seq_to_item_vec(
+/// Fills `out` with the same `(ItemId, Orientation)` sequence [`parse_walk_seq_to_item_vec`]
+/// returns, without allocating a fresh result `Vec` of its own -- `out` is `.clear()`ed first, so
+/// a caller that owns a [`PathWalkScratch`] (or any other reused buffer) and calls this once per
+/// record instead of the allocating wrapper saves one heap allocation per `W` line.
+pub fn parse_walk_seq_to_item_vec_into(
     data: &[u8],
     graph_storage: &GraphStorage,
-) -> Vec<(ItemId, Orientation)> {
+    line: usize,
+    out: &mut Vec<(ItemId, Orientation)>,
+) -> Result<(), GfaParseError> {
+    out.clear();
+
     // later codes assumes that data is non-empty...
     if data.is_empty() {
-        return Vec::new();
+        return Ok(());
     }
 
     // whatever the orientation of the first node is, will be used to split the sequence first;
@@ -819,6 +1616,18 @@ pub fn parse_walk_seq_to_item_vec(
     let s1 = Orientation::from_lg(data[0]);
     let s2 = s1.flip();
 
+    let lookup_node = |node: &[u8]| -> Result<ItemId, GfaParseError> {
+        graph_storage.get_node_id(node).ok_or_else(|| GfaParseError {
+            kind: GfaParseErrorKind::UnknownNode,
+            line,
+            byte_offset: None,
+            reason: match str::from_utf8(node) {
+                Ok(s) => format!("walk contains unknown node {{{}}}", s),
+                Err(_) => "walk contains unknown node (non-UTF-8 identifier)".to_string(),
+            },
+        })
+    };
+
     let mut it = data.iter();
     let end = it
         .position(|x| x == &b'\t' || x == &b'\n' || x == &b'\r')
@@ -827,81 +1636,82 @@ pub fn parse_walk_seq_to_item_vec(
     log::debug!("parsing walk sequences of size {}..", end);
 
     // ignore first > | < so that no empty is created for 1st node
-    let sids: Vec<(ItemId, Orientation)> = data[..end]
+    let chunks: Vec<Vec<(ItemId, Orientation)>> = data[..end]
         .par_split(|x| &s1 == x)
-        .map(|x| {
+        .map(|x| -> Result<Vec<(ItemId, Orientation)>, GfaParseError> {
             if x.is_empty() {
                 // not nice... but Rust expects struct `std::iter::Once<(ItemIdSize, util::Orientation)>`
                 //
                 // this case shouldn't occur too often, so should be fine in terms for runtime
-                vec![]
+                Ok(vec![])
             } else {
                 let i = x.iter().position(|z| &s2 == z).unwrap_or(x.len());
-                let sid = (
-                    graph_storage.get_node_id(&x[..i]).unwrap_or_else(|| {
-                        panic!(
-                            "walk contains unknown node {{{}}}'",
-                            str::from_utf8(&x[..i]).unwrap()
-                        )
-                    }),
-                    s1,
-                );
+                let sid = (lookup_node(&x[..i])?, s1);
                 if i < x.len() {
                     // not nice... but Rust expects struct `std::iter::Once<(ItemIdSize, util::Orientation)>`
                     //
                     // this case can happen more frequently... hopefully it doesn't blow up the
                     // runtime
-                    [sid]
-                        .into_par_iter()
-                        .chain(
-                            x[i + 1..]
-                                .par_split(|y| &s2 == y)
-                                .map(|y| {
-                                    if y.is_empty() {
-                                        vec![]
-                                    } else {
-                                        vec![(
-                                            graph_storage.get_node_id(y).unwrap_or_else(|| {
-                                                panic!(
-                                                    "walk contains unknown node {{{}}}",
-                                                    str::from_utf8(y).unwrap()
-                                                )
-                                            }),
-                                            s2,
-                                        )]
-                                    }
-                                })
-                                .flatten(),
-                        )
-                        .collect()
+                    let rest: Result<Vec<Vec<(ItemId, Orientation)>>, GfaParseError> = x[i + 1..]
+                        .par_split(|y| &s2 == y)
+                        .map(|y| -> Result<Vec<(ItemId, Orientation)>, GfaParseError> {
+                            if y.is_empty() {
+                                Ok(vec![])
+                            } else {
+                                Ok(vec![(lookup_node(y)?, s2)])
+                            }
+                        })
+                        .collect();
+                    let mut v = vec![sid];
+                    v.extend(rest?.into_iter().flatten());
+                    Ok(v)
                 } else {
-                    vec![sid]
+                    Ok(vec![sid])
                 }
             }
         })
-        .flatten()
-        .collect();
+        .collect::<Result<Vec<Vec<(ItemId, Orientation)>>, GfaParseError>>()?;
     log::debug!("..done");
-    sids
+    out.extend(chunks.into_iter().flatten());
+    Ok(())
+}
+
+/// Allocating wrapper around [`parse_walk_seq_to_item_vec_into`], kept for callers (and tests)
+/// that don't have a reusable scratch buffer on hand.
+pub fn parse_walk_seq_to_item_vec(
+    data: &[u8],
+    graph_storage: &GraphStorage,
+    line: usize,
+) -> Result<Vec<(ItemId, Orientation)>, GfaParseError> {
+    let mut out = Vec::new();
+    parse_walk_seq_to_item_vec_into(data, graph_storage, line, &mut out)?;
+    Ok(out)
 }
 
+/// Strand composition is derived from a simple byte count over `data[..end]` rather than from
+/// the per-node parse below: a W-line node token is never itself preceded by anything but
+/// exactly one `>`/`<` delimiter (the same invariant the `par_split` below relies on), so
+/// counting those delimiter bytes directly is equivalent to, and cheaper than, threading an
+/// orientation flag through the parallel closure.
+///
+/// Unlike the legacy `path_parser::parse_path_seq_to_item_vec_fast` (which has no `W`-line
+/// counterpart and isn't part of this crate's compiled module tree), the fully-contained
+/// branch of `parse_gfa_paths_walks` already dispatches `W` lines here on the same
+/// rayon-parallel footing as `P` lines go through `parse_path_seq_update_tables` -- there is no
+/// slow-path fallback to close for walks in this pipeline.
 pub fn parse_walk_seq_update_tables(
     data: &[u8],
     graph_storage: &GraphStorage,
     item_table: &mut ItemTable,
     exclude_table: Option<&mut ActiveTable>,
     num_path: usize,
-) -> (u32, u32) {
+    line: usize,
+) -> Result<(u32, u32, StrandComposition), GfaParseError> {
     // later codes assumes that data is non-empty...
     if data.is_empty() {
-        return (0, 0);
+        return Ok((0, 0, StrandComposition::default()));
     }
 
-    let items_ptr = Wrap(&mut item_table.items);
-    let id_prefsum_ptr = Wrap(&mut item_table.id_prefsum);
-
-    let mutex_item_table = Arc::new(Mutex::new(&mut item_table.items));
-
     let mut it = data.iter();
     let end = it
         .position(|x| x == &b'\t' || x == &b'\n' || x == &b'\r')
@@ -909,26 +1719,39 @@ pub fn parse_walk_seq_update_tables(
 
     log::debug!("parsing walk sequences of size {}..", end);
 
-    let bp_len = Arc::new(AtomicU32::new(0));
+    // rayon's `par_split`/`map`/`collect` chain already accumulates each task's results locally
+    // before merging them back in split order, so `sids` comes out in the same order a serial
+    // walk would have produced -- no shared mutex or raw-pointer aliasing across threads needed
+    // to append to `item_table.items` in the right order below.
     // ignore first > | < so that no empty is created for 1st node
-    data[1..end]
+    let sids: Vec<(u64, u32)> = data[1..end]
         .par_split(|&x| x == b'>' || x == b'<')
-        .for_each(|node| {
-            let sid = graph_storage
+        .map(|node| {
+            graph_storage
                 .get_node_id(node)
-                .unwrap_or_else(|| panic!("unknown node {}", str::from_utf8(node).unwrap()));
-            if let Ok(_) = mutex_item_table.lock() {
-                unsafe {
-                    (*items_ptr.0).push(sid.0);
-                    (*id_prefsum_ptr.0)[num_path + 1] += 1;
-                }
-            }
-            bp_len.fetch_add(
-                graph_storage.node_len(&sid),
-                std::sync::atomic::Ordering::SeqCst,
-            );
-        });
-    let bp_len = bp_len.load(std::sync::atomic::Ordering::SeqCst);
+                .ok_or_else(|| GfaParseError {
+                    kind: GfaParseErrorKind::UnknownNode,
+                    line,
+                    byte_offset: None,
+                    reason: match str::from_utf8(node) {
+                        Ok(s) => format!("walk contains unknown node {{{}}}", s),
+                        Err(_) => "walk contains unknown node (non-UTF-8 identifier)".to_string(),
+                    },
+                })
+                .map(|sid| (sid.0, graph_storage.node_len(&sid)))
+        })
+        .collect::<Result<Vec<_>, GfaParseError>>()?;
+
+    let bp_len = sids.iter().map(|&(_, l)| l).sum();
+    for (sid, _) in &sids {
+        item_table.items.push(*sid);
+        item_table.id_prefsum[num_path + 1] += 1;
+    }
+
+    let strand = StrandComposition {
+        plus: data[..end].iter().filter(|&&b| b == b'>').count() as u32,
+        minus: data[..end].iter().filter(|&&b| b == b'<').count() as u32,
+    };
 
     // compute prefix sum
     let mut num_nodes_path = 0;
@@ -946,13 +1769,46 @@ pub fn parse_walk_seq_update_tables(
     }
 
     log::debug!("..done");
-    (num_nodes_path as u32, bp_len)
+    Ok((num_nodes_path as u32, bp_len, strand))
+}
+
+/// Reusable scratch buffers for the `P`/`W`-line slow path in [`parse_gfa_paths_walks_sequential`]
+/// (and [`parse_gfa_paths_walks_multiple`]'s equivalent single-threaded loop): `.clear()`ed and
+/// refilled once per record by [`parse_path_seq_to_item_vec_into`] / [`parse_walk_seq_to_item_vec_into`],
+/// so their capacity is kept between records. A `W` record is parsed straight into `items`; a `P`
+/// record's parse still allocates its own result, which is then copied in. Not used by the
+/// rayon-parallel fast path in [`parse_gfa_paths_walks`], where each path is already its own
+/// independent task and a per-task allocation doesn't cost anything the map/collect wasn't going
+/// to pay for anyway.
+#[derive(Default)]
+pub struct PathWalkScratch {
+    pub items: Vec<(ItemId, Orientation)>,
+    pub overlaps: Vec<usize>,
+}
+
+/// Fills `out_items`/`out_overlaps` with what [`parse_path_seq_to_item_vec`] returns -- see
+/// [`PathWalkScratch`]. The parse goes through the allocating [`parse_path_seq_to_item_vec`] and
+/// its result is copied in, so only the capacity of `out_items`/`out_overlaps` is reused.
+pub fn parse_path_seq_to_item_vec_into(
+    data: &[u8],
+    graph_storage: &GraphStorage,
+    line: usize,
+    out_items: &mut Vec<(ItemId, Orientation)>,
+    out_overlaps: &mut Vec<usize>,
+) -> Result<(), GfaParseError> {
+    let (items, overlaps) = parse_path_seq_to_item_vec(data, graph_storage, line)?;
+    out_items.clear();
+    out_items.extend(items);
+    out_overlaps.clear();
+    out_overlaps.extend(overlaps);
+    Ok(())
 }
 
 pub fn parse_path_seq_to_item_vec(
     data: &[u8],
     graph_storage: &GraphStorage,
-) -> Vec<(ItemId, Orientation)> {
+    line: usize,
+) -> Result<(Vec<(ItemId, Orientation)>, Vec<usize>), GfaParseError> {
     let mut it = data.iter();
     let end = it
         .position(|x| x == &b'\t' || x == &b'\n' || x == &b'\r')
@@ -961,7 +1817,7 @@ pub fn parse_path_seq_to_item_vec(
 
     log::debug!("parsing path sequences of size {}..", end);
 
-    let segment_ids: Vec<_> = (0..end)
+    let segment_ids: Result<Vec<_>, GfaParseError> = (0..end)
         .step_by(chunk_size)
         .map(|chunk_start| {
             let chunk_end = *[end, chunk_start + chunk_size].iter().min().unwrap();
@@ -988,35 +1844,64 @@ pub fn parse_path_seq_to_item_vec(
                 if curr_pos >= segment_end {
                     break;
                 }
-                let segment_id = get_segment_id(&data[curr_pos..segment_end], graph_storage);
+                let segment_id =
+                    try_get_segment_id(&data[curr_pos..segment_end], graph_storage, line)?;
                 let orientation = Orientation::from_pm(data[segment_end - 1]);
                 segment_ids.push((segment_id, orientation));
                 // move curr_pos forward (after next comma)
                 curr_pos = segment_end + 1;
             }
-            segment_ids
+            Ok(segment_ids)
         })
         .collect();
 
     log::debug!("..done");
 
-    let segment_ids = segment_ids.into_iter().concat();
-    segment_ids
+    let segment_ids = segment_ids?.into_iter().concat();
+    let overlaps = parse_path_overlaps(&data[end..], segment_ids.len(), line)?;
+    Ok((segment_ids, overlaps))
 }
 
-fn get_segment_id(node: &[u8], graph_storage: &GraphStorage) -> ItemId {
+// non-panicking: every caller below now has a `line` number on hand to report, including the
+// fast, `get_path_segment_ids`-driven path that used to carry a panicking `get_segment_id`
+// sibling of its own (see the git history of this function for that version)
+fn try_get_segment_id(
+    node: &[u8],
+    graph_storage: &GraphStorage,
+    line: usize,
+) -> Result<ItemId, GfaParseError> {
+    if node.is_empty() {
+        return Err(GfaParseError {
+            kind: GfaParseErrorKind::MalformedField,
+            line,
+            byte_offset: None,
+            reason: "path segment is empty".to_string(),
+        });
+    }
     let segment_id = graph_storage
         .get_node_id(&node[0..node.len() - 1])
-        .unwrap_or_else(|| panic!("unknown node {}", str::from_utf8(node).unwrap()));
-    // TODO: Is orientation really necessary?
+        .ok_or_else(|| GfaParseError {
+            kind: GfaParseErrorKind::UnknownNode,
+            line,
+            byte_offset: None,
+            reason: match str::from_utf8(node) {
+                Ok(s) => format!("unknown node {}", s),
+                Err(_) => "unknown node (non-UTF-8 identifier)".to_string(),
+            },
+        })?;
     let orientation = node[node.len() - 1];
-    assert!(
-        orientation == b'-' || orientation == b'+',
-        "unknown orientation of segment {}",
-        str::from_utf8(node).unwrap()
-    );
-    //plus_strands[rayon::current_thread_index().unwrap()] += (orientation == b'+') as u32;
-    segment_id
+    if orientation != b'-' && orientation != b'+' {
+        return Err(GfaParseError {
+            kind: GfaParseErrorKind::MalformedField,
+            line,
+            byte_offset: None,
+            reason: match str::from_utf8(node) {
+                Ok(s) => format!("unknown orientation of segment {}", s),
+                Err(_) => "unknown orientation of segment (non-UTF-8 identifier)".to_string(),
+            },
+        });
+    }
+    Ok(segment_id)
 }
 
 fn get_path_segment_ids(
@@ -1024,8 +1909,9 @@ fn get_path_segment_ids(
     graph_storage: &GraphStorage,
     end: usize,
     chunk_size: usize,
-) -> (Vec<ItemId>, u32) {
-    let (segment_ids, bp_lens): (Vec<_>, Vec<_>) = (0..end)
+    line: usize,
+) -> Result<(Vec<ItemId>, u32), GfaParseError> {
+    let chunks: Result<Vec<(Vec<ItemId>, u32)>, GfaParseError> = (0..end)
         .step_by(chunk_size)
         .map(|chunk_start| {
             let chunk_end = *[end, chunk_start + chunk_size].iter().min().unwrap();
@@ -1053,20 +1939,22 @@ fn get_path_segment_ids(
                 if curr_pos >= segment_end {
                     break;
                 }
-                let segment_id = get_segment_id(&data[curr_pos..segment_end], graph_storage);
+                let segment_id =
+                    try_get_segment_id(&data[curr_pos..segment_end], graph_storage, line)?;
                 bp_len += graph_storage.node_len(&segment_id);
                 segment_ids.push(segment_id);
                 // move curr_pos forward (after next comma)
                 curr_pos = segment_end + 1;
             }
-            (segment_ids, bp_len)
+            Ok((segment_ids, bp_len))
         })
-        .unzip();
+        .collect();
 
+    let (segment_ids, bp_lens): (Vec<_>, Vec<_>) = chunks?.into_iter().unzip();
     let segment_ids = segment_ids.into_iter().concat();
     let bp_len = bp_lens.into_iter().sum();
 
-    (segment_ids, bp_len)
+    Ok((segment_ids, bp_len))
 }
 
 pub fn parse_path_seq_update_tables_multiple(
@@ -1075,7 +1963,8 @@ pub fn parse_path_seq_update_tables_multiple(
     item_table: &mut ItemTable,
     exclude_tables: Vec<&mut Option<ActiveTable>>,
     num_path: usize,
-) -> (u32, u32) {
+    line: usize,
+) -> Result<(u32, u32), GfaParseError> {
     let mut it = data.iter();
     let end = it
         .position(|x| x == &b'\t' || x == &b'\n' || x == &b'\r')
@@ -1083,7 +1972,7 @@ pub fn parse_path_seq_update_tables_multiple(
 
     log::debug!("parsing path sequences of size {} bytes..", end);
 
-    let (segment_ids, bp_len) = get_path_segment_ids(data, graph_storage, end, CHUNK_SIZE);
+    let (segment_ids, bp_len) = get_path_segment_ids(data, graph_storage, end, CHUNK_SIZE, line)?;
 
     segment_ids.into_iter().for_each(|segment_id| {
         item_table.items.push(segment_id.0);
@@ -1108,16 +1997,22 @@ pub fn parse_path_seq_update_tables_multiple(
     }
 
     log::debug!("..done");
-    (num_nodes_path as u32, bp_len)
+    Ok((num_nodes_path as u32, bp_len))
 }
 
+/// `overlap_adjusted_bp` toggles subtraction of each node's CIGAR overlap column (see
+/// `parse_path_overlaps`) from the raw segment-length sum below. Like the rest of this
+/// fast path, a malformed overlap column returns a `GfaParseError` carrying the record's
+/// `line` rather than panicking.
 pub fn parse_path_seq_update_tables(
     data: &[u8],
     graph_storage: &GraphStorage,
     item_table: &mut ItemTable,
     exclude_table: Option<&mut ActiveTable>,
     num_path: usize,
-) -> (u32, u32) {
+    overlap_adjusted_bp: bool,
+    line: usize,
+) -> Result<(u32, u32, StrandComposition), GfaParseError> {
     let mut it = data.iter();
     let end = it
         .position(|x| x == &b'\t' || x == &b'\n' || x == &b'\r')
@@ -1125,36 +2020,64 @@ pub fn parse_path_seq_update_tables(
 
     log::debug!("parsing path sequences of size {} bytes..", end);
 
-    let items_ptr = Wrap(&mut item_table.items);
-    let id_prefsum_ptr = Wrap(&mut item_table.id_prefsum);
-
-    let mutex_item_table = Arc::new(Mutex::new(&mut item_table.items));
-
-    //let mut plus_strands: Vec<u32> = vec![0; rayon::current_num_threads()];
-    let bp_len = data[..end]
+    // see parse_walk_seq_update_tables for why collecting into a plain Vec here -- instead of
+    // pushing into item_table.items from within the parallel closure via a shared mutex -- is
+    // both simpler and order-preserving. The `bool` is whether the node was visited on the `+`
+    // strand, tallied into `StrandComposition` below -- this used to be thrown away (see the
+    // git history of this function), now it's the per-path strand-bias signal the request asks
+    // for.
+    let segment_ids: Vec<(u64, u32, bool)> = data[..end]
         .par_split(|&x| x == b',')
         .map(|node| {
             let segment_id = graph_storage
                 .get_node_id(&node[0..node.len() - 1])
-                .unwrap_or_else(|| panic!("unknown node {}", str::from_utf8(node).unwrap()));
+                .ok_or_else(|| GfaParseError {
+                    kind: GfaParseErrorKind::UnknownNode,
+                    line,
+                    byte_offset: None,
+                    reason: match str::from_utf8(node) {
+                        Ok(s) => format!("unknown node {}", s),
+                        Err(_) => "unknown node (non-UTF-8 identifier)".to_string(),
+                    },
+                })?;
             // TODO: Is orientation really necessary?
             let orientation = node[node.len() - 1];
-            assert!(
-                orientation == b'-' || orientation == b'+',
-                "unknown orientation of segment {}",
-                str::from_utf8(node).unwrap()
-            );
-            //plus_strands[rayon::current_thread_index().unwrap()] += (orientation == b'+') as u32;
-
-            if let Ok(_) = mutex_item_table.lock() {
-                unsafe {
-                    (*items_ptr.0).push(segment_id.0);
-                    (*id_prefsum_ptr.0)[num_path + 1] += 1;
-                }
+            if orientation != b'-' && orientation != b'+' {
+                return Err(GfaParseError {
+                    kind: GfaParseErrorKind::MalformedField,
+                    line,
+                    byte_offset: None,
+                    reason: match str::from_utf8(node) {
+                        Ok(s) => format!("unknown orientation of segment {}", s),
+                        Err(_) => {
+                            "unknown orientation of segment (non-UTF-8 identifier)".to_string()
+                        }
+                    },
+                });
             }
-            graph_storage.node_len(&segment_id)
+            Ok((
+                segment_id.0,
+                graph_storage.node_len(&segment_id),
+                orientation == b'+',
+            ))
         })
-        .sum();
+        .collect::<Result<Vec<_>, GfaParseError>>()?;
+
+    let mut bp_len: u32 = segment_ids.iter().map(|&(_, l, _)| l).sum();
+    if overlap_adjusted_bp {
+        let overlaps = parse_path_overlaps(&data[end..], segment_ids.len(), line)?;
+        let total_overlap: usize = overlaps.iter().sum();
+        bp_len -= total_overlap.min(bp_len as usize) as u32;
+    }
+    let plus = segment_ids.iter().filter(|&&(_, _, is_plus)| is_plus).count() as u32;
+    let strand = StrandComposition {
+        plus,
+        minus: segment_ids.len() as u32 - plus,
+    };
+    for (segment_id, _, _) in &segment_ids {
+        item_table.items.push(*segment_id);
+        item_table.id_prefsum[num_path + 1] += 1;
+    }
 
     // compute prefix sum
     let mut num_nodes_path = 0;
@@ -1172,13 +2095,152 @@ pub fn parse_path_seq_update_tables(
     }
 
     log::debug!("..done");
-    (num_nodes_path as u32, bp_len)
+    Ok((num_nodes_path as u32, bp_len, strand))
+}
+
+// tokenizes a GAF path column, supporting panacus' `>s1<s2>s3` oriented-segment syntax as
+// well as the comma-separated `id+,id-` form used by P-lines
+fn tokenize_gaf_path(path: &[u8]) -> Vec<(Orientation, Vec<u8>)> {
+    if path.first() == Some(&b'>') || path.first() == Some(&b'<') {
+        let mut res = Vec::new();
+        let mut i = 0;
+        while i < path.len() {
+            let orientation = Orientation::from_lg(path[i]);
+            let start = i + 1;
+            let mut j = start;
+            while j < path.len() && path[j] != b'>' && path[j] != b'<' {
+                j += 1;
+            }
+            res.push((orientation, path[start..j].to_vec()));
+            i = j;
+        }
+        res
+    } else {
+        path.split(|&b| b == b',')
+            .filter(|s| !s.is_empty())
+            .map(|s| match s.split_last() {
+                Some((b'+', rest)) => (Orientation::Forward, rest.to_vec()),
+                Some((b'-', rest)) => (Orientation::Backward, rest.to_vec()),
+                _ => (Orientation::Forward, s.to_vec()),
+            })
+            .collect()
+    }
+}
+
+/// Parses a single GAF (Graph Alignment Format) record and returns the sequence of graph
+/// items it traverses, in the same `(ItemId, Orientation)` form produced by
+/// `parse_walk_seq_to_item_vec`/`parse_path_seq_to_item_vec`. The path column (col. 6) may use
+/// panacus' oriented-segment syntax (`>s1<s2>s3`) or the P-line style `id+,id-` form. Records
+/// with an unaligned path (`*`), a mapping quality (col. 12) below `min_mapq`, or a traversed
+/// node id not present in `graph_storage` (e.g. the GAF was aligned against a different build
+/// of the graph) are skipped.
+/// The first and last traversed node are clipped against the alignment's path-start/path-end
+/// offsets (col. 8/9), so that nodes lying entirely outside the aligned region are excluded.
+pub fn parse_gaf_to_item_vec(
+    line: &[u8],
+    graph_storage: &GraphStorage,
+    min_mapq: u8,
+) -> Option<Vec<(ItemId, Orientation)>> {
+    let fields: Vec<&[u8]> = line.split(|&b| b == b'\t').collect();
+    if fields.len() < 12 {
+        return None;
+    }
+
+    let path = fields[5];
+    if path == b"*" {
+        return None;
+    }
+
+    let mapq: u8 = str::from_utf8(fields[11])
+        .ok()
+        .and_then(|s| s.trim_end().parse().ok())
+        .unwrap_or(0);
+    if mapq < min_mapq {
+        return None;
+    }
+
+    let pstart: usize = str::from_utf8(fields[7]).ok()?.trim().parse().ok()?;
+    let pend: usize = str::from_utf8(fields[8]).ok()?.trim().parse().ok()?;
+
+    let mut offset = 0;
+    let mut sids = Vec::new();
+    for (orientation, node) in tokenize_gaf_path(path) {
+        let sid = graph_storage.get_node_id(&node)?;
+        let len = graph_storage.node_len(&sid) as usize;
+        // drop nodes that fall entirely outside the aligned [pstart, pend) window, which
+        // clips the first/last segment of the path to the reported alignment boundaries
+        if offset + len > pstart && offset < pend {
+            sids.push((sid, orientation));
+        }
+        offset += len;
+    }
+    Some(sids)
+}
+
+/// Accumulates the per-node read coverage of a single GAF alignment record into `item_table`,
+/// using the same `ItemTable`/`num_path` bookkeeping as `parse_walk_seq_update_tables` and
+/// `parse_path_seq_update_tables`, so a GAF file slots into the existing coverage/growth
+/// machinery as if each aligned read were its own path. Returns `None` if the record is
+/// skipped (unaligned path or mapping quality below `min_mapq`), otherwise the number of
+/// covered nodes and bp.
+pub fn parse_gaf_update_tables(
+    line: &[u8],
+    graph_storage: &GraphStorage,
+    item_table: &mut ItemTable,
+    num_path: usize,
+    min_mapq: u8,
+) -> Option<(u32, u32)> {
+    let sids = parse_gaf_to_item_vec(line, graph_storage, min_mapq)?;
+
+    let mut bp_len = 0;
+    for (sid, _) in &sids {
+        item_table.items.push(sid.0);
+        item_table.id_prefsum[num_path + 1] += 1;
+        bp_len += graph_storage.node_len(sid);
+    }
+
+    let num_nodes_path = item_table.id_prefsum[num_path + 1];
+    item_table.id_prefsum[num_path + 1] += item_table.id_prefsum[num_path];
+
+    Some((num_nodes_path as u32, bp_len))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_gaf_path_tokenization_and_mapq_filter() {
+        let graph_storage =
+            GraphStorage::from_gfa("tests/test_files/t_groups.gfa", true, CountType::Node);
+
+        // below the mapping quality threshold -> skipped
+        let low_mapq = b"read1\t100\t0\t100\t+\t>1>3>5\t100\t0\t100\t100\t100\t10";
+        assert!(parse_gaf_to_item_vec(low_mapq, &graph_storage, 20).is_none());
+
+        // unaligned record -> skipped
+        let unaligned = b"read2\t100\t0\t100\t+\t*\t0\t0\t0\t0\t0\t60";
+        assert!(parse_gaf_to_item_vec(unaligned, &graph_storage, 0).is_none());
+
+        // P-line style path syntax is tokenized the same way as the oriented-segment form
+        let plain = tokenize_gaf_path(b"1+,3+,5+");
+        let oriented = tokenize_gaf_path(b">1>3>5");
+        assert_eq!(
+            plain.iter().map(|(_, n)| n.clone()).collect::<Vec<_>>(),
+            oriented.iter().map(|(_, n)| n.clone()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_gaf_unknown_node_is_skipped_not_panicked() {
+        let graph_storage =
+            GraphStorage::from_gfa("tests/test_files/t_groups.gfa", true, CountType::Node);
+
+        // node "999" doesn't exist in t_groups.gfa -> the whole record is skipped, not a panic
+        let unknown_node = b"read1\t100\t0\t100\t+\t>1>999>5\t100\t0\t100\t100\t100\t60";
+        assert!(parse_gaf_to_item_vec(unknown_node, &graph_storage, 0).is_none());
+    }
+
     #[test]
     fn test_chunk_sizes() {
         let data = "1+,3+,5+,6+,8+,9+,11+,12+,14+,15+\t8M,1M,1M,3M,1M,19M,1M,4M,1M,11M".as_bytes();
@@ -1202,7 +2264,7 @@ mod tests {
         ];
         for i in 1..35 {
             eprintln!("{}:", i);
-            let (res, _) = get_path_segment_ids(data, &graph_storage, end, i);
+            let (res, _) = get_path_segment_ids(data, &graph_storage, end, i, 1).unwrap();
             assert_eq!(res, exp);
         }
     }
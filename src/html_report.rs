@@ -1,5 +1,6 @@
 use base64::prelude::*;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
+use std::collections::BinaryHeap;
 use std::ffi::OsStr;
 use std::fs::File;
 use std::io::{BufReader, Read};
@@ -42,6 +43,7 @@ pub const REPORT_CONTENT_HBS: &[u8] = include_bytes!("../hbs/report_content.hbs"
 pub const HEXBIN_HBS: &[u8] = include_bytes!("../hbs/hexbin.hbs");
 pub const LINE_HBS: &[u8] = include_bytes!("../hbs/line.hbs");
 pub const PNG_HBS: &[u8] = include_bytes!("../hbs/png.hbs");
+pub const RAREFACTION_HBS: &[u8] = include_bytes!("../hbs/rarefaction.hbs");
 
 fn combine_vars(mut a: JsVars, b: JsVars) -> JsVars {
     for (k, v) in b {
@@ -52,6 +54,33 @@ fn combine_vars(mut a: JsVars, b: JsVars) -> JsVars {
     a
 }
 
+/// One analysis/run/countable combination as reported by [`AnalysisSection::summarize`], for
+/// `panacus inspect`.
+#[derive(Serialize, Debug)]
+pub struct SectionSummary {
+    pub analysis: String,
+    pub run_name: String,
+    pub countable: String,
+    pub item_kinds: Vec<&'static str>,
+    pub num_samples: Option<usize>,
+    pub has_table: bool,
+}
+
+/// The structured, composable report model: a title/id (`id`, plus `analysis`/`run_name`/
+/// `countable` identifying which run produced it), a list of typed content blocks (`items`,
+/// see [`ReportItem`] -- tables, bar/line/heatmap/hexbin plots with axis labels and series
+/// data, images), and an optional rendered summary `table`. `execute_pipeline` is the `Report`
+/// aggregator: it collects one `AnalysisSection` per analysis across a multi-analysis
+/// invocation and renders the whole batch to a single self-contained HTML page via
+/// [`AnalysisSection::into_html`], to machine-readable JSON via
+/// [`AnalysisSection::to_json_with_digest`], or to a flat tsv via `generate_report_flat`, so downstream tooling can
+/// consume results without scraping HTML.
+///
+/// Two things this doesn't do yet: `items` is flat (no nested child subsections), and there's
+/// no free-text/markdown note block or a place to stash the
+/// [`InputRequirement`](crate::analyses::InputRequirement) set that
+/// produced a section -- that provenance currently lives only in the `Task::GraphStateChange`
+/// that built the shared `GraphBroker`, not alongside the section itself.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct AnalysisSection {
     pub analysis: String,
@@ -146,15 +175,39 @@ fn get_extension_from_filename(filename: &str) -> Option<&str> {
     Path::new(filename).extension().and_then(OsStr::to_str)
 }
 
+// escapes a string for safe interpolation inside a single/double-quoted JS string or template
+// literal embedded in the report's inline `<script>` blocks: backslashes and quote characters
+// are escaped, `${` is split so it can't start a template substitution, and a literal
+// `</script` is split so the HTML parser can't end the surrounding script block early. Used for
+// any filename/run-name-derived text (object keys, `fname`) that ends up spliced into the
+// report's JS rather than passed through `to_json`, which already escapes JSON string values.
+fn escape_js_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\'' => out.push_str("\\'"),
+            '"' => out.push_str("\\\""),
+            '`' => out.push_str("\\`"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\u{2028}' => out.push_str("\\u2028"),
+            '\u{2029}' => out.push_str("\\u2029"),
+            _ => out.push(c),
+        }
+    }
+    out.replace("${", "$\\{").replace("</script", "<\\/script")
+}
+
 fn get_js_objects_string(objects: JsVars) -> String {
     let mut res = String::from("{");
     for (k, v) in objects {
         res.push('"');
-        res.push_str(&k);
+        res.push_str(&escape_js_string(&k));
         res.push_str("\": {");
         for (subkey, subvalue) in v {
             res.push('"');
-            res.push_str(&subkey);
+            res.push_str(&escape_js_string(&subkey));
             res.push_str("\": ");
             res.push_str(&subvalue);
             res.push_str(", ");
@@ -170,9 +223,323 @@ impl AnalysisSection {
         sections: Vec<Self>,
         registry: &mut Handlebars,
         filename: &str,
+    ) -> Result<String, RenderError> {
+        Self::generate_report_themed(sections, registry, filename, None, "auto")
+    }
+
+    /// Serialize a report to JSON wrapped in `{"_digest": ..., "sections": [...]}`: the digest
+    /// is a SHA-256 over the canonicalized (key-sorted) `sections` payload, so `render --verify`
+    /// can confirm a JSON result shared between collaborators or regenerated elsewhere is
+    /// bit-for-bit equivalent before building a report from it.
+    pub fn to_json_with_digest(sections: &[Self]) -> anyhow::Result<String> {
+        let payload = serde_json::to_value(sections)?;
+        let digest = crate::io::digest_json_payload(&payload);
+        let doc = serde_json::json!({ "_digest": digest, "sections": payload });
+        Ok(serde_json::to_string_pretty(&doc)?)
+    }
+
+    /// Load a JSON result file written by [`Self::to_json_with_digest`], or a bare
+    /// `[AnalysisSection]` array from before the `"_digest"` wrapper existed. Returns the
+    /// sections plus the stored digest, if any.
+    pub fn load_json(path: &str) -> anyhow::Result<(Vec<Self>, Option<String>)> {
+        let file = File::open(path)?;
+        let value: serde_json::Value = serde_json::from_reader(BufReader::new(file))?;
+        Self::load_json_value(value)
+    }
+
+    /// Same as [`Self::load_json`], but for a JSON document that's already in memory, e.g. piped
+    /// in over stdin or passed inline via `--json`.
+    pub fn load_json_str(content: &str) -> anyhow::Result<(Vec<Self>, Option<String>)> {
+        let value: serde_json::Value = serde_json::from_str(content)?;
+        Self::load_json_value(value)
+    }
+
+    fn load_json_value(value: serde_json::Value) -> anyhow::Result<(Vec<Self>, Option<String>)> {
+        match value {
+            serde_json::Value::Object(mut map) => {
+                let digest = map
+                    .remove("_digest")
+                    .and_then(|v| v.as_str().map(String::from));
+                let sections_value = map
+                    .remove("sections")
+                    .ok_or_else(|| anyhow::anyhow!("JSON result is missing a `sections` field"))?;
+                Ok((serde_json::from_value(sections_value)?, digest))
+            }
+            serde_json::Value::Array(_) => Ok((serde_json::from_value(value)?, None)),
+            _ => anyhow::bail!("JSON result must be an object or an array of sections"),
+        }
+    }
+
+    /// Recompute the digest of `path`'s stored `sections` payload and compare it against the
+    /// `"_digest"` it carries, for `render --verify`. Returns `false` (not an error) both when
+    /// the digest doesn't match and when the file predates digests entirely, since either way
+    /// the file's integrity can't be confirmed.
+    pub fn verify_json(path: &str) -> anyhow::Result<bool> {
+        let file = File::open(path)?;
+        let value: serde_json::Value = serde_json::from_reader(BufReader::new(file))?;
+        Self::verify_json_value(&value)
+    }
+
+    /// Same as [`Self::verify_json`], but for a JSON document that's already in memory.
+    pub fn verify_json_str(content: &str) -> anyhow::Result<bool> {
+        let value: serde_json::Value = serde_json::from_str(content)?;
+        Self::verify_json_value(&value)
+    }
+
+    fn verify_json_value(value: &serde_json::Value) -> anyhow::Result<bool> {
+        let map = match value {
+            serde_json::Value::Object(map) => map,
+            _ => return Ok(false),
+        };
+        let stored = match map.get("_digest").and_then(|v| v.as_str()) {
+            Some(d) => d.to_string(),
+            None => return Ok(false),
+        };
+        let sections = match map.get("sections") {
+            Some(v) => v,
+            None => return Ok(false),
+        };
+        let recomputed = crate::io::digest_json_payload(sections);
+        Ok(recomputed == stored)
+    }
+
+    /// Concatenate each section's already-computed `table` (the same tsv text the HTML report
+    /// embeds as a data-hook table) into one tsv document, so `report`/`render` can hand off
+    /// the same underlying data to spreadsheet/notebook tooling without going through HTML or
+    /// the full `AnalysisSection` JSON model. Sections without a table (plot-only items such as
+    /// custom SVG/PNG attachments) are skipped.
+    pub fn generate_report_tsv(sections: &[Self]) -> String {
+        let mut res = String::new();
+        for section in sections {
+            if let Some(table) = &section.table {
+                res.push_str(&format!(
+                    "# {} / {} / {}\n",
+                    section.analysis, section.run_name, section.countable
+                ));
+                res.push_str(table);
+                if !table.ends_with('\n') {
+                    res.push('\n');
+                }
+                res.push('\n');
+            }
+        }
+        res
+    }
+
+    /// Summarize a loaded report for `panacus inspect`: which analysis/run/countable combinations
+    /// are present and what kind of chart each one carries, without registering any Handlebars
+    /// template or otherwise touching the HTML rendering path. The current JSON schema doesn't
+    /// carry a panacus version stamp or the original count-type/quorum/grouping parameters, so
+    /// those aren't part of the summary; `num_samples` is only known for `Rarefaction` items,
+    /// the one kind that stores it directly.
+    pub fn summarize(sections: &[Self]) -> Vec<SectionSummary> {
+        sections
+            .iter()
+            .map(|section| SectionSummary {
+                analysis: section.analysis.clone(),
+                run_name: section.run_name.clone(),
+                countable: section.countable.clone(),
+                item_kinds: section.items.iter().map(ReportItem::kind_name).collect(),
+                num_samples: section.items.iter().find_map(ReportItem::num_samples),
+                has_table: section.table.is_some(),
+            })
+            .collect()
+    }
+
+    /// Compact terminal overview for `panacus report --format summary`: one line per
+    /// analysis/run/countable combination, reusing the same [`SectionSummary`] data `panacus
+    /// inspect` reports after the fact, so a CI pipeline gets a pass/fail-at-a-glance view
+    /// without generating the HTML shell or a full JSON document first.
+    pub fn generate_report_summary(sections: &[Self]) -> String {
+        let mut res = String::new();
+        for summary in Self::summarize(sections) {
+            res.push_str(&format!(
+                "{} / {} / {}",
+                summary.analysis, summary.run_name, summary.countable
+            ));
+            if let Some(num_samples) = summary.num_samples {
+                res.push_str(&format!(" ({num_samples} samples)"));
+            }
+            res.push_str(&format!(" [{}]", summary.item_kinds.join(", ")));
+            if !summary.has_table {
+                res.push_str(" (no table)");
+            }
+            res.push('\n');
+        }
+        res
+    }
+
+    /// One tab-separated record per analysis/run/countable combination, for piping into
+    /// downstream scripts; same underlying data as [`Self::generate_report_summary`], just
+    /// flattened into a machine-readable tsv instead of a human-facing line.
+    pub fn generate_report_flat(sections: &[Self]) -> String {
+        let mut res =
+            String::from("analysis\trun_name\tcountable\titem_kinds\tnum_samples\thas_table\n");
+        for summary in Self::summarize(sections) {
+            res.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                summary.analysis,
+                summary.run_name,
+                summary.countable,
+                summary.item_kinds.join(","),
+                summary
+                    .num_samples
+                    .map(|n| n.to_string())
+                    .unwrap_or_default(),
+                summary.has_table,
+            ));
+        }
+        res
+    }
+
+    /// Render each section's `Bar`/`Line` chart items as a static SVG, server-side, from the
+    /// parsed JSON dataset rather than in the browser with Vega -- for inclusion in papers or
+    /// other contexts where a live HTML page with embedded JS isn't wanted. Items of other
+    /// kinds (tables, heatmaps, hexbins, pre-rendered images, rarefaction) aren't charted this
+    /// way yet and are skipped; a `<!-- ... -->` comment marks each skip so it's obvious the
+    /// panel is missing rather than silently empty.
+    pub fn generate_report_svg(sections: &[Self]) -> String {
+        const WIDTH: f64 = 640.0;
+        const HEIGHT: f64 = 360.0;
+        const MARGIN: f64 = 40.0;
+
+        let mut panels = Vec::new();
+        for section in sections {
+            for item in &section.items {
+                match item {
+                    ReportItem::Bar {
+                        name,
+                        labels,
+                        values,
+                        ..
+                    } => {
+                        panels.push(Self::render_bar_svg(name, labels, values, WIDTH, HEIGHT, MARGIN));
+                    }
+                    ReportItem::Line {
+                        name,
+                        x_values,
+                        y_values,
+                        ..
+                    } => {
+                        panels.push(Self::render_line_svg(
+                            name, x_values, y_values, WIDTH, HEIGHT, MARGIN,
+                        ));
+                    }
+                    other => panels.push(format!(
+                        "<!-- skipped {} item {}: server-side SVG export not implemented for this chart kind -->",
+                        section.analysis,
+                        other.get_id()
+                    )),
+                }
+            }
+        }
+
+        let total_height = HEIGHT * panels.len().max(1) as f64;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{total_height}\" viewBox=\"0 0 {WIDTH} {total_height}\">\n"
+        );
+        for (i, panel) in panels.into_iter().enumerate() {
+            svg.push_str(&format!(
+                "<g transform=\"translate(0, {})\">{}</g>\n",
+                HEIGHT * i as f64,
+                panel
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn render_bar_svg(
+        name: &str,
+        labels: &[String],
+        values: &[f64],
+        width: f64,
+        height: f64,
+        margin: f64,
+    ) -> String {
+        let max_value = values.iter().cloned().fold(0.0_f64, f64::max).max(1.0);
+        let plot_width = width - 2.0 * margin;
+        let plot_height = height - 2.0 * margin;
+        let bar_width = plot_width / values.len().max(1) as f64;
+        let mut bars = String::new();
+        for (i, value) in values.iter().enumerate() {
+            let bar_height = (value / max_value) * plot_height;
+            let x = margin + i as f64 * bar_width;
+            let y = margin + (plot_height - bar_height);
+            bars.push_str(&format!(
+                "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"steelblue\" />\n",
+                x + bar_width * 0.1,
+                y,
+                bar_width * 0.8,
+                bar_height
+            ));
+        }
+        format!(
+            "<text x=\"{margin}\" y=\"{}\" font-size=\"14\">{}</text>\n<g>{bars}</g>\n<text x=\"{margin}\" y=\"{:.2}\">{}</text>",
+            margin - 10.0,
+            escape_js_string(name),
+            height - margin + 20.0,
+            labels.join(", "),
+        )
+    }
+
+    fn render_line_svg(
+        name: &str,
+        x_values: &[f32],
+        y_values: &[f32],
+        width: f64,
+        height: f64,
+        margin: f64,
+    ) -> String {
+        let max_x = x_values.iter().cloned().fold(0.0_f32, f32::max).max(1.0) as f64;
+        let max_y = y_values.iter().cloned().fold(0.0_f32, f32::max).max(1.0) as f64;
+        let plot_width = width - 2.0 * margin;
+        let plot_height = height - 2.0 * margin;
+        let points = x_values
+            .iter()
+            .zip(y_values.iter())
+            .map(|(x, y)| {
+                let px = margin + (*x as f64 / max_x) * plot_width;
+                let py = margin + plot_height - (*y as f64 / max_y) * plot_height;
+                format!("{px:.2},{py:.2}")
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!(
+            "<text x=\"{margin}\" y=\"{}\" font-size=\"14\">{}</text>\n<polyline points=\"{points}\" fill=\"none\" stroke=\"steelblue\" stroke-width=\"2\" />",
+            margin - 10.0,
+            escape_js_string(name),
+        )
+    }
+
+    /// Overwrite `run_name` on every section so multiple JSON result files rendered together
+    /// (`render --compare`) stay grouped by source in the report's tree/tabs instead of
+    /// colliding under whatever run names happen to already be baked into each document. This
+    /// reuses the existing analysis/run_name/countable tree rather than a dedicated overlay
+    /// chart, since the bundled report JS doesn't have a multi-dataset canvas to hand off to.
+    pub fn relabel(mut sections: Vec<Self>, label: &str) -> Vec<Self> {
+        for section in &mut sections {
+            section.run_name = label.to_string();
+        }
+        sections
+    }
+
+    /// Like [`Self::generate_report`], but lets the caller supply a Handlebars template
+    /// (already read from a user-provided file) in place of the bundled `REPORT_HBS`, and
+    /// an initial `theme` (`"light"`, `"dark"`, or `"auto"`) for the Bootstrap color-mode
+    /// switcher baked into `bootstrap_color_modes_js`.
+    pub fn generate_report_themed(
+        sections: Vec<Self>,
+        registry: &mut Handlebars,
+        filename: &str,
+        custom_template: Option<&str>,
+        theme: &str,
     ) -> Result<String, RenderError> {
         if !registry.has_template("report") {
-            registry.register_template_string("report", from_utf8(REPORT_HBS).unwrap())?;
+            let template_src = custom_template
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| from_utf8(REPORT_HBS).unwrap().to_string());
+            registry.register_template_string("report", template_src)?;
         }
 
         let tree = Self::get_tree(&sections, registry)?;
@@ -181,8 +548,9 @@ impl AnalysisSection {
         let mut vars = Self::get_variables();
         vars.insert("content", content);
         vars.insert("data_hook", get_js_objects_string(js_objects));
-        vars.insert("fname", filename.to_string());
+        vars.insert("fname", escape_js_string(filename));
         vars.insert("tree", tree);
+        vars.insert("theme", theme.to_string());
         registry.render("report", &vars)
     }
 
@@ -391,6 +759,16 @@ pub enum ReportItem {
         id: String,
         file: String,
     },
+    /// Coverage histogram plus coverage/quorum sliders; the growth curve itself is recomputed
+    /// client-side from `hist` (instead of shipping one precomputed `Growth` per threshold
+    /// combination), so exploring a new threshold doesn't require re-running the CLI.
+    Rarefaction {
+        id: String,
+        name: String,
+        /// `hist[j]` = number of features covered by exactly `j` of `num_samples` paths, `j = 1..=num_samples`.
+        hist: Vec<usize>,
+        num_samples: usize,
+    },
 }
 
 impl ReportItem {
@@ -405,6 +783,7 @@ impl ReportItem {
             Self::Png { id, .. } => id.to_string(),
             Self::Svg { id, .. } => id.to_string(),
             Self::Json { id, .. } => id.to_string(),
+            Self::Rarefaction { id, .. } => id.to_string(),
         }
     }
 
@@ -419,11 +798,66 @@ impl ReportItem {
             Self::Png { .. } => "Png".to_string(),
             Self::Svg { .. } => "Svg".to_string(),
             Self::Json { .. } => "Json".to_string(),
+            Self::Rarefaction { name, .. } => name.to_string(),
+        }
+    }
+
+    /// Short, stable label for `panacus inspect`'s item-kind listing.
+    fn kind_name(&self) -> &'static str {
+        match self {
+            Self::Bar { .. } => "Bar",
+            Self::MultiBar { .. } => "MultiBar",
+            Self::Table { .. } => "Table",
+            Self::Heatmap { .. } => "Heatmap",
+            Self::Hexbin { .. } => "Hexbin",
+            Self::Line { .. } => "Line",
+            Self::Png { .. } => "Png",
+            Self::Svg { .. } => "Svg",
+            Self::Json { .. } => "Json",
+            Self::Rarefaction { .. } => "Rarefaction",
+        }
+    }
+
+    /// `Some(num_samples)` for a `Rarefaction` item, the only kind that stores the sample count
+    /// directly; `None` otherwise.
+    fn num_samples(&self) -> Option<usize> {
+        match self {
+            Self::Rarefaction { num_samples, .. } => Some(*num_samples),
+            _ => None,
         }
     }
 
     fn into_html(self, registry: &mut Handlebars) -> RenderedHTML {
         match self {
+            Self::Rarefaction {
+                id,
+                name,
+                hist,
+                num_samples,
+            } => {
+                if !registry.has_template("rarefaction") {
+                    registry.register_template_string(
+                        "rarefaction",
+                        from_utf8(RAREFACTION_HBS).unwrap(),
+                    )?;
+                }
+                let hist_text = format!(
+                    "[{}]",
+                    hist.iter().map(|h| h.to_string()).collect::<Vec<_>>().join(", ")
+                );
+                let js_object = format!(
+                    "new Rarefaction('{}', '{}', {}, {})",
+                    id, name, num_samples, hist_text
+                );
+                let data = HashMap::from([("id".to_string(), to_json(&id))]);
+                Ok((
+                    registry.render("rarefaction", &data)?,
+                    HashMap::from([(
+                        "datasets".to_string(),
+                        HashMap::from([(id.clone(), js_object)]),
+                    )]),
+                ))
+            }
             Self::Table { id, header, values } => {
                 if !registry.has_template("table") {
                     registry.register_template_string("table", from_utf8(TABLE_HBS).unwrap())?;
@@ -566,8 +1000,8 @@ impl ReportItem {
                 let mut js_object = format!("new Hexbin('{}', {{'values': [", id,);
                 for (_i, bin) in bins.iter().enumerate() {
                     js_object.push_str(&format!(
-                        "{{ coverage: {}, length: {}, size: {} }}, ",
-                        bin.x, bin.y, bin.size,
+                        "{{ coverage: {}, length: {}, size: {}, real_coverage: {}, real_length: {}, weight: {} }}, ",
+                        bin.x, bin.y, bin.size, bin.real_x, bin.real_y, bin.weight_sum,
                     ));
                 }
                 js_object.push_str("]}, [");
@@ -653,6 +1087,21 @@ pub struct Bin {
     pub size: u64,
     pub x: f64,
     pub y: f64,
+    // `x`/`y` in the space binning was actually performed in, i.e. log10(1+value) when
+    // `hexbin`/`squarebin` was called with `log_x`/`log_y` set; `real_x`/`real_y` hold the same
+    // position converted back to linear units, so axis ticks can be labeled in the values a user
+    // actually expects instead of their log-transformed bin coordinates. Equal to `x`/`y` when
+    // the corresponding axis wasn't log-scaled.
+    #[serde(default)]
+    pub real_x: f64,
+    #[serde(default)]
+    pub real_y: f64,
+    // Sum of each member's weight, when `hexbin`/`squarebin` was called with a `weights` vector
+    // (e.g. node length in bp), so a bin's shading can reflect how much sequence it holds rather
+    // than how many nodes happen to fall in it. Falls back to `size` (i.e. a weight of 1 per
+    // member) when no weight vector was supplied, so this field is always meaningful on its own.
+    #[serde(default)]
+    pub weight_sum: u64,
     pub content: Vec<ItemId>,
 }
 
@@ -674,24 +1123,163 @@ struct CounterBin {
     pub real_y: f64,
 }
 
+// A squared distance, ordered so a max-heap of these keeps its *worst* (largest) candidate on
+// top -- the shape `KdTree::kth_nearest_distance`'s bounded heap needs to cheaply evict the
+// worst of its k best-so-far candidates as closer points are found.
+#[derive(PartialEq)]
+struct HeapDist(f64);
+
+impl Eq for HeapDist {}
+
+impl PartialOrd for HeapDist {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapDist {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.partial_cmp(&other.0).unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+// A 2D k-d tree over bin-plot points, built once per `apply_knn_density` call and queried once
+// per bin center to turn raw point density into a k-nearest-neighbor estimate that adapts to how
+// sparse or dense the local neighborhood is, instead of saturating like a raw count would.
+struct KdTree {
+    point: (f64, f64),
+    axis: u8,
+    left: Option<Box<KdTree>>,
+    right: Option<Box<KdTree>>,
+}
+
+impl KdTree {
+    // Builds the tree recursively, splitting `points` on the median of the current axis
+    // (alternating x/y by depth) so the tree stays roughly balanced regardless of input order.
+    fn build(points: &mut [(f64, f64)], depth: usize) -> Option<Box<Self>> {
+        if points.is_empty() {
+            return None;
+        }
+        let axis = (depth % 2) as u8;
+        points.sort_by(|a, b| {
+            let (va, vb) = if axis == 0 { (a.0, b.0) } else { (a.1, b.1) };
+            va.partial_cmp(&vb).unwrap()
+        });
+        let mid = points.len() / 2;
+        let point = points[mid];
+        let (left_points, rest) = points.split_at_mut(mid);
+        let right_points = &mut rest[1..];
+        Some(Box::new(Self {
+            point,
+            axis,
+            left: Self::build(left_points, depth + 1),
+            right: Self::build(right_points, depth + 1),
+        }))
+    }
+
+    // Descends into the near child first (the side of the splitting plane `(qx, qy)` falls on),
+    // then only descends into the far child if the squared distance to the splitting plane is
+    // still smaller than the worst of the k candidates found so far -- the standard k-d tree
+    // pruning rule, since any point on the far side is at least `axis_gap` away on that axis
+    // alone.
+    fn visit(node: &Option<Box<Self>>, qx: f64, qy: f64, k: usize, heap: &mut BinaryHeap<HeapDist>) {
+        let Some(n) = node else {
+            return;
+        };
+        let dx = n.point.0 - qx;
+        let dy = n.point.1 - qy;
+        let dist2 = dx * dx + dy * dy;
+        if heap.len() < k {
+            heap.push(HeapDist(dist2));
+        } else if dist2 < heap.peek().unwrap().0 {
+            heap.pop();
+            heap.push(HeapDist(dist2));
+        }
+
+        let axis_gap = if n.axis == 0 { qx - n.point.0 } else { qy - n.point.1 };
+        let (near, far) = if axis_gap < 0.0 {
+            (&n.left, &n.right)
+        } else {
+            (&n.right, &n.left)
+        };
+        Self::visit(near, qx, qy, k, heap);
+        let worst = if heap.len() < k {
+            f64::INFINITY
+        } else {
+            heap.peek().unwrap().0
+        };
+        if axis_gap * axis_gap < worst {
+            Self::visit(far, qx, qy, k, heap);
+        }
+    }
+
+    // Distance from `(qx, qy)` to its k-th nearest neighbor in the tree (0.0 if the tree has
+    // fewer than k points, since there's no meaningful k-th neighbor to report).
+    fn kth_nearest_distance(root: &Option<Box<Self>>, qx: f64, qy: f64, k: usize) -> f64 {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        Self::visit(root, qx, qy, k, &mut heap);
+        if heap.len() < k {
+            return 0.0;
+        }
+        heap.peek().map_or(0.0, |d| d.0.sqrt())
+    }
+}
+
 impl Bin {
-    pub fn hexbin(points: &Vec<(ItemId, u32, f64)>, nx: u32, ny: u32) -> Vec<Self> {
-        let max_coverage = points
+    // log10(1+v) rather than log10(v) so a zero-valued point (e.g. coverage 0) still maps to a
+    // finite coordinate instead of -infinity.
+    fn axis_transform(value: f64, log_scale: bool) -> f64 {
+        if log_scale {
+            (1.0 + value).log10()
+        } else {
+            value
+        }
+    }
+
+    // Inverse of `axis_transform`, used to recover the linear-unit value a bin's transformed
+    // center corresponds to, for `real_x`/`real_y`.
+    fn axis_untransform(value: f64, log_scale: bool) -> f64 {
+        if log_scale {
+            10f64.powf(value) - 1.0
+        } else {
+            value
+        }
+    }
+
+    pub fn hexbin(
+        points: &Vec<(ItemId, u32, f64)>,
+        nx: u32,
+        ny: u32,
+        log_density: bool,
+        log_x: bool,
+        log_y: bool,
+        weights: Option<&[u64]>,
+    ) -> Vec<Self> {
+        let coords: Vec<(ItemId, f64, f64)> = points
+            .iter()
+            .map(|(id, c, l)| {
+                (
+                    *id,
+                    Self::axis_transform(*c as f64, log_x),
+                    Self::axis_transform(*l, log_y),
+                )
+            })
+            .collect();
+        let max_coverage = coords
             .iter()
             .map(|(_i, c, _l)| *c)
-            .max()
-            .expect("At least one point");
-        let max_length = points.iter().map(|(_i, _c, l)| *l).fold(0. / 0., f64::max);
-        let dx = max_coverage as f64 / (nx - 1) as f64;
+            .fold(f64::NEG_INFINITY, f64::max);
+        let max_length = coords.iter().map(|(_i, _c, l)| *l).fold(0. / 0., f64::max);
+        let dx = max_coverage / (nx - 1) as f64;
         let t = dx as f64 / 3f64.sqrt();
         let dy = max_length / (ny - 1) as f64;
         // eprintln!("max c: {}, dx: {}, t: {}, dy: {}", max_coverage, dx, t, dy);
         let mut bins: HashMap<(bool, i64, i64), Self> = HashMap::new();
-        for point in points {
+        for (idx, point) in coords.iter().enumerate() {
             // Calculate positions in both grids
-            let mut black_x = (point.1 as f64 / dx).floor() * dx;
+            let mut black_x = (point.1 / dx).floor() * dx;
             let mut black_y = (point.2 / dy).floor() * dy;
-            let mut green_x = ((point.1 as f64 - dx / 2.0) / dx).floor() * dx + dx / 2.0;
+            let mut green_x = ((point.1 - dx / 2.0) / dx).floor() * dx + dx / 2.0;
             let mut green_y = ((point.2 - dy / 2.0) / dy).floor() * dy + dy / 2.0;
 
             // eprintln!("point: {:?}, black: {:?}, green: {:?}", point, (black_x, black_y), (green_x, green_y));
@@ -710,42 +1298,180 @@ impl Bin {
 
             // eprintln!("\tpoint: {:?}, black: {:?}, green: {:?}", point, (black_x, black_y), (green_x, green_y));
 
-            if Self::distance(point.1 as f64, point.2, black_x, black_y)
-                < Self::distance(point.1 as f64, point.2, green_x, green_y)
+            let weight = weights.map_or(1, |w| w[idx]);
+            if Self::distance(point.1, point.2, black_x, black_y)
+                < Self::distance(point.1, point.2, green_x, green_y)
             {
-                bins.entry((false, (black_x / dx) as i64, (black_y / dy) as i64))
+                let bin = bins
+                    .entry((false, (black_x / dx) as i64, (black_y / dy) as i64))
                     .or_insert(Self {
-                        x: black_x as f64,
-                        y: black_y as f64,
+                        x: black_x,
+                        y: black_y,
                         size: 0,
+                        real_x: 0.0,
+                        real_y: 0.0,
+                        weight_sum: 0,
                         content: Vec::new(),
-                    })
-                    .content
-                    .push(point.0);
+                    });
+                bin.content.push(point.0);
+                bin.weight_sum += weight;
             } else {
                 // eprintln!("\t\tGreen one");
-                bins.entry((
-                    true,
-                    ((green_x - dx / 2.0) / dx) as i64,
-                    ((green_y - dy / 2.0) / dy) as i64,
-                ))
-                .or_insert(Self {
-                    x: green_x as f64,
-                    y: green_y as f64,
-                    size: 0,
-                    content: Vec::new(),
-                })
-                .content
-                .push(point.0);
+                let bin = bins
+                    .entry((
+                        true,
+                        ((green_x - dx / 2.0) / dx) as i64,
+                        ((green_y - dy / 2.0) / dy) as i64,
+                    ))
+                    .or_insert(Self {
+                        x: green_x,
+                        y: green_y,
+                        size: 0,
+                        real_x: 0.0,
+                        real_y: 0.0,
+                        weight_sum: 0,
+                        content: Vec::new(),
+                    });
+                bin.content.push(point.0);
+                bin.weight_sum += weight;
             }
         }
         let mut bins: Vec<Bin> = bins.into_values().collect();
         for bin in &mut bins {
             bin.size = bin.content.len() as u64;
+            bin.real_x = Self::axis_untransform(bin.x, log_x);
+            bin.real_y = Self::axis_untransform(bin.y, log_y);
+        }
+        if log_density {
+            Self::apply_log_density(&mut bins);
         }
         bins
     }
 
+    // plain rectangular grid counterpart to `hexbin`: same (coverage, log-length) point
+    // aggregation, just without the staggered hex offset, so bin centers fall on a regular
+    // nx-by-ny grid
+    pub fn squarebin(
+        points: &Vec<(ItemId, u32, f64)>,
+        nx: u32,
+        ny: u32,
+        log_density: bool,
+        log_x: bool,
+        log_y: bool,
+        weights: Option<&[u64]>,
+    ) -> Vec<Self> {
+        let coords: Vec<(ItemId, f64, f64)> = points
+            .iter()
+            .map(|(id, c, l)| {
+                (
+                    *id,
+                    Self::axis_transform(*c as f64, log_x),
+                    Self::axis_transform(*l, log_y),
+                )
+            })
+            .collect();
+        let max_coverage = coords
+            .iter()
+            .map(|(_i, c, _l)| *c)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let max_length = coords.iter().map(|(_i, _c, l)| *l).fold(0. / 0., f64::max);
+        let dx = max_coverage / (nx - 1) as f64;
+        let dy = max_length / (ny - 1) as f64;
+        let mut bins: HashMap<(i64, i64), Self> = HashMap::new();
+        for (idx, point) in coords.iter().enumerate() {
+            let grid_x = (point.1 / dx).floor() as i64;
+            let grid_y = (point.2 / dy).floor() as i64;
+            let bin = bins.entry((grid_x, grid_y)).or_insert(Self {
+                x: grid_x as f64 * dx,
+                y: grid_y as f64 * dy,
+                size: 0,
+                real_x: 0.0,
+                real_y: 0.0,
+                weight_sum: 0,
+                content: Vec::new(),
+            });
+            bin.content.push(point.0);
+            bin.weight_sum += weights.map_or(1, |w| w[idx]);
+        }
+        let mut bins: Vec<Bin> = bins.into_values().collect();
+        for bin in &mut bins {
+            bin.size = bin.content.len() as u64;
+            bin.real_x = Self::axis_untransform(bin.x, log_x);
+            bin.real_y = Self::axis_untransform(bin.y, log_y);
+        }
+        if log_density {
+            Self::apply_log_density(&mut bins);
+        }
+        bins
+    }
+
+    // Buckets a single `(coverage, length)` point into the grid cell `squarebin` would put it
+    // in, without rebuilding the whole `Vec<Bin>` -- an O(1) "which bin is this point in" lookup
+    // for callers like interactive tooltips/annotation that only need one point at a time.
+    // `max_coverage`/`max_length` must be the same values `squarebin` derived from the point set,
+    // so the returned indices line up with the bins it produced.
+    pub fn rect_grid_index(
+        coverage: u32,
+        length: f64,
+        max_coverage: u32,
+        max_length: f64,
+        nx: u32,
+        ny: u32,
+    ) -> (i64, i64) {
+        let dx = max_coverage as f64 / (nx - 1) as f64;
+        let dy = max_length / (ny - 1) as f64;
+        ((coverage as f64 / dx).floor() as i64, (length / dy).floor() as i64)
+    }
+
+    // Adaptive alternative to the raw `content.len()` count `hexbin`/`squarebin` leave in
+    // `bin.size`: builds a k-d tree over every original point, then for each bin center runs a
+    // k-nearest-neighbor query and sets the bin's density to the "balloon estimator"
+    // `k / (pi * r_k^2)`, where `r_k` is the distance to the k-th nearest point. Dense regions
+    // get a small `r_k` (and thus high density) without saturating the way a raw count does, and
+    // sparse tails still get a non-zero reading instead of looking empty. The raw densities are
+    // then rescaled onto the same 0..=1000 range `apply_log_density` uses, so either mode can
+    // feed the same color-scale JS. A no-op when there are fewer than `k` points.
+    pub fn apply_knn_density(bins: &mut [Self], points: &[(ItemId, u32, f64)], k: usize) {
+        if k == 0 || points.len() < k {
+            return;
+        }
+        let mut coords: Vec<(f64, f64)> = points.iter().map(|(_, c, l)| (*c as f64, *l)).collect();
+        let tree = KdTree::build(&mut coords, 0);
+
+        let densities: Vec<f64> = bins
+            .iter()
+            .map(|bin| {
+                let r_k = KdTree::kth_nearest_distance(&tree, bin.x, bin.y, k);
+                if r_k > 0.0 {
+                    k as f64 / (std::f64::consts::PI * r_k * r_k)
+                } else {
+                    f64::INFINITY
+                }
+            })
+            .collect();
+        let max_density = densities.iter().cloned().filter(|d| d.is_finite()).fold(0.0, f64::max);
+        if max_density <= 0.0 {
+            return;
+        }
+        for (bin, density) in bins.iter_mut().zip(densities) {
+            bin.size = (1000.0 * density.min(max_density) / max_density).round() as u64;
+        }
+    }
+
+    // rescales each bin's raw point count onto a fixed log10 scale (0..=1000, monotonic with
+    // the raw count) so a handful of hot bins don't saturate a linear color scale on large
+    // graphs; a no-op when every bin is empty
+    fn apply_log_density(bins: &mut [Self]) {
+        let max_size = bins.iter().map(|b| b.size).max().unwrap_or(0);
+        if max_size == 0 {
+            return;
+        }
+        let log_max = ((max_size + 1) as f64).log10();
+        for bin in bins.iter_mut() {
+            bin.size = (1000.0 * ((bin.size + 1) as f64).log10() / log_max).round() as u64;
+        }
+    }
+
     fn distance(x1: f64, y1: f64, x2: f64, y2: f64) -> f64 {
         (((x1 - x2).powf(2.0) + (y1 - y2).powf(2.0)) as f64).sqrt()
     }
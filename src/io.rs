@@ -1,13 +1,19 @@
 /* standard use */
-use std::io::{BufRead, BufReader, Read};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read, Seek, SeekFrom};
 use std::io::{Error, ErrorKind};
 use std::str::{self, FromStr};
 
 /* external use */
+use bzip2::read::BzDecoder;
 use flate2::read::MultiGzDecoder;
+use memmap2::Mmap;
 use quick_csv::Csv;
 use rayon::prelude::*;
 use strum_macros::{EnumString, EnumVariantNames};
+use xz2::read::XzDecoder;
+use zstd::Decoder as ZstdDecoder;
 
 /* internal use */
 use crate::graph_broker::{AbacusByGroup, PathSegment, ThresholdContainer};
@@ -18,32 +24,249 @@ use crate::util::*;
 pub enum OutputFormat {
     Table,
     Html,
+    Json,
 }
 
-pub fn bufreader_from_compressed_gfa(gfa_file: &str) -> BufReader<Box<dyn Read>> {
-    log::info!("loading graph from {}", &gfa_file);
-    let f = std::fs::File::open(gfa_file).expect("Error opening file");
-    let reader: Box<dyn Read> = if gfa_file.ends_with(".gz") {
-        log::info!("assuming that {} is gzip compressed..", &gfa_file);
-        Box::new(MultiGzDecoder::new(f))
+// Returns a memory-mapped view of `path` when it's a regular file -- the case the
+// uncompressed/passthrough branch of `open_compressed` falls into -- so the OS can page an
+// on-disk GFA in on demand instead of this process eagerly buffering/copying the whole thing;
+// `None` for anything mmap can't handle (pipes, stdin, sockets), so the caller falls back to
+// the ordinary buffered reader.
+//
+// Safety: panacus only ever reads from the mapping; the same caveat every mmap'd reader carries
+// (the file being truncated or rewritten by another process while mapped) applies here too, and
+// is accepted rather than guarded against, as elsewhere in this codebase where files are opened
+// for read-only, short-lived use.
+fn try_mmap_plain_file(path: &str) -> Option<Mmap> {
+    let meta = std::fs::metadata(path).ok()?;
+    if !meta.is_file() {
+        return None;
+    }
+    let file = File::open(path).ok()?;
+    unsafe { Mmap::map(&file) }.ok()
+}
+
+// BGZF (the block-gzip framing `samtools`/`htslib` use so a gzip stream stays seekable) wraps
+// each <=64KiB chunk in its own ordinary gzip member, with a `BC` subfield tucked into the
+// member's `FEXTRA` field that stores the on-disk size of that one block. Ordinary gzip never
+// sets `FEXTRA`, so checking for this subfield is how `open_compressed` tells a BGZF-framed
+// `.gfa.gz` apart from a plain one -- only the former is safe to split into independently
+// decompressible chunks.
+fn is_bgzf_block_header(data: &[u8]) -> bool {
+    data.len() >= 18
+        && data[0] == 0x1f
+        && data[1] == 0x8b
+        && data[2] == 0x08
+        && data[3] & 0x04 != 0 // FEXTRA
+        && data[12] == b'B'
+        && data[13] == b'C'
+}
+
+// total size in bytes of the BGZF block starting at `data[0]`, read out of that block's `BC`
+// subfield (`BSIZE`, the block size minus one) -- `None` if `data` isn't a BGZF block header.
+fn bgzf_block_len(data: &[u8]) -> Option<usize> {
+    if !is_bgzf_block_header(data) {
+        return None;
+    }
+    let bsize = u16::from_le_bytes([data[16], data[17]]) as usize;
+    Some(bsize + 1)
+}
+
+// walks `data` block by block via `bgzf_block_len`, returning the byte range of every block;
+// `None` if the framing breaks down anywhere (a truncated file, or a block claiming to run past
+// the end of `data`), in which case the caller falls back to treating the file as plain gzip.
+fn bgzf_block_boundaries(data: &[u8]) -> Option<Vec<(usize, usize)>> {
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let len = bgzf_block_len(&data[pos..])?;
+        if len == 0 || pos + len > data.len() {
+            return None;
+        }
+        blocks.push((pos, pos + len));
+        pos += len;
+    }
+    Some(blocks)
+}
+
+// decompresses a whole BGZF file at once, one rayon task per block, instead of the single
+// streaming inflate `MultiGzDecoder` would otherwise do -- each block is itself a complete,
+// independent gzip member, so this overlaps decompression across cores with no cross-block
+// dependency to serialize on. Blocks are collected back in file order before being concatenated,
+// so the result is byte-for-byte what a sequential decode of the same file would produce.
+fn decompress_bgzf_parallel(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let blocks = bgzf_block_boundaries(data)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "malformed BGZF block framing"))?;
+    log::info!(
+        "decompressing {} BGZF block(s) across up to {} thread(s)..",
+        blocks.len(),
+        rayon::current_num_threads()
+    );
+    let decompressed: Vec<Vec<u8>> = blocks
+        .par_iter()
+        .map(|&(start, end)| -> std::io::Result<Vec<u8>> {
+            let mut out = Vec::new();
+            MultiGzDecoder::new(&data[start..end]).read_to_end(&mut out)?;
+            Ok(out)
+        })
+        .collect::<std::io::Result<Vec<_>>>()?;
+    Ok(decompressed.into_iter().flatten().collect())
+}
+
+// sniff the first few bytes of a reader and wrap it in the matching decompressor, falling
+// back to plain passthrough; used instead of extension-sniffing so any of these parsers
+// can transparently accept gzip/BGZF, zstd, bzip2, or xz input regardless of the file name.
+//
+// The passthrough (uncompressed) case additionally tries to memory-map `path` via
+// `try_mmap_plain_file` -- this is panacus' first-class, zero-buffering input mode for
+// human-pangenome-scale GFAs, and is what keeps peak RSS down to "whatever pages the parser
+// actually touches" instead of the whole file. It only applies to regular, uncompressed
+// files: pipes/stdin can't be mapped (`try_mmap_plain_file` returns `None` for them, so they
+// keep going through `peek`), and compressed input still has to flow through a streaming
+// decoder regardless, since decompression itself produces bytes that were never on disk.
+// `parse_gfa_paths_walks` and friends are untouched by this -- they still consume whichever
+// `Read` impl `BufReader` hands them via `read_until`, so `node2id` lookups and prefix-sum
+// bookkeeping are unaffected either way.
+pub fn open_compressed(path: &str) -> std::io::Result<BufReader<Box<dyn Read>>> {
+    let f = std::fs::File::open(path)?;
+    let mut peek = BufReader::new(f);
+    let magic = peek.fill_buf()?.to_vec();
+
+    let reader: Box<dyn Read> = if magic.starts_with(&[0x1f, 0x8b]) {
+        if is_bgzf_block_header(&magic) {
+            log::info!("assuming that {} is BGZF compressed..", path);
+            let mut compressed = Vec::new();
+            peek.read_to_end(&mut compressed)?;
+            match decompress_bgzf_parallel(&compressed) {
+                Ok(decompressed) => Box::new(Cursor::new(decompressed)),
+                Err(e) => {
+                    log::warn!(
+                        "{} looked BGZF-framed but failed to decompress as such ({}); falling back to streaming gzip",
+                        path, e
+                    );
+                    Box::new(MultiGzDecoder::new(Cursor::new(compressed)))
+                }
+            }
+        } else {
+            log::info!("assuming that {} is gzip compressed..", path);
+            Box::new(MultiGzDecoder::new(peek))
+        }
+    } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        log::info!("assuming that {} is zstd compressed..", path);
+        Box::new(ZstdDecoder::new(peek)?)
+    } else if magic.starts_with(&[0x42, 0x5a, 0x68]) {
+        log::info!("assuming that {} is bzip2 compressed..", path);
+        Box::new(BzDecoder::new(peek))
+    } else if magic.starts_with(&[0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00]) {
+        log::info!("assuming that {} is xz compressed..", path);
+        Box::new(XzDecoder::new(peek))
+    } else if let Some(mmap) = try_mmap_plain_file(path) {
+        log::info!("memory-mapping uncompressed {}", path);
+        Box::new(Cursor::new(mmap))
     } else {
-        Box::new(f)
+        Box::new(peek)
     };
-    BufReader::new(reader)
+    Ok(BufReader::new(reader))
 }
 
+pub fn bufreader_from_compressed_gfa(gfa_file: &str) -> BufReader<Box<dyn Read>> {
+    log::info!("loading graph from {}", &gfa_file);
+    open_compressed(gfa_file).expect("Error opening file")
+}
+
+// which of the line-oriented formats below a ParseError was raised from, so a caller juggling
+// several input files at once can tell them apart without re-parsing the message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    Bed,
+    Gff,
+    Groups,
+}
+
+impl std::fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::Bed => write!(f, "BED"),
+            ParseErrorKind::Gff => write!(f, "GFF"),
+            ParseErrorKind::Groups => write!(f, "group"),
+        }
+    }
+}
+
+// a single malformed line, carrying enough context (file kind, line, optional column) for a
+// caller to report it programmatically instead of just reading a log line; used both as the
+// fail-fast `Err` and, in lenient mode, collected into a `Vec<ParseError>` so a mostly-valid
+// multi-gigabyte file still yields a result plus a diagnostic report rather than aborting
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub line: usize,
+    pub column: Option<usize>,
+    pub reason: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.column {
+            Some(c) => write!(
+                f,
+                "error in {} line {}, column {}: {}",
+                self.kind, self.line, c, self.reason
+            ),
+            None => write!(f, "error in {} line {}: {}", self.kind, self.line, self.reason),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn log_parse_issues(issues: &[ParseError], what: &str) {
+    if !issues.is_empty() {
+        log::warn!("skipped {} malformed {} line(s):", issues.len(), what);
+        for issue in issues {
+            log::warn!("  {}", issue);
+        }
+    }
+}
+
+// BED columns 0-2 (name/start/end) plus strand (col 5, '+'/'-'/'.') and score (col 4);
+// each returned segment is paired with whether its BED strand column was '-', since
+// `PathSegment` itself carries no orientation field in this tree
+//
+// `regions`, when given, restricts the returned segments to those intersecting one of the
+// supplied (sequence name, start, end) intervals, per `overlap_mode` (HTSeq-style: `Union`
+// selects on any overlap, `IntersectionStrict` only when fully contained in one region,
+// `IntersectionNonempty` only when exactly one region overlaps at all, dropping ambiguous
+// multi-feature hits); pass `open_bed_region` the same list so the decompressed stream is
+// already seeked close to the first matching record before this function has to filter anything
 pub fn parse_bed_to_path_segments<R: Read>(
     data: &mut BufReader<R>,
     use_block_info: bool,
-) -> Vec<PathSegment> {
+    lenient: bool,
+    min_score: Option<f64>,
+    regions: Option<&[(String, usize, usize)]>,
+    overlap_mode: OverlapMode,
+) -> Result<(Vec<(PathSegment, bool)>, Vec<ParseError>), ParseError> {
     // based on https://en.wikipedia.org/wiki/BED_(file_format)
     let mut segments = Vec::new();
+    let mut issues = Vec::new();
 
     for (i, line) in data.lines().enumerate() {
+        let line_no = i + 1;
         let line = match line {
             Ok(l) => l,
             Err(e) => {
-                panic!("error reading line {}: {}", i + 1, e);
+                let err = ParseError {
+                    kind: ParseErrorKind::Bed,
+                    line: line_no,
+                    column: None,
+                    reason: format!("error reading line: {}", e),
+                };
+                if lenient {
+                    issues.push(err);
+                    continue;
+                }
+                return Err(err);
             }
         };
 
@@ -63,63 +286,397 @@ pub fn parse_bed_to_path_segments<R: Read>(
             continue;
         }
 
+        macro_rules! bail {
+            ($reason:expr) => {{
+                bail!(None, $reason)
+            }};
+            ($column:expr, $reason:expr) => {{
+                let err = ParseError {
+                    kind: ParseErrorKind::Bed,
+                    line: line_no,
+                    column: $column,
+                    reason: $reason,
+                };
+                if lenient {
+                    issues.push(err);
+                    continue;
+                }
+                return Err(err);
+            }};
+        }
+
         if fields.len() == 1 {
-            segments.push(PathSegment::from_str(path_name));
+            if region_overlaps(path_name, 0, usize::MAX, regions, overlap_mode) {
+                segments.push((PathSegment::from_str(path_name), false));
+            }
         } else if fields.len() >= 3 {
-            let start = usize::from_str(fields[1]).expect(&format!(
-                "error line {}: `{}` is not an usize",
-                i + 1,
-                fields[1]
-            ));
-            let end = usize::from_str(fields[2]).expect(&format!(
-                "error line {}: `{}` is not an usize",
-                i + 1,
-                fields[2]
-            ));
+            let start = match usize::from_str(fields[1]) {
+                Ok(s) => s,
+                Err(_) => bail!(Some(2), format!("`{}` is not an usize", fields[1])),
+            };
+            let end = match usize::from_str(fields[2]) {
+                Ok(e) => e,
+                Err(_) => bail!(Some(3), format!("`{}` is not an usize", fields[2])),
+            };
+            if start > end {
+                bail!(format!("start ({}) is greater than end ({})", start, end));
+            }
+
+            if let Some(cutoff) = min_score {
+                if let Some(score_field) = fields.get(4) {
+                    match f64::from_str(score_field) {
+                        Ok(score) if score < cutoff => continue,
+                        Ok(_) => {}
+                        Err(_) => bail!(Some(5), format!("`{}` is not a valid score", score_field)),
+                    }
+                }
+            }
+            let is_reverse = fields.get(5).map(|s| *s == "-").unwrap_or(false);
+
+            if !region_overlaps(path_name, start, end, regions, overlap_mode) {
+                continue;
+            }
 
             if use_block_info && fields.len() == 12 {
                 let block_count = fields[9].parse::<usize>().unwrap_or(0);
-                let block_sizes: Vec<usize> = fields[10]
+                let mut block_sizes: Vec<usize> = fields[10]
                     .split(',')
                     .filter_map(|s| usize::from_str(s.trim()).ok())
                     .collect();
-                let block_starts: Vec<usize> = fields[11]
+                let mut block_starts: Vec<usize> = fields[11]
                     .split(',')
                     .filter_map(|s| usize::from_str(s.trim()).ok())
                     .collect();
 
                 if block_count == block_sizes.len() && block_count == block_starts.len() {
+                    if is_reverse {
+                        // visit blocks in 5'->3' order along the minus strand
+                        block_sizes.reverse();
+                        block_starts.reverse();
+                    }
                     for (size, start_offset) in block_sizes.iter().zip(block_starts.iter()) {
                         let block_start = start + start_offset;
                         let block_end = block_start + size;
-                        segments.push(PathSegment::from_str_start_end(
-                            path_name,
-                            block_start,
-                            block_end,
+                        segments.push((
+                            PathSegment::from_str_start_end(path_name, block_start, block_end),
+                            is_reverse,
                         ));
                     }
                 } else {
-                    panic!(
-                        "error in block sizes/starts in line {}: counts do not match",
-                        i + 1
-                    );
+                    bail!("block_count does not match block_sizes/block_starts".to_string());
                 }
             } else {
-                segments.push(PathSegment::from_str_start_end(path_name, start, end));
+                segments.push((
+                    PathSegment::from_str_start_end(path_name, start, end),
+                    is_reverse,
+                ));
             }
         } else {
-            panic!(
-                "error in line {}: row must have either 1, 3, or 12 columns, but has 2",
-                i + 1
-            );
+            bail!(format!(
+                "row must have either 1, 3, or 12 columns, but has {}",
+                fields.len()
+            ));
+        }
+    }
+
+    log_parse_issues(&issues, "BED");
+    Ok((segments, issues))
+}
+
+// peeks the first non-blank, non-comment line of `data` without consuming it (same
+// `fill_buf`-based trick `open_compressed` uses to sniff compression) and decides whether it
+// looks like GFF3/GTF or plain BED, so a caller can then hand the untouched reader to whichever
+// of `parse_bed_to_path_segments` / `parse_gff_to_path_segments` matches. GFF3 files usually
+// declare themselves via a `##gff-version` pragma; lacking that (as GTF does), we fall back to
+// the fixed 9-tab-column shape the GFF/GTF family shares with numeric `start`/`end` columns,
+// which BED's 1/3/12-column rows never have.
+pub fn sniff_region_format<R: Read>(data: &mut BufReader<R>) -> std::io::Result<RegionFileFormat> {
+    let peek = String::from_utf8_lossy(data.fill_buf()?).into_owned();
+    for line in peek.lines() {
+        let line = line.trim_end_matches('\r');
+        if line.starts_with("##gff-version") {
+            return Ok(RegionFileFormat::Gff);
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        return Ok(
+            if fields.len() == 9
+                && usize::from_str(fields[3]).is_ok()
+                && usize::from_str(fields[4]).is_ok()
+            {
+                RegionFileFormat::Gff
+            } else {
+                RegionFileFormat::Bed
+            },
+        );
+    }
+    Ok(RegionFileFormat::Bed)
+}
+
+// GFF3 (https://github.com/The-Sequence-Ontology/Specifications/blob/master/gff3.md) and GTF
+// share the same 9 tab-separated columns: seqid, source, type, start, end, score, strand,
+// phase, attributes. `start`/`end` are 1-based inclusive in both formats, so they're converted
+// to BED-style 0-based half-open here (`start - 1, end`) before becoming a `PathSegment`, the
+// same coordinate convention `parse_bed_to_path_segments` produces.
+//
+// `feature_type`, when given, keeps only rows whose column 3 matches it exactly (e.g. `"exon"`
+// or `"CDS"`), mirroring how `use_block_info` expands a BED row into its sub-blocks -- both are
+// ways of narrowing a whole-record annotation down to the sub-features a caller actually wants
+// counted. `##`/`#` pragma and comment lines are skipped, as are blank lines.
+pub fn parse_gff_to_path_segments<R: Read>(
+    data: &mut BufReader<R>,
+    feature_type: Option<&str>,
+    lenient: bool,
+    min_score: Option<f64>,
+    regions: Option<&[(String, usize, usize)]>,
+    overlap_mode: OverlapMode,
+) -> Result<(Vec<(PathSegment, bool)>, Vec<ParseError>), ParseError> {
+    let mut segments = Vec::new();
+    let mut issues = Vec::new();
+
+    for (i, line) in data.lines().enumerate() {
+        let line_no = i + 1;
+        let line = match line {
+            Ok(l) => l,
+            Err(e) => {
+                let err = ParseError {
+                    kind: ParseErrorKind::Gff,
+                    line: line_no,
+                    column: None,
+                    reason: format!("error reading line: {}", e),
+                };
+                if lenient {
+                    issues.push(err);
+                    continue;
+                }
+                return Err(err);
+            }
+        };
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split('\t').collect();
+
+        macro_rules! bail {
+            ($reason:expr) => {{
+                bail!(None, $reason)
+            }};
+            ($column:expr, $reason:expr) => {{
+                let err = ParseError {
+                    kind: ParseErrorKind::Gff,
+                    line: line_no,
+                    column: $column,
+                    reason: $reason,
+                };
+                if lenient {
+                    issues.push(err);
+                    continue;
+                }
+                return Err(err);
+            }};
+        }
+
+        if fields.len() != 9 {
+            bail!(format!(
+                "row must have 9 columns, but has {}",
+                fields.len()
+            ));
+        }
+
+        let seqid = fields[0];
+
+        if let Some(wanted) = feature_type {
+            if fields[2] != wanted {
+                continue;
+            }
+        }
+
+        let start = match usize::from_str(fields[3]) {
+            Ok(s) if s >= 1 => s - 1,
+            Ok(_) => bail!(Some(4), "start must be >= 1 (GFF coordinates are 1-based)".to_string()),
+            Err(_) => bail!(Some(4), format!("`{}` is not an usize", fields[3])),
+        };
+        let end = match usize::from_str(fields[4]) {
+            Ok(e) => e,
+            Err(_) => bail!(Some(5), format!("`{}` is not an usize", fields[4])),
+        };
+        if start > end {
+            bail!(format!("start ({}) is greater than end ({})", start, end));
+        }
+
+        if let Some(cutoff) = min_score {
+            match fields[5] {
+                "." => {}
+                score_field => match f64::from_str(score_field) {
+                    Ok(score) if score < cutoff => continue,
+                    Ok(_) => {}
+                    Err(_) => bail!(Some(6), format!("`{}` is not a valid score", score_field)),
+                },
+            }
+        }
+        let is_reverse = fields[6] == "-";
+
+        if !region_overlaps(seqid, start, end, regions, overlap_mode) {
+            continue;
         }
+
+        segments.push((PathSegment::from_str_start_end(seqid, start, end), is_reverse));
     }
 
-    segments
+    log_parse_issues(&issues, "GFF");
+    Ok((segments, issues))
+}
+
+// a parsed tabix (.tbi) index, as produced by `tabix -p bed file.bed.gz`; see
+// https://samtools.github.io/hts-specs/tabix.pdf for the binary layout. We only keep the
+// sequence-name dictionary plus, per sequence, the smallest chunk-start virtual file offset
+// found in its linear index -- enough to seek close to a region without walking the full
+// bin tree, since BED records within a bgzip block are still scanned (and filtered) linearly
+// by `parse_bed_to_path_segments` once we land there
+pub struct TabixIndex {
+    seq_offset: HashMap<String, u64>,
 }
 
-pub fn parse_groups<R: Read>(data: &mut BufReader<R>) -> Result<Vec<(PathSegment, String)>, Error> {
+impl TabixIndex {
+    pub fn read<R: Read>(data: &mut R) -> Result<Self, Error> {
+        let mut magic = [0u8; 4];
+        data.read_exact(&mut magic)?;
+        if &magic != b"TBI\x01" {
+            return Err(Error::new(ErrorKind::InvalidData, "not a tabix index"));
+        }
+
+        let n_ref = read_i32(data)?;
+        let _format = read_i32(data)?;
+        let _col_seq = read_i32(data)?;
+        let _col_beg = read_i32(data)?;
+        let _col_end = read_i32(data)?;
+        let _meta = read_i32(data)?;
+        let _skip = read_i32(data)?;
+        let l_nm = read_i32(data)? as usize;
+        let mut names_buf = vec![0u8; l_nm];
+        data.read_exact(&mut names_buf)?;
+        let seq_names: Vec<String> = names_buf
+            .split(|&b| b == 0)
+            .filter(|s| !s.is_empty())
+            .map(|s| String::from_utf8_lossy(s).into_owned())
+            .collect();
+
+        let mut seq_offset = HashMap::new();
+        for i in 0..n_ref {
+            let n_bin = read_i32(data)?;
+            for _ in 0..n_bin {
+                let _bin = read_u32(data)?;
+                let n_chunk = read_i32(data)?;
+                for _ in 0..n_chunk {
+                    let _cnk_beg = read_u64(data)?;
+                    let _cnk_end = read_u64(data)?;
+                }
+            }
+            let n_intv = read_i32(data)?;
+            let mut min_offset = None;
+            for _ in 0..n_intv {
+                let ioff = read_u64(data)?;
+                if ioff > 0 && min_offset.is_none() {
+                    min_offset = Some(ioff);
+                }
+            }
+            if let (Some(name), Some(off)) = (seq_names.get(i as usize), min_offset) {
+                seq_offset.insert(name.clone(), off);
+            }
+        }
+
+        Ok(Self { seq_offset })
+    }
+
+    pub fn load(path: &str) -> Result<Self, Error> {
+        let mut reader = open_compressed(path)?;
+        Self::read(&mut reader)
+    }
+
+    // smallest virtual offset among all requested sequences that are actually indexed; falls
+    // back to the start of the file if none of the requested sequences are present, so the
+    // caller still gets a (slower, but correct) full scan
+    fn seek_offset(&self, regions: &[(String, usize, usize)]) -> u64 {
+        regions
+            .iter()
+            .filter_map(|(name, _, _)| self.seq_offset.get(name))
+            .copied()
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+fn read_i32<R: Read>(data: &mut R) -> Result<i32, Error> {
+    let mut buf = [0u8; 4];
+    data.read_exact(&mut buf)?;
+    Ok(i32::from_le_bytes(buf))
+}
+
+fn read_u32<R: Read>(data: &mut R) -> Result<u32, Error> {
+    let mut buf = [0u8; 4];
+    data.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(data: &mut R) -> Result<u64, Error> {
+    let mut buf = [0u8; 8];
+    data.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+// open a BGZF-compressed BED (`.bed.gz`) for region-restricted parsing: detect the BGZF magic
+// (a gzip member whose FEXTRA subfield is `BC`, RFC1952 + the BAM/tabix spec), and when a
+// companion `.tbi` index and a non-empty `regions` list are supplied, seek the raw file to the
+// virtual file offset of the first bgzip block that can contain a matching record instead of
+// decompressing from byte 0. The returned reader still yields a contiguous decompressed BED
+// stream, with any preceding records in that block skipped by byte offset; `regions` should
+// also be passed to `parse_bed_to_path_segments` so non-overlapping records further into the
+// stream are filtered out rather than returned
+pub fn open_bed_region(
+    path: &str,
+    tbi_path: Option<&str>,
+    regions: &[(String, usize, usize)],
+) -> Result<BufReader<Box<dyn Read>>, Error> {
+    let mut peek = BufReader::new(File::open(path)?);
+    let magic = peek.fill_buf()?.to_vec();
+    if !magic.starts_with(&[0x1f, 0x8b]) {
+        // not gzip/BGZF at all -- fall back to the regular compression auto-detection
+        return open_compressed(path);
+    }
+    let is_bgzf = magic.len() > 17 && magic[12] == b'B' && magic[13] == b'C';
+    if !is_bgzf || tbi_path.is_none() || regions.is_empty() {
+        return open_compressed(path);
+    }
+
+    let index = TabixIndex::load(tbi_path.unwrap())?;
+    let voffset = index.seek_offset(regions);
+    let coffset = voffset >> 16;
+    let uoffset = (voffset & 0xffff) as usize;
+
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(coffset))?;
+    let mut decoder: Box<dyn Read> = Box::new(MultiGzDecoder::new(file));
+    if uoffset > 0 {
+        let mut skip = vec![0u8; uoffset];
+        decoder.read_exact(&mut skip)?;
+    }
+    Ok(BufReader::new(decoder))
+}
+
+// two-column (path name, group name) table; like `parse_bed_to_path_segments`, this is generic
+// over `R: Read` and doesn't own file-opening itself, so callers passing a path-backed grouping
+// file should build `data` via `open_compressed` to get the same transparent gzip/BGZF/zstd/
+// bzip2/xz handling that `bufreader_from_compressed_gfa` already gives the main GFA stream
+pub fn parse_groups<R: Read>(
+    data: &mut BufReader<R>,
+    lenient: bool,
+) -> Result<(Vec<(PathSegment, String)>, Vec<ParseError>), ParseError> {
     let mut res: Vec<(PathSegment, String)> = Vec::new();
+    let mut issues = Vec::new();
 
     let mut i = 1;
     let mut buf = vec![];
@@ -130,14 +687,41 @@ pub fn parse_groups<R: Read>(data: &mut BufReader<R>) -> Result<Vec<(PathSegment
                 buf.pop();
             }
         }
-        let line = String::from_utf8(buf.clone())
-            .expect(&format!("error in line {}: some character is not UTF-8", i));
+        let line = match String::from_utf8(buf.clone()) {
+            Ok(l) => l,
+            Err(_) => {
+                let err = ParseError {
+                    kind: ParseErrorKind::Groups,
+                    line: i,
+                    column: None,
+                    reason: "some character is not UTF-8".to_string(),
+                };
+                if lenient {
+                    issues.push(err);
+                    i += 1;
+                    buf.clear();
+                    continue;
+                }
+                return Err(err);
+            }
+        };
         let columns: Vec<&str> = line.split('\t').collect();
 
         if columns.len() != 2 {
-            let msg = format!("error in line {}: table must have exactly two columns", i);
-            log::error!("{}", &msg);
-            return Err(Error::new(ErrorKind::InvalidData, msg));
+            let err = ParseError {
+                kind: ParseErrorKind::Groups,
+                line: i,
+                column: None,
+                reason: "table must have exactly two columns".to_string(),
+            };
+            if lenient {
+                issues.push(err);
+                i += 1;
+                buf.clear();
+                continue;
+            }
+            log::error!("{}", &err);
+            return Err(err);
         }
 
         let path_seg = PathSegment::from_str(columns[0]);
@@ -147,9 +731,11 @@ pub fn parse_groups<R: Read>(data: &mut BufReader<R>) -> Result<Vec<(PathSegment
         buf.clear();
     }
 
-    Ok(res)
+    log_parse_issues(&issues, "group");
+    Ok((res, issues))
 }
 
+#[allow(dead_code)]
 pub fn parse_tsv<R: Read>(
     data: &mut BufReader<R>,
 ) -> Result<(Vec<Vec<u8>>, Vec<Vec<Vec<u8>>>), Error> {
@@ -206,80 +792,105 @@ pub fn parse_tsv<R: Read>(
     Ok((comments, table))
 }
 
-fn transpose_table(table: &Vec<Vec<Vec<u8>>>) -> Vec<Vec<&[u8]>> {
-    let n = table.first().unwrap_or(&Vec::new()).len();
-
-    let mut res = vec![vec![&table[0][0][..]; table.len()]; n];
-
-    for j in 0..n {
-        for i in 0..table.len() {
-            res[j][i] = &table[i][j][..];
-        }
-    }
-
-    res
-}
-
-fn parse_column(col: &Vec<&[u8]>, offset: usize) -> Result<Vec<usize>, Error> {
-    let skip_lines = 2;
-    let mut res = vec![0; col.len() - skip_lines];
-
-    for (i, e) in col[skip_lines..].iter().enumerate() {
-        if let Ok(val) = usize::from_str(str::from_utf8(e).unwrap()) {
-            res[i] = val;
-        } else {
-            let msg = format!(
-                "error in line {}: value must be integer, but is '{}'",
-                i + 3 + offset,
-                &str::from_utf8(e).unwrap()
-            );
-            log::error!("{}", &msg);
-            Err(Error::new(ErrorKind::InvalidData, msg))?
-        }
-    }
-
-    Ok(res)
-}
-
+// streams the table line by line instead of buffering it into a row-major `Vec<Vec<Vec<u8>>>`
+// and then transposing it, so genome-scale histograms never hold the full table and its
+// transpose in memory at once; each hist column's `Vec<usize>` is grown lazily to the
+// largest index seen so far. Preserves the comment-collection and "not generated by
+// panacus" validation behavior of the buffered implementation.
 pub fn parse_hists<R: Read>(
     data: &mut BufReader<R>,
 ) -> Result<(Vec<(CountType, Vec<usize>)>, Vec<Vec<u8>>), Error> {
-    log::info!("loading coverage histogram from");
-    let (comments, raw_table) = parse_tsv(data)?;
-    let raw_table = transpose_table(&raw_table);
-    if raw_table.len() < 4 && b"panacus" != raw_table[0][0] {
-        let msg = format!(
-            "error in line {}: table appears not to be generated by panacus",
-            comments.len()
-        );
-        log::error!("{}", &msg);
-        return Err(Error::new(ErrorKind::InvalidData, msg));
-    }
+    log::info!("streaming coverage histogram from table");
+    let mut comments: Vec<Vec<u8>> = Vec::new();
+    let mut kinds: Option<Vec<String>> = None;
+    let mut count_types: Option<Vec<CountType>> = None;
+    let mut hists: Vec<Vec<usize>> = Vec::new();
+    let mut data_row_no = 0usize;
+
+    for line in data.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let cols: Vec<&str> = line.split('\t').collect();
+        if cols[0].starts_with('#') {
+            comments.push(line.clone().into_bytes());
+            continue;
+        }
 
-    let mut res = Vec::new();
+        if kinds.is_none() {
+            if cols.first() != Some(&"panacus") {
+                let msg = format!(
+                    "error in line {}: table appears not to be generated by panacus",
+                    comments.len()
+                );
+                log::error!("{}", &msg);
+                return Err(Error::new(ErrorKind::InvalidData, msg));
+            }
+            kinds = Some(cols[1..].iter().map(|s| s.to_string()).collect());
+            hists = vec![Vec::new(); cols.len() - 1];
+            continue;
+        }
 
-    let index = parse_column(&raw_table[0], comments.len())?;
-    let mx = index.iter().max().unwrap();
-    for col in &raw_table[1..] {
-        if b"hist" == &col[0] {
-            let count = CountType::from_str(str::from_utf8(col[1]).unwrap()).map_err(|_| {
+        if count_types.is_none() {
+            let types: Result<Vec<CountType>, Error> = cols[1..]
+                .iter()
+                .map(|c| {
+                    CountType::from_str(c).map_err(|_| {
+                        let msg = format!(
+                            "error in line {}: expected count type declaration, but got '{}'",
+                            2 + comments.len(),
+                            c
+                        );
+                        log::error!("{}", &msg);
+                        Error::new(ErrorKind::InvalidData, msg)
+                    })
+                })
+                .collect();
+            count_types = Some(types?);
+            continue;
+        }
+
+        let index = match usize::from_str(cols[0]) {
+            Ok(v) => v,
+            Err(_) => {
+                let msg = format!(
+                    "error in line {}: value must be integer, but is '{}'",
+                    data_row_no + 3 + comments.len(),
+                    cols[0]
+                );
+                log::error!("{}", &msg);
+                return Err(Error::new(ErrorKind::InvalidData, msg));
+            }
+        };
+        for (j, c) in cols[1..].iter().enumerate().take(hists.len()) {
+            let val = usize::from_str(c).map_err(|_| {
                 let msg = format!(
-                    "error in line {}: expected count type declaration, but got '{}'",
-                    2 + comments.len(),
-                    &str::from_utf8(col[1]).unwrap()
+                    "error in line {}: value must be integer, but is '{}'",
+                    data_row_no + 3 + comments.len(),
+                    c
                 );
                 log::error!("{}", &msg);
                 Error::new(ErrorKind::InvalidData, msg)
             })?;
-            let mut cov = vec![0; mx + 1];
-            for (i, c) in index.iter().zip(parse_column(col, comments.len())?) {
-                cov[*i] = c;
+            if index >= hists[j].len() {
+                hists[j].resize(index + 1, 0);
             }
-
-            res.push((count, cov));
+            hists[j][index] = val;
         }
+        data_row_no += 1;
     }
 
+    let kinds = kinds.unwrap_or_default();
+    let count_types = count_types.unwrap_or_default();
+    let res: Vec<(CountType, Vec<usize>)> = kinds
+        .into_iter()
+        .zip(count_types)
+        .zip(hists)
+        .filter(|((kind, _), _)| kind == "hist")
+        .map(|((_, count), cov)| (count, cov))
+        .collect();
+
     if res.is_empty() {
         let msg = "table does not contain hist columns";
         log::error!("{}", msg);
@@ -480,6 +1091,151 @@ pub fn write_table(headers: &Vec<Vec<String>>, columns: &Vec<Vec<f64>>) -> Resul
     Ok(res)
 }
 
+// same header/column structure as write_table, but as a structured JSON document instead
+// of a tsv block: a typed schema per column (the four header rows tag each column with its
+// analysis kind, count type, coverage and quorum threshold) and the numeric matrix kept as
+// real numbers rather than floor()'d strings, so downstream tooling can load it without
+// re-parsing tsv.
+pub fn write_table_json(
+    headers: &Vec<Vec<String>>,
+    columns: &Vec<Vec<f64>>,
+) -> anyhow::Result<String> {
+    let labels = headers.first().cloned().unwrap_or_default();
+    let schema: Vec<serde_json::Value> = headers
+        .iter()
+        .skip(1)
+        .map(|col| {
+            let mut obj = serde_json::Map::new();
+            for (label, value) in labels.iter().zip(col.iter()) {
+                obj.insert(label.clone(), serde_json::Value::String(value.clone()));
+            }
+            serde_json::Value::Object(obj)
+        })
+        .collect();
+    let doc = serde_json::json!({
+        "metadata": {
+            "command": std::env::args().collect::<Vec<String>>().join(" "),
+            "version": option_env!("GIT_HASH").unwrap_or(env!("CARGO_PKG_VERSION")),
+        },
+        "columns": schema,
+        "data": columns,
+    });
+    Ok(serde_json::to_string_pretty(&doc)?)
+}
+
+// canonicalize a JSON value by sorting object keys recursively, then hash the resulting byte
+// string with SHA-256; used to fingerprint a JSON result payload independent of key order or
+// whitespace, so the same analysis re-serialized on another machine still digests identically.
+// A self-contained SHA-256 is used here (rather than pulling in a hashing crate) since the
+// canonicalization step -- not the choice of hash primitive -- is what makes the digest
+// reproducible across machines/serializers.
+fn canonicalize_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<(&String, &serde_json::Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let body = entries
+                .into_iter()
+                .map(|(k, v)| format!("{}:{}", serde_json::to_string(k).unwrap(), canonicalize_json(v)))
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("{{{}}}", body)
+        }
+        serde_json::Value::Array(items) => {
+            let body = items
+                .iter()
+                .map(canonicalize_json)
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("[{}]", body)
+        }
+        other => other.to_string(),
+    }
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+        0xc67178f2,
+    ];
+    let mut h: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    let mut msg = data.to_vec();
+    let bit_len = (data.len() as u64) * 8;
+    msg.push(0x80);
+    while msg.len() % 64 != 56 {
+        msg.push(0);
+    }
+    msg.extend_from_slice(&bit_len.to_be_bytes());
+
+    for chunk in msg.chunks(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ ((!e) & g);
+            let temp1 = hh
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+
+        h[0] = h[0].wrapping_add(a);
+        h[1] = h[1].wrapping_add(b);
+        h[2] = h[2].wrapping_add(c);
+        h[3] = h[3].wrapping_add(d);
+        h[4] = h[4].wrapping_add(e);
+        h[5] = h[5].wrapping_add(f);
+        h[6] = h[6].wrapping_add(g);
+        h[7] = h[7].wrapping_add(hh);
+    }
+
+    h.iter().map(|word| format!("{:08x}", word)).collect()
+}
+
+// digest a JSON result payload for `render --verify`: canonicalizes (so key order is
+// irrelevant) and hashes with SHA-256, matching the `"_digest"` field panacus stores alongside
+// the payload when writing a JSON result.
+pub fn digest_json_payload(value: &serde_json::Value) -> String {
+    sha256_hex(canonicalize_json(value).as_bytes())
+}
+
 pub fn write_ordered_table(
     headers: &Vec<Vec<String>>,
     columns: &Vec<Vec<f64>>,
@@ -535,6 +1291,42 @@ pub fn write_ordered_table(
 //     }
 //     write_table(&header_cols, &output_columns, out)
 // }
+// renders a labeled series of values as an ASCII/Unicode bar chart for stdout, scaling each
+// bar to the terminal width (or 80 columns if it can't be determined) and using partial block
+// characters so the relative magnitude within a row is still visible at low resolution
+pub fn render_term_bar_chart(labels: &[String], values: &[f64]) -> String {
+    const BLOCKS: [char; 9] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+    let width: usize = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(80);
+    let label_width = labels.iter().map(|l| l.len()).max().unwrap_or(0);
+    let max = values.iter().cloned().fold(0.0_f64, f64::max);
+    let bar_width = width.saturating_sub(label_width + "  | ".len() + 12).max(1);
+
+    let mut res = format!("max = {:.2}\n", max);
+    for (label, value) in labels.iter().zip(values) {
+        let frac = if max > 0.0 { value / max } else { 0.0 };
+        let filled = frac * bar_width as f64;
+        let full_blocks = filled.floor() as usize;
+        let remainder = ((filled - filled.floor()) * (BLOCKS.len() - 1) as f64).round() as usize;
+
+        let mut bar = BLOCKS[BLOCKS.len() - 1].to_string().repeat(full_blocks);
+        if remainder > 0 && full_blocks < bar_width {
+            bar.push(BLOCKS[remainder]);
+        }
+        res.push_str(&format!(
+            "{:>label_width$} | {} {:.2}\n",
+            label,
+            bar,
+            value,
+            label_width = label_width
+        ));
+    }
+    res
+}
+
 pub fn write_metadata_comments() -> anyhow::Result<String> {
     let mut res = format!(
         "# {}\n",
@@ -5,11 +5,18 @@ mod commands;
 pub mod graph_broker;
 mod html_report;
 mod io;
+mod order_file;
+mod progress;
 mod util;
+#[cfg(target_arch = "wasm32")]
+mod wasm;
 
 use env_logger::Builder;
 use log::LevelFilter;
-use std::{fmt::Debug, io::Write};
+use std::{
+    fmt::Debug,
+    io::{Read, Write},
+};
 use thiserror::Error;
 
 use analyses::Analysis;
@@ -17,9 +24,8 @@ use analysis_parameter::{AnalysisRun, Task};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use graph_broker::{GraphBroker, GraphState};
 use html_report::AnalysisSection;
-
-use std::fs::File;
-use std::io::BufReader;
+use progress::Progress;
+use rayon::prelude::*;
 
 #[macro_export]
 macro_rules! clap_enum_variants {
@@ -65,6 +71,24 @@ fn set_number_of_threads(params: &ArgMatches) {
     );
 }
 
+/// Reports which global allocator is active, next to the "running panacus on N threads"
+/// message emitted by `set_number_of_threads`, and surfaces the `--max-memory` hint if one was
+/// given. Large GFA pangenomes are allocation-fragmentation-bound at peak memory, and a
+/// `#[global_allocator]` swap to something like mimalloc or jemalloc (the way alevin-fry does
+/// it) behind a cargo feature would be the real fix -- but that requires a `[dependencies]`
+/// entry and a `[features]` table, and there is no `Cargo.toml` anywhere in this tree to add
+/// them to, so this only reports the (always system-default, for now) allocator and hint
+/// rather than silently pretending the swap happened.
+fn report_allocator(args: &ArgMatches) {
+    if let Some(max_memory) = args.get_one::<u64>("max_memory") {
+        log::info!(
+            "using system default allocator (no mimalloc/jemalloc feature compiled in); --max-memory {max_memory}GB noted but not yet enforced"
+        );
+    } else {
+        log::info!("using system default allocator (no mimalloc/jemalloc feature compiled in)");
+    }
+}
+
 fn set_verbosity(args: &ArgMatches) {
     if args.get_flag("verbose") {
         Builder::new().filter_level(LevelFilter::Debug).init();
@@ -81,6 +105,7 @@ pub fn run_cli() -> Result<(), anyhow::Error> {
     let args = Command::new("panacus")
         .subcommand(commands::render::get_subcommand())
         .subcommand(commands::report::get_subcommand())
+        .subcommand(commands::inspect::get_subcommand())
         .subcommand(commands::hist::get_subcommand())
         .subcommand(commands::growth::get_subcommand())
         .subcommand(commands::histgrowth::get_subcommand())
@@ -89,6 +114,9 @@ pub fn run_cli() -> Result<(), anyhow::Error> {
         .subcommand(commands::table::get_subcommand())
         .subcommand(commands::node_distribution::get_subcommand())
         .subcommand(commands::similarity::get_subcommand())
+        .subcommand(commands::cycles::get_subcommand())
+        .subcommand(commands::export::get_subcommand())
+        .subcommand(commands::bench::get_subcommand())
         .subcommand_required(true)
         .arg(
             Arg::new("threads")
@@ -108,35 +136,221 @@ pub fn run_cli() -> Result<(), anyhow::Error> {
                 .global(true)
                 .help("Set the number of threads used (default: use all threads)"),
         )
+        .arg(
+            Arg::new("max_memory")
+                .long("max-memory")
+                .value_name("GB")
+                .global(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Hint the target peak memory (in GB) to a fragmentation-resistant global allocator on large-graph runs; currently only reported in the startup log, see the doc comment on report_allocator for why"),
+        )
+        .arg(
+            Arg::new("cache_dir")
+                .long("cache-dir")
+                .value_name("DIR")
+                .global(true)
+                .conflicts_with("no_cache")
+                .help("Not yet implemented: intended as the directory for a `.pac` sidecar cache of the parsed graph state (see graph_broker::cache), but reading/writing that cache isn't wired into graph loading in this build -- the value is only compared between consecutive graph-state changes within one run, where a different value forces a reparse; it does not persist anything to DIR"),
+        )
+        .arg(
+            Arg::new("no_cache")
+                .long("no-cache")
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .conflicts_with("cache_dir")
+                .help("No effect while --cache-dir doesn't persist anything (see --cache-dir); reserved so existing invocations keep working once it does"),
+        )
+        .arg(
+            Arg::new("progress")
+                .long("progress")
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .conflicts_with("quiet")
+                .help("Report pipeline progress (which task/analysis is running, a parse summary per graph) as a throttled bar on stderr; always off under --json/--tsv"),
+        )
+        .arg(
+            Arg::new("quiet")
+                .long("quiet")
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .conflicts_with("progress")
+                .help("Suppress progress reporting even if --progress is set elsewhere (default: progress reporting is already off unless --progress is given)"),
+        )
         .get_matches();
 
     set_verbosity(&args);
     set_number_of_threads(&args);
+    report_allocator(&args);
+    let cache_dir = if args.get_flag("no_cache") {
+        None
+    } else {
+        args.get_one::<String>("cache_dir").cloned()
+    };
+    let progress_requested = args.get_flag("progress") && !args.get_flag("quiet");
 
     let mut instructions: Vec<AnalysisRun> = Vec::new();
     let mut shall_write_html = false;
     let mut dry_run = false;
     let mut json = false;
+    let mut tsv = false;
+    let mut report_format: Option<String> = None;
+    let mut custom_template: Option<String> = None;
+    let mut theme = "auto".to_string();
 
     if let Some(args) = args.subcommand_matches("render") {
+        let json_files: Vec<String> = args
+            .get_many::<String>("json_files")
+            .map(|v| v.cloned().collect())
+            .unwrap_or_default();
+        let inline_json = args.get_one::<String>("json").cloned();
+
+        if args.get_flag("verify") {
+            let mut all_ok = true;
+            if let Some(contents) = &inline_json {
+                let ok = AnalysisSection::verify_json_str(contents)?;
+                all_ok &= ok;
+                println!("<inline>: {}", if ok { "OK" } else { "FAILED" });
+            }
+            for file_path in &json_files {
+                let ok = if file_path == "-" {
+                    let mut contents = String::new();
+                    std::io::stdin().read_to_string(&mut contents)?;
+                    AnalysisSection::verify_json_str(&contents)?
+                } else {
+                    AnalysisSection::verify_json(file_path)?
+                };
+                all_ok &= ok;
+                println!("{}: {}", file_path, if ok { "OK" } else { "FAILED" });
+            }
+            if !all_ok {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+
+        let compare = args.get_flag("compare");
+        let labels: Vec<String> = args
+            .get_many::<String>("label")
+            .map(|v| v.cloned().collect())
+            .unwrap_or_default();
+
+        let mut full_report = Vec::new();
+        let mut source_index = 0;
+        if let Some(contents) = &inline_json {
+            let (report, _digest) = AnalysisSection::load_json_str(contents)?;
+            full_report.extend(label_source(
+                report,
+                compare,
+                labels.get(source_index),
+                "<inline>",
+            ));
+            source_index += 1;
+        }
+        for file_path in &json_files {
+            let (report, _digest) = if file_path == "-" {
+                let mut contents = String::new();
+                std::io::stdin().read_to_string(&mut contents)?;
+                AnalysisSection::load_json_str(&contents)?
+            } else {
+                AnalysisSection::load_json(file_path)?
+            };
+            full_report.extend(label_source(
+                report,
+                compare,
+                labels.get(source_index),
+                file_path,
+            ));
+            source_index += 1;
+        }
+        if args.get_flag("tsv") {
+            writeln!(&mut out, "{}", AnalysisSection::generate_report_tsv(&full_report))?;
+            return Ok(());
+        }
+        match args.get_one::<String>("format").map(String::as_str) {
+            Some("svg") => {
+                writeln!(&mut out, "{}", AnalysisSection::generate_report_svg(&full_report))?;
+                return Ok(());
+            }
+            Some("png") => {
+                anyhow::bail!(
+                    "`--format png` is not yet implemented: no raster image encoder is wired up in this build"
+                );
+            }
+            _ => {}
+        }
+        let custom_template = args
+            .get_one::<String>("template")
+            .map(std::fs::read_to_string)
+            .transpose()?;
+        let theme = args.get_one::<String>("theme").cloned().unwrap_or_default();
+        let mut registry = handlebars::Handlebars::new();
+        let report_name = json_files.first().map(String::as_str).unwrap_or("<inline>");
+        let report_text = AnalysisSection::generate_report_themed(
+            full_report,
+            &mut registry,
+            report_name,
+            custom_template.as_deref(),
+            &theme,
+        )?;
+        writeln!(&mut out, "{report_text}")?;
+        return Ok(());
+    }
+
+    if let Some(args) = args.subcommand_matches("inspect") {
         let json_files: Vec<String> = args
             .get_many::<String>("json_files")
             .unwrap()
             .cloned()
             .collect();
-        let mut full_report = Vec::new();
+        let verbose = args.get_flag("verbose");
+        let as_json = args.get_one::<String>("format").map(String::as_str) == Some("json");
+
+        let mut by_file = Vec::new();
         for file_path in &json_files {
-            let file = File::open(file_path)?;
-            let reader = BufReader::new(file);
+            let (report, digest) = AnalysisSection::load_json(file_path)?;
+            by_file.push((file_path.clone(), digest, AnalysisSection::summarize(&report)));
+        }
 
-            // Read the JSON contents of the file as an instance of `User`.
-            let report: Vec<AnalysisSection> = serde_json::from_reader(reader)?;
-            full_report.extend(report);
+        if as_json {
+            let doc: Vec<_> = by_file
+                .iter()
+                .map(|(file_path, digest, summaries)| {
+                    serde_json::json!({
+                        "file": file_path,
+                        "digest": digest,
+                        "sections": summaries,
+                    })
+                })
+                .collect();
+            writeln!(&mut out, "{}", serde_json::to_string_pretty(&doc)?)?;
+        } else {
+            for (file_path, digest, summaries) in &by_file {
+                writeln!(&mut out, "{file_path}")?;
+                if let Some(digest) = digest {
+                    writeln!(&mut out, "  digest: {digest}")?;
+                }
+                for summary in summaries {
+                    write!(
+                        &mut out,
+                        "  {} / {} / {}",
+                        summary.analysis, summary.run_name, summary.countable
+                    )?;
+                    if let Some(num_samples) = summary.num_samples {
+                        write!(&mut out, " ({num_samples} samples)")?;
+                    }
+                    writeln!(&mut out)?;
+                    if verbose {
+                        writeln!(&mut out, "    items: {}", summary.item_kinds.join(", "))?;
+                    }
+                }
+            }
         }
-        let mut registry = handlebars::Handlebars::new();
-        let report_text =
-            AnalysisSection::generate_report(full_report, &mut registry, &json_files[0])?;
-        writeln!(&mut out, "{report_text}")?;
+        return Ok(());
+    }
+
+    if let Some(result) = commands::bench::run(&args, &mut out) {
+        result?;
+        out.flush()?;
         return Ok(());
     }
 
@@ -146,6 +360,16 @@ pub fn run_cli() -> Result<(), anyhow::Error> {
         if let Some(report_matches) = args.subcommand_matches("report") {
             dry_run = report_matches.get_flag("dry_run");
             json = report_matches.get_flag("json");
+            tsv = report_matches.get_flag("tsv");
+            report_format = report_matches.get_one::<String>("format").cloned();
+            custom_template = report_matches
+                .get_one::<String>("template")
+                .map(std::fs::read_to_string)
+                .transpose()?;
+            theme = report_matches
+                .get_one::<String>("theme")
+                .cloned()
+                .unwrap_or(theme);
         }
     }
     // if let Some(hist) = commands::hist::get_instructions(&args) {
@@ -172,13 +396,31 @@ pub fn run_cli() -> Result<(), anyhow::Error> {
     // if let Some(similarity) = commands::similarity::get_instructions(&args) {
     //     instructions.extend(similarity?);
     // }
+    // if let Some(cycles) = commands::cycles::get_instructions(&args) {
+    //     instructions.extend(cycles?);
+    // }
+    // if let Some(export) = commands::export::get_instructions(&args) {
+    //     instructions.extend(export?);
+    // }
 
     let instructions: Vec<Task> = get_tasks(instructions)?;
     log::info!("{:?}", instructions);
 
     // ride on!
     if !dry_run {
-        execute_pipeline(instructions, &mut out, shall_write_html, json)?;
+        let progress = Progress::new(progress_requested && !json && !tsv);
+        execute_pipeline(
+            instructions,
+            &mut out,
+            shall_write_html,
+            json,
+            tsv,
+            report_format.as_deref(),
+            custom_template.as_deref(),
+            &theme,
+            cache_dir.as_deref(),
+            &progress,
+        )?;
     } else {
         println!("{:#?}", instructions);
     }
@@ -194,6 +436,24 @@ pub enum ConfigParseError {
     NameNotFound { name: String },
 }
 
+/// Under `render --compare`, relabel a loaded JSON source's sections so it stays distinguishable
+/// from the others in the combined report: an explicit `--label` wins, otherwise fall back to
+/// `default_label` (the source's file path, or `-`/`<inline>` for piped/inline input). Without
+/// `--compare`, sections are passed through untouched and keep whatever run name they already
+/// carry.
+fn label_source(
+    sections: Vec<AnalysisSection>,
+    compare: bool,
+    label: Option<&String>,
+    default_label: &str,
+) -> Vec<AnalysisSection> {
+    if !compare {
+        return sections;
+    }
+    let label = label.map(String::as_str).unwrap_or(default_label);
+    AnalysisSection::relabel(sections, label)
+}
+
 fn get_tasks(instructions: Vec<AnalysisRun>) -> anyhow::Result<Vec<Task>> {
     let tasks = AnalysisRun::convert_to_tasks(instructions);
     Ok(tasks)
@@ -204,19 +464,51 @@ pub fn execute_pipeline<W: Write>(
     out: &mut std::io::BufWriter<W>,
     shall_write_html: bool,
     json: bool,
+    tsv: bool,
+    report_format: Option<&str>,
+    custom_template: Option<&str>,
+    theme: &str,
+    cache_dir: Option<&str>,
+    progress: &Progress,
 ) -> anyhow::Result<()> {
     if instructions.is_empty() {
         log::warn!("No instructions supplied");
         return Ok(());
     }
+    let total_tasks = instructions.len() as u64;
     let mut report = Vec::new();
     let mut gb = GraphBroker::new();
-    for index in 0..instructions.len() {
-        match &mut instructions[index] {
-            Task::Analysis(analysis) => {
-                log::info!("Executing Analysis: {}", analysis.get_type());
-                report.extend(analysis.generate_report_section(Some(&gb))?);
+    let mut index = 0;
+    while index < instructions.len() {
+        progress.bar("pipeline tasks", index as u64, total_tasks);
+        if matches!(instructions[index], Task::Analysis(_)) {
+            // A run of `Task::Analysis` entries between two mutating tasks only ever reads
+            // `gb` (see the `Send + Sync` doc comment on `Analysis`), so the whole run can be
+            // handed to rayon's global pool at once instead of one at a time.
+            let start = index;
+            while index < instructions.len() && matches!(instructions[index], Task::Analysis(_)) {
+                index += 1;
+            }
+            let segment = &mut instructions[start..index];
+            log::info!("Executing {} analyses in parallel", segment.len());
+            progress.stage(&format!("running {} analyses in parallel", segment.len()));
+            let sections: Vec<anyhow::Result<Vec<AnalysisSection>>> = segment
+                .par_iter_mut()
+                .map(|task| match task {
+                    Task::Analysis(analysis) => {
+                        log::info!("Executing Analysis: {}", analysis.get_type());
+                        analysis.generate_report_section(Some(&gb), Some(progress))
+                    }
+                    _ => unreachable!("segment contains only Task::Analysis entries"),
+                })
+                .collect();
+            for section in sections {
+                report.extend(section?);
             }
+            continue;
+        }
+        match &mut instructions[index] {
+            Task::Analysis(_) => unreachable!("handled by the batch above"),
             Task::GraphStateChange {
                 graph,
                 subset,
@@ -226,34 +518,59 @@ pub fn execute_pipeline<W: Write>(
                 reqs,
             } => {
                 log::info!("Executing graph change: {:?}", reqs);
+                progress.stage(&format!("parsing graph {graph}"));
                 gb.change_graph_state(
                     GraphState {
                         graph: graph.to_string(),
                         subset: subset.to_string(),
                         exclude: exclude.to_string(),
                         grouping: grouping.clone(),
+                        cache_dir: cache_dir.map(str::to_string),
                     },
-                    &reqs,
+                    &*reqs,
                     *nice,
+                    Some(progress),
                 )?;
+                progress.stage(&format!(
+                    "parsed graph {graph}: {} nodes, {} edges",
+                    gb.get_node_count(),
+                    gb.get_edge_count()
+                ));
             }
             Task::OrderChange(order) => {
                 log::info!("Executing order change: {:?}", order);
-                unimplemented!("Order Change is not yet implemented");
+                if let Some(path) = order {
+                    let entries = order_file::parse_order_entries(path)?;
+                    order_file::validate_permutation(path, &entries, &gb.group_names())?;
+                    gb.change_order(path)?;
+                }
             }
             Task::AbacusByGroupCSCChange => {
                 log::info!("Executing AbacusByGroup CSC change");
                 unimplemented!("CSC Change is not yet implemented");
             }
         }
+        index += 1;
     }
+    progress.bar("pipeline tasks", total_tasks, total_tasks);
     if json {
-        let json_text = serde_json::to_string_pretty(&report)?;
+        let json_text = AnalysisSection::to_json_with_digest(&report)?;
         writeln!(out, "{json_text}")?;
+    } else if tsv {
+        writeln!(out, "{}", AnalysisSection::generate_report_tsv(&report))?;
+    } else if report_format == Some("summary") {
+        write!(out, "{}", AnalysisSection::generate_report_summary(&report))?;
+    } else if report_format == Some("flat") {
+        write!(out, "{}", AnalysisSection::generate_report_flat(&report))?;
     } else if shall_write_html {
         let mut registry = handlebars::Handlebars::new();
-        let report =
-            AnalysisSection::generate_report(report, &mut registry, "<Placeholder Filename>")?;
+        let report = AnalysisSection::generate_report_themed(
+            report,
+            &mut registry,
+            "<Placeholder Filename>",
+            custom_template,
+            theme,
+        )?;
         writeln!(out, "{report}")?;
     } else {
         if let Task::Analysis(analysis) = instructions.last_mut().unwrap() {
@@ -264,6 +581,97 @@ pub fn execute_pipeline<W: Write>(
     Ok(())
 }
 
+/// Per-[`Task`] timing/memory sample produced by [`execute_pipeline_benchmarked`] for
+/// `commands::bench`. `peak_bytes` is the whole-process RSS high-water mark as of right after
+/// the task finished (see `read_peak_rss_bytes`), not an isolated per-task allocation delta --
+/// RSS is inherently a whole-process measurement, so it can only ever grow across a run.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TaskBenchResult {
+    pub task_type: String,
+    pub seconds: f64,
+    pub peak_bytes: u64,
+}
+
+/// Reads the kernel-tracked peak resident set size (`VmHWM`) of the current process from
+/// `/proc/self/status`. Returns 0 if the file or field can't be read (e.g. non-Linux), so a
+/// bench run never fails just because memory sampling isn't supported on the host.
+fn read_peak_rss_bytes() -> u64 {
+    std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| {
+            status.lines().find_map(|line| {
+                line.strip_prefix("VmHWM:").map(|rest| {
+                    rest.trim_end_matches("kB").trim().parse::<u64>().unwrap_or(0) * 1024
+                })
+            })
+        })
+        .unwrap_or(0)
+}
+
+/// Like [`execute_pipeline`], but runs each [`Task`] purely for its side effects and records, per
+/// task, how long it took and the process' peak RSS right after it finished, instead of
+/// rendering a report -- the data `commands::bench` needs for its results document. Report
+/// formatting (json/tsv/html) is out of scope here; a bench run only cares about reproducible
+/// timing, not the analysis output itself.
+pub fn execute_pipeline_benchmarked(
+    mut instructions: Vec<Task>,
+) -> anyhow::Result<Vec<TaskBenchResult>> {
+    let mut results = Vec::with_capacity(instructions.len());
+    let mut gb = GraphBroker::new();
+    // Benchmarking cares about reproducible timing, so it never renders progress output --
+    // pass a permanently-disabled handle down.
+    let progress = Progress::default();
+    for index in 0..instructions.len() {
+        let start = std::time::Instant::now();
+        let task_type = match &mut instructions[index] {
+            Task::Analysis(analysis) => {
+                let task_type = analysis.get_type();
+                analysis.generate_report_section(Some(&gb), Some(&progress))?;
+                task_type
+            }
+            Task::GraphStateChange {
+                graph,
+                subset,
+                exclude,
+                grouping,
+                nice,
+                reqs,
+            } => {
+                gb.change_graph_state(
+                    GraphState {
+                        graph: graph.to_string(),
+                        subset: subset.to_string(),
+                        exclude: exclude.to_string(),
+                        grouping: grouping.clone(),
+                        cache_dir: None,
+                    },
+                    &reqs,
+                    *nice,
+                    Some(&progress),
+                )?;
+                "GraphStateChange".to_string()
+            }
+            Task::OrderChange(order) => {
+                if let Some(path) = order {
+                    let entries = order_file::parse_order_entries(path)?;
+                    order_file::validate_permutation(path, &entries, &gb.group_names())?;
+                    gb.change_order(path)?;
+                }
+                "OrderChange".to_string()
+            }
+            Task::AbacusByGroupCSCChange => {
+                unimplemented!("CSC Change is not yet implemented");
+            }
+        };
+        results.push(TaskBenchResult {
+            task_type,
+            seconds: start.elapsed().as_secs_f64(),
+            peak_bytes: read_peak_rss_bytes(),
+        });
+    }
+    Ok(results)
+}
+
 #[cfg(test)]
 mod tests {
     use analysis_parameter::Grouping;
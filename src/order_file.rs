@@ -0,0 +1,181 @@
+//! Parsing and validation for the external ordering file accepted by `Task::OrderChange`
+//! (`AnalysisParameter::OrderedGrowth`'s `order` field), which names the exact sequence groups
+//! should be added to the pangenome-growth curve in -- e.g. to reproduce a specific
+//! phylogenetic or collection order instead of the random/greedy orderings `OrderedHistgrowth`
+//! otherwise generates.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum OrderFileError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("{path}, line {line}: expected \"<id>\\t<rank>\", got {content:?}")]
+    MalformedRankRow {
+        path: String,
+        line: usize,
+        content: String,
+    },
+    #[error("{path} lists id {id:?} more than once")]
+    DuplicateId { path: String, id: String },
+    #[error(
+        "{path} is not a permutation of the current grouping -- missing {missing:?}, unexpected {extra:?}"
+    )]
+    NotAPermutation {
+        path: String,
+        missing: Vec<String>,
+        extra: Vec<String>,
+    },
+}
+
+/// Reads `path` as the sequence of group identifiers a `Task::OrderChange` should install.
+///
+/// Two formats are accepted:
+/// - one identifier per line (blank lines and `#`-prefixed comments ignored), in file order; or
+/// - a TSV of `<id>\t<rank>` rows, detected by any line containing a tab, sorted by `rank`
+///   ascending -- a single leading row whose rank column doesn't parse as a number is tolerated
+///   and skipped as a header.
+pub fn parse_order_entries(path: &str) -> Result<Vec<String>, OrderFileError> {
+    let contents = fs::read_to_string(path)?;
+    let lines: Vec<(usize, &str)> = contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+
+    if lines.iter().any(|(_, line)| line.contains('\t')) {
+        let mut ranked: Vec<(usize, String)> = Vec::with_capacity(lines.len());
+        for (index, (line_no, line)) in lines.iter().enumerate() {
+            let mut fields = line.splitn(2, '\t');
+            let id = fields.next().unwrap_or_default().trim();
+            let rank = fields.next().unwrap_or_default().trim();
+            match rank.parse::<usize>() {
+                Ok(rank) => ranked.push((rank, id.to_string())),
+                Err(_) if index == 0 => continue,
+                Err(_) => {
+                    return Err(OrderFileError::MalformedRankRow {
+                        path: path.to_string(),
+                        line: *line_no,
+                        content: line.to_string(),
+                    })
+                }
+            }
+        }
+        ranked.sort_by_key(|(rank, _)| *rank);
+        Ok(ranked.into_iter().map(|(_, id)| id).collect())
+    } else {
+        Ok(lines.into_iter().map(|(_, line)| line.to_string()).collect())
+    }
+}
+
+/// Checks that `order` (as produced by [`parse_order_entries`]) is a permutation of `universe`
+/// (e.g. [`crate::graph_broker::GraphBroker::group_names`]): every id appears exactly once, and
+/// the two sets match exactly. `path` is only used to label the error.
+pub fn validate_permutation(
+    path: &str,
+    order: &[String],
+    universe: &HashSet<String>,
+) -> Result<(), OrderFileError> {
+    let mut seen = HashSet::with_capacity(order.len());
+    for id in order {
+        if !seen.insert(id.clone()) {
+            return Err(OrderFileError::DuplicateId {
+                path: path.to_string(),
+                id: id.clone(),
+            });
+        }
+    }
+
+    let mut missing: Vec<String> = universe.difference(&seen).cloned().collect();
+    let mut extra: Vec<String> = seen.difference(universe).cloned().collect();
+    if missing.is_empty() && extra.is_empty() {
+        Ok(())
+    } else {
+        missing.sort();
+        extra.sort();
+        Err(OrderFileError::NotAPermutation {
+            path: path.to_string(),
+            missing,
+            extra,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_tmp(name: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(format!("panacus_order_file_test_{name}"));
+        fs::write(&path, content).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_parse_plain_list() {
+        let path = write_tmp("plain", "# comment\nsample_b\n\nsample_a\nsample_c\n");
+        let entries = parse_order_entries(&path).unwrap();
+        assert_eq!(entries, vec!["sample_b", "sample_a", "sample_c"]);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_parse_ranked_tsv_with_header() {
+        let path = write_tmp(
+            "ranked",
+            "id\trank\nsample_a\t2\nsample_b\t0\nsample_c\t1\n",
+        );
+        let entries = parse_order_entries(&path).unwrap();
+        assert_eq!(entries, vec!["sample_b", "sample_c", "sample_a"]);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_parse_ranked_tsv_without_header() {
+        let path = write_tmp("ranked_no_header", "sample_a\t1\nsample_b\t0\n");
+        let entries = parse_order_entries(&path).unwrap();
+        assert_eq!(entries, vec!["sample_b", "sample_a"]);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_parse_malformed_rank_row_errors() {
+        let path = write_tmp("malformed", "id\trank\nsample_a\tnot-a-number\n");
+        let err = parse_order_entries(&path).unwrap_err();
+        assert!(matches!(err, OrderFileError::MalformedRankRow { .. }));
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_validate_permutation_ok() {
+        let universe: HashSet<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let order = vec!["c".to_string(), "a".to_string(), "b".to_string()];
+        assert!(validate_permutation("order.tsv", &order, &universe).is_ok());
+    }
+
+    #[test]
+    fn test_validate_permutation_detects_duplicate() {
+        let universe: HashSet<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        let order = vec!["a".to_string(), "a".to_string()];
+        let err = validate_permutation("order.tsv", &order, &universe).unwrap_err();
+        assert!(matches!(err, OrderFileError::DuplicateId { .. }));
+    }
+
+    #[test]
+    fn test_validate_permutation_detects_missing_and_extra() {
+        let universe: HashSet<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let order = vec!["a".to_string(), "d".to_string()];
+        match validate_permutation("order.tsv", &order, &universe).unwrap_err() {
+            OrderFileError::NotAPermutation { missing, extra, .. } => {
+                assert_eq!(missing, vec!["b".to_string(), "c".to_string()]);
+                assert_eq!(extra, vec!["d".to_string()]);
+            }
+            other => panic!("expected NotAPermutation, got {other:?}"),
+        }
+    }
+}
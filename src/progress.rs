@@ -0,0 +1,107 @@
+//! A throttled terminal progress reporter for long-running pipeline stages, gated behind
+//! `--progress`/`--quiet` in `run_cli` and always a no-op under `--json` (see how `run_cli`
+//! builds its [`Progress`] instance), so machine-readable output stays clean. Renders to
+//! stderr only, never stdout, since stdout may be the report itself.
+//!
+//! Genuine bytes-parsed/total reporting for GFA ingestion and items-processed/total reporting
+//! for per-group abacus construction would need hooks inside `GraphStorage::from_gfa` and
+//! `AbacusByGroup`'s builders -- both live in the `graph_broker::graph`/`graph_broker::abacus`
+//! submodules missing from this tree snapshot (see `graph_broker::cache`'s doc comment for why
+//! the rest of this tree already works around that wall). What follows reports at the
+//! granularity this tree can actually observe instead: which pipeline task or analysis is
+//! currently running (`stage`), and a bounded `current`/`total` bar (`bar`) for the few counts
+//! -- task index, parallel-analysis-batch size -- that `execute_pipeline` already has in hand.
+
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Minimum time between re-renders of the same bar, so a tight loop doesn't flood stderr.
+const THROTTLE: Duration = Duration::from_millis(100);
+
+pub struct Progress {
+    enabled: bool,
+    last_render: Mutex<Option<Instant>>,
+}
+
+impl Progress {
+    /// `enabled` should already fold in `--progress`, `--quiet`, and `--json`/`--tsv` (see
+    /// `run_cli`): true only when the user asked for progress output and it isn't
+    /// machine-readable output.
+    pub fn new(enabled: bool) -> Self {
+        Progress {
+            enabled,
+            last_render: Mutex::new(None),
+        }
+    }
+
+    fn due(&self) -> bool {
+        let mut last = self.last_render.lock().unwrap();
+        let now = Instant::now();
+        let due = last.map(|t| now.duration_since(t) >= THROTTLE).unwrap_or(true);
+        if due {
+            *last = Some(now);
+        }
+        due
+    }
+
+    /// Announces the start (or completion) of a coarse, named stage -- a pipeline task, an
+    /// analysis type, a GFA parse. Always rendered immediately, ignoring the throttle, since
+    /// stage boundaries are infrequent and a user watching for "is this stuck" wants to see
+    /// them land instantly.
+    pub fn stage(&self, label: &str) {
+        if !self.enabled {
+            return;
+        }
+        eprintln!("[panacus] {label}");
+        let _ = std::io::stderr().flush();
+    }
+
+    /// A `current`/`total` progress bar for `label`, throttled to at most one render per
+    /// [`THROTTLE`] unless `current >= total` (completion is always rendered).
+    pub fn bar(&self, label: &str, current: u64, total: u64) {
+        if !self.enabled {
+            return;
+        }
+        let finished = current >= total;
+        if !finished && !self.due() {
+            return;
+        }
+        const WIDTH: usize = 30;
+        let frac = if total == 0 { 1.0 } else { current as f64 / total as f64 };
+        let filled = ((frac * WIDTH as f64).round() as usize).min(WIDTH);
+        let bar = "#".repeat(filled) + &"-".repeat(WIDTH - filled);
+        eprint!("\r[panacus] {label} [{bar}] {current}/{total}");
+        if finished {
+            eprintln!();
+        }
+        let _ = std::io::stderr().flush();
+    }
+}
+
+impl Default for Progress {
+    /// A disabled handle, for call sites (tests, the bench harness) that don't thread a real
+    /// `--progress` decision through.
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_progress_does_not_panic() {
+        let progress = Progress::default();
+        progress.stage("parsing graph");
+        progress.bar("nodes", 1, 10);
+    }
+
+    #[test]
+    fn test_due_throttles_back_to_back_renders() {
+        let progress = Progress::new(true);
+        assert!(progress.due());
+        assert!(!progress.due());
+    }
+}
@@ -1,20 +1,128 @@
 /* standard use */
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::io::{Error, ErrorKind};
 use std::path::Path;
+use std::str::FromStr;
 
 /* external use */
+use serde::{Deserialize, Serialize};
 use strum_macros::{EnumIter, EnumString, EnumVariantNames};
 
 /* internal use */
-use crate::graph::ItemId;
+use crate::graph_broker::ItemId;
 
 // storage space for item IDs
 //pub type ItemIdSize = u64;
 pub type CountSize = u32;
 pub type GroupSize = u16;
 
+/// A `rustc-hash`-style non-cryptographic hasher (the FxHash multiply-rotate-xor construction):
+/// fast for the purely-integer keys used by the cDBG infix-equivalence and coverage tables
+/// (`abacus.rs`), where the default SipHash hasher's DoS resistance is pure overhead since none
+/// of these keys come from untrusted input. Hand-rolled rather than pulled in as a `rustc-hash`
+/// dependency, the same reasoning as the hand-rolled FNV-1a hash in `graph_broker::cache` --
+/// there is no `Cargo.toml` in this tree to declare an external crate in.
+pub struct FxHasher {
+    hash: u64,
+}
+
+const FX_SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl Default for FxHasher {
+    fn default() -> Self {
+        FxHasher { hash: 0 }
+    }
+}
+
+impl FxHasher {
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(FX_SEED);
+    }
+}
+
+impl std::hash::Hasher for FxHasher {
+    fn write(&mut self, mut bytes: &[u8]) {
+        while bytes.len() >= 8 {
+            self.add(u64::from_ne_bytes(bytes[..8].try_into().unwrap()));
+            bytes = &bytes[8..];
+        }
+        if !bytes.is_empty() {
+            let mut buf = [0u8; 8];
+            buf[..bytes.len()].copy_from_slice(bytes);
+            self.add(u64::from_ne_bytes(buf));
+        }
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// Drop-in replacement for `std::collections::HashMap` keyed on the purely-integer keys used by
+/// the cDBG infix-equivalence and coverage tables (`abacus.rs`). Iteration order differs from
+/// `HashMap`'s, which is fine: every entry in those tables contributes to the final count
+/// independently of the others.
+pub type FxHashMap<K, V> = HashMap<K, V, std::hash::BuildHasherDefault<FxHasher>>;
+
+/// Drop-in replacement for `std::collections::HashSet`, same hasher and same rationale as
+/// [`FxHashMap`]; used for item-id membership tests (e.g. the non-countable item classification
+/// in `abacus.rs`) where only presence, not an associated value, is needed.
+pub type FxHashSet<T> = HashSet<T, std::hash::BuildHasherDefault<FxHasher>>;
+
+/// A minimal, hand-rolled stand-in for `dashmap::DashMap`: keys are routed by hash into one of a
+/// fixed number of independently-locked shards, so concurrent inserts from different rayon
+/// workers only contend when two keys land in the same shard, instead of all serializing behind
+/// one global `Mutex`. There is no `Cargo.toml` in this tree to pull in the real `dashmap` crate,
+/// the same constraint that motivated the hand-rolled `FxHasher` above.
+pub struct ShardedMap<K, V> {
+    shards: Vec<std::sync::Mutex<FxHashMap<K, V>>>,
+}
+
+impl<K: Eq + std::hash::Hash, V> ShardedMap<K, V> {
+    /// Builds a map with `shard_count` shards (at least 1); callers typically size this to the
+    /// rayon thread pool so concurrent writers rarely contend for the same shard.
+    pub fn new(shard_count: usize) -> Self {
+        let shard_count = shard_count.max(1);
+        ShardedMap {
+            shards: (0..shard_count)
+                .map(|_| std::sync::Mutex::new(FxHashMap::default()))
+                .collect(),
+        }
+    }
+
+    fn shard_index(&self, key: &K) -> usize {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = FxHasher::default();
+        key.hash(&mut hasher);
+        hasher.finish() as usize % self.shards.len()
+    }
+
+    /// Inserts `(key, value)`, locking only the one shard `key` hashes into.
+    pub fn insert(&self, key: K, value: V) {
+        let idx = self.shard_index(&key);
+        self.shards[idx].lock().unwrap().insert(key, value);
+    }
+
+    /// Consumes the map, merging all shards into a single `FxHashMap`.
+    pub fn into_inner(self) -> FxHashMap<K, V> {
+        let mut merged = FxHashMap::default();
+        for shard in self.shards {
+            merged.extend(shard.into_inner().unwrap());
+        }
+        merged
+    }
+}
+
 pub const SIZE_T: usize = 2048;
 pub struct Wrap<T>(pub *mut T);
 unsafe impl Sync for Wrap<Vec<usize>> {}
@@ -25,19 +133,115 @@ unsafe impl Sync for Wrap<[Vec<u32>; SIZE_T]> {}
 unsafe impl Sync for Wrap<Vec<Vec<u32>>> {}
 unsafe impl Sync for Wrap<[Vec<u64>; SIZE_T]> {}
 unsafe impl Sync for Wrap<Vec<Vec<u64>>> {}
-unsafe impl Sync for Wrap<[HashMap<u64, InfixEqStorage>; SIZE_T]> {}
+unsafe impl Sync for Wrap<[FxHashMap<u64, InfixEqStorage>; SIZE_T]> {}
 
 pub fn path_basename(string: &str) -> &str {
     Path::new(string).file_name().expect(&format!("Error basename in {}", string)).to_str().unwrap()
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, EnumString, EnumVariantNames, EnumIter)]
+/// A tiny, hand-rolled regex-lite matcher for `Grouping::Pattern` and pattern-based
+/// subset/exclude expressions. Supports literal characters, `.` (any char), `\d`/`\w`
+/// character classes, and `*`/`+` quantifiers on the immediately preceding atom, matched
+/// against the *entire* input (implicitly anchored at both ends, as path names have no
+/// meaningful "partial match"). There is no `Cargo.toml` in this tree to declare a `regex`
+/// (or `fst`) dependency in, so this hand-rolls just the subset of syntax PanSN-style path
+/// names (e.g. `sample\d+#1#.*`) actually need, the same reasoning as the hand-rolled
+/// `FxHasher` above and the FNV-1a hash in `graph_broker::cache`.
+pub fn pattern_match(pattern: &str, text: &str) -> bool {
+    let pat: Vec<char> = pattern.chars().collect();
+    let txt: Vec<char> = text.chars().collect();
+    match_here(&pat, &txt)
+}
+
+fn atom_len(pat: &[char]) -> usize {
+    if pat.is_empty() {
+        0
+    } else if pat[0] == '\\' && pat.len() > 1 {
+        2
+    } else {
+        1
+    }
+}
+
+fn atom_matches(pat: &[char], c: char) -> bool {
+    if pat[0] == '\\' && pat.len() > 1 {
+        match pat[1] {
+            'd' => c.is_ascii_digit(),
+            'w' => c.is_alphanumeric() || c == '_',
+            other => c == other,
+        }
+    } else if pat[0] == '.' {
+        true
+    } else {
+        c == pat[0]
+    }
+}
+
+fn match_here(pat: &[char], txt: &[char]) -> bool {
+    if pat.is_empty() {
+        return txt.is_empty();
+    }
+    let alen = atom_len(pat);
+    let quantifier = pat.get(alen).copied();
+    match quantifier {
+        Some('*') => match_quantified(&pat[alen..][1..], &pat[..alen], txt, 0),
+        Some('+') => {
+            if txt.is_empty() || !atom_matches(&pat[..alen], txt[0]) {
+                false
+            } else {
+                match_quantified(&pat[alen..][1..], &pat[..alen], &txt[1..], 0)
+            }
+        }
+        _ => {
+            if txt.is_empty() {
+                false
+            } else if atom_matches(&pat[..alen], txt[0]) {
+                match_here(&pat[alen..], &txt[1..])
+            } else {
+                false
+            }
+        }
+    }
+}
+
+/// Greedily consume as many repetitions of `atom` from `txt` as possible, then backtrack
+/// (shrinking the run one character at a time) until the rest of `pat` matches the remainder.
+fn match_quantified(rest: &[char], atom: &[char], txt: &[char], min_consumed: usize) -> bool {
+    let mut consumed = 0;
+    while consumed < txt.len() && atom_matches(atom, txt[consumed]) {
+        consumed += 1;
+    }
+    loop {
+        if consumed < min_consumed {
+            return false;
+        }
+        if match_here(rest, &txt[consumed..]) {
+            return true;
+        }
+        if consumed == 0 {
+            return false;
+        }
+        consumed -= 1;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, EnumVariantNames, EnumIter)]
 #[strum(serialize_all = "lowercase")]
 pub enum CountType {
     Node,
     Bp,
     Edge,
     All,
+    // the k itself isn't part of the enum (strum's `EnumString`/`VARIANTS` only round-trip
+    // unit variants through the CLI parser), so it travels alongside as a separate parameter,
+    // the same way `ClusterMethod`/`SimilarityMetric` are threaded independently of `CountType`
+    Kmer,
+    // like `Kmer`, the window (k, w) pair travels alongside as separate parameters rather than
+    // as enum data; see `minimizers` for the sketching this mode selects
+    Minimizer,
+    // per-junction coverage: reports each alternative out-edge of a branching node side by side,
+    // reusing the same edge-indexed r/c/v layout as `Edge` (see `AbacusByGroup::to_tsv`)
+    Branch,
 }
 
 impl fmt::Display for CountType {
@@ -50,11 +254,196 @@ impl fmt::Display for CountType {
                 CountType::Edge => "edge",
                 CountType::Bp => "bp",
                 CountType::All => "all",
+                CountType::Kmer => "kmer",
+                CountType::Minimizer => "minimizer",
+                CountType::Branch => "branch",
             }
         )
     }
 }
 
+// resolution strategy for deciding whether a countable (node/edge/bp) "intersects" a subset or
+// exclude BED interval it straddles, modeled on HTSeq's feature-counting overlap modes; `Union`
+// matches the long-standing behavior of `parse_bed_to_path_segments`'s region filter
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    EnumString,
+    EnumVariantNames,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum OverlapMode {
+    Union,
+    IntersectionStrict,
+    IntersectionNonempty,
+}
+
+impl Default for OverlapMode {
+    fn default() -> Self {
+        OverlapMode::Union
+    }
+}
+
+impl fmt::Display for OverlapMode {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{}",
+            match self {
+                OverlapMode::Union => "union",
+                OverlapMode::IntersectionStrict => "intersection-strict",
+                OverlapMode::IntersectionNonempty => "intersection-nonempty",
+            }
+        )
+    }
+}
+
+// which of the line-oriented region-file formats `io::sniff_region_format` detected; BED and
+// GFF3/GTF encode the same (seqid, start, end, strand) information in different column layouts,
+// so `io::parse_bed_to_path_segments` and `io::parse_gff_to_path_segments` each take one variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionFileFormat {
+    Bed,
+    Gff,
+}
+
+// shared by `io::parse_bed_to_path_segments` and `io::parse_gff_to_path_segments`: does
+// `[start, end)` on `name` intersect one of `regions`, per `overlap_mode`? `regions: None` keeps
+// every record (no region filter configured)
+pub fn region_overlaps(
+    name: &str,
+    start: usize,
+    end: usize,
+    regions: Option<&[(String, usize, usize)]>,
+    overlap_mode: OverlapMode,
+) -> bool {
+    let regions = match regions {
+        None => return true,
+        Some(regions) => regions,
+    };
+    match overlap_mode {
+        OverlapMode::Union => regions
+            .iter()
+            .any(|(n, s, e)| n == name && start < *e && *s < end),
+        OverlapMode::IntersectionStrict => regions
+            .iter()
+            .any(|(n, s, e)| n == name && *s <= start && end <= *e),
+        OverlapMode::IntersectionNonempty => {
+            regions
+                .iter()
+                .filter(|(n, s, e)| n == name && start < *e && *s < end)
+                .count()
+                == 1
+        }
+    }
+}
+
+pub enum RequireThreshold {
+    Absolute,
+    Relative,
+    Either,
+}
+
+pub fn parse_threshold_cli(
+    threshold_str: &str,
+    require: RequireThreshold,
+) -> Result<Vec<Threshold>, Error> {
+    threshold_str
+        .split(',')
+        .enumerate()
+        .map(|(i, el)| parse_threshold_element(threshold_str, i, el.trim(), &require))
+        .collect()
+}
+
+// parses a single comma-separated element, so `RequireThreshold::Either` can mix absolute
+// counts with relative fractions in the same list (e.g. "5,0.5,10"); a trailing '%' is
+// accepted in any mode and is always read as a relative fraction (e.g. "50%" -> Relative(0.5))
+fn parse_threshold_element(
+    threshold_str: &str,
+    i: usize,
+    el: &str,
+    require: &RequireThreshold,
+) -> Result<Threshold, Error> {
+    if let Some(pct) = el.strip_suffix('%') {
+        let pct_val = f64::from_str(pct.trim()).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "percentage threshold \"{}\" ({}. element in list) is required to be a number, but isn't.",
+                    &threshold_str,
+                    i + 1
+                ),
+            )
+        })?;
+        return if (0.0..=100.0).contains(&pct_val) {
+            Ok(Threshold::Relative(pct_val / 100.0))
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "percentage threshold \"{}\" ({}. element in list) must be within [0,100].",
+                    &threshold_str,
+                    i + 1
+                ),
+            ))
+        };
+    }
+
+    let rel_val = match f64::from_str(el) {
+        Ok(t) => {
+            if (0.0..=1.0).contains(&t) {
+                Ok(t)
+            } else {
+                Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "relative threshold \"{}\" ({}. element in list) must be within [0,1].",
+                        &threshold_str,
+                        i + 1
+                    ),
+                ))
+            }
+        }
+        Err(_) => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "threshold \"{}\" ({}. element in list) is required to be float, but isn't.",
+                &threshold_str,
+                i + 1
+            ),
+        )),
+    };
+
+    Ok(match require {
+        RequireThreshold::Absolute => Threshold::Absolute(usize::from_str(el).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "threshold \"{}\" ({}. element in list) is required to be integer, but isn't.",
+                    &threshold_str,
+                    i + 1
+                ),
+            )
+        })?),
+        RequireThreshold::Relative => Threshold::Relative(rel_val?),
+        RequireThreshold::Either => {
+            if let Ok(t) = usize::from_str(el) {
+                Threshold::Absolute(t)
+            } else {
+                Threshold::Relative(rel_val?)
+            }
+        }
+    })
+}
+
 #[derive(Debug)]
 pub struct ItemTable {
     pub items: [Vec<ItemId>; SIZE_T],
@@ -171,6 +560,275 @@ impl ActiveTable {
     pub fn with_annotation(&self) -> bool {
         self.annotation.is_some()
     }
+
+    /// Merges another table of the same size into `self`: bits are combined with the same OR
+    /// semantics as [`ActiveTable::activate`] (so merge order never matters), and any annotation
+    /// intervals are folded in via [`IntervalContainer::merge_from`]. Used to combine per-worker
+    /// local tables computed in parallel back into the shared one.
+    pub fn merge_from(&mut self, other: ActiveTable) {
+        for (id, active) in other.items.into_iter().enumerate() {
+            if active {
+                self.items[id] = true;
+            }
+        }
+        if let Some(other_annotation) = other.annotation {
+            if let Some(annotation) = self.annotation.as_mut() {
+                annotation.merge_from(other_annotation);
+            }
+        }
+    }
+}
+
+/// Merges `(start, end)` into `intervals`, which must already be sorted, non-overlapping and
+/// non-adjacent (the invariant every [`IntervalContainer`] entry maintains). Finds the *entire*
+/// run of existing intervals the new one touches, overlaps, or bridges — not just its immediate
+/// neighbor — and collapses that whole run into one, so an interval spanning several existing
+/// ones (or bridging two that were previously separate) coalesces correctly in a single pass.
+pub fn merge_interval(intervals: &mut Vec<(usize, usize)>, start: usize, end: usize) {
+    // first interval that could touch/overlap/bridge the new one from the low side
+    let lo = intervals.partition_point(|&(_, e)| e < start);
+    // one past the last interval that could touch/overlap/bridge it from the high side
+    let hi = intervals.partition_point(|&(s, _)| s <= end);
+    let merged_start = intervals[lo..hi]
+        .iter()
+        .map(|&(s, _)| s)
+        .chain([start])
+        .min()
+        .unwrap();
+    let merged_end = intervals[lo..hi]
+        .iter()
+        .map(|&(_, e)| e)
+        .chain([end])
+        .max()
+        .unwrap();
+    intervals.splice(lo..hi, [(merged_start, merged_end)]);
+}
+
+/// Intervals present in both `a` and `b` (each sorted/non-overlapping), via a merge-style
+/// two-pointer sweep.
+fn intersect_intervals(a: &[(usize, usize)], b: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let (s1, e1) = a[i];
+        let (s2, e2) = b[j];
+        let start = s1.max(s2);
+        let end = e1.min(e2);
+        if start < end {
+            result.push((start, end));
+        }
+        if e1 < e2 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    result
+}
+
+/// Intervals in `a` with any part overlapping `b` removed.
+fn subtract_intervals(a: &[(usize, usize)], b: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut result = Vec::new();
+    let mut j = 0;
+    for &(start, end) in a {
+        let mut cursor = start;
+        while j < b.len() && b[j].1 <= cursor {
+            j += 1;
+        }
+        let mut k = j;
+        while k < b.len() && b[k].0 < end {
+            if b[k].0 > cursor {
+                result.push((cursor, b[k].0));
+            }
+            cursor = cursor.max(b[k].1);
+            k += 1;
+        }
+        if cursor < end {
+            result.push((cursor, end));
+        }
+    }
+    result
+}
+
+/// A static, augmented interval tree supporting "stabbing" queries: given a range, return every
+/// stored interval overlapping it, in `O(log n + k)`. Unlike the plain `[(usize, usize)]` slices
+/// the rest of this module works with, it does not require its input to be sorted or
+/// non-overlapping -- built once per path from that path's include/exclude coordinate list, so
+/// overlapping or out-of-order BED regions are handled correctly instead of silently assumed away.
+#[derive(Debug, Clone, Default)]
+pub struct IntervalTree {
+    nodes: Vec<IntervalTreeNode>,
+    root: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct IntervalTreeNode {
+    start: usize,
+    end: usize,
+    // the largest `end` anywhere in this node's subtree, used to prune branches that cannot
+    // possibly contain an overlap with the query range
+    max_end: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl IntervalTree {
+    /// Builds a balanced tree by sorting `intervals` by start and recursively splitting at the
+    /// midpoint, augmenting each node with its subtree's maximum end on the way back up.
+    pub fn build(intervals: &[(usize, usize)]) -> Self {
+        let mut sorted = intervals.to_vec();
+        sorted.sort_unstable_by_key(|&(start, _)| start);
+        let mut nodes = Vec::with_capacity(sorted.len());
+        let root = Self::build_range(&sorted, &mut nodes);
+        IntervalTree { nodes, root }
+    }
+
+    fn build_range(sorted: &[(usize, usize)], nodes: &mut Vec<IntervalTreeNode>) -> Option<usize> {
+        if sorted.is_empty() {
+            return None;
+        }
+        let mid = sorted.len() / 2;
+        let (start, end) = sorted[mid];
+        let idx = nodes.len();
+        nodes.push(IntervalTreeNode {
+            start,
+            end,
+            max_end: end,
+            left: None,
+            right: None,
+        });
+        let left = Self::build_range(&sorted[..mid], nodes);
+        let right = Self::build_range(&sorted[mid + 1..], nodes);
+        let mut max_end = end;
+        if let Some(l) = left {
+            max_end = max_end.max(nodes[l].max_end);
+        }
+        if let Some(r) = right {
+            max_end = max_end.max(nodes[r].max_end);
+        }
+        nodes[idx].left = left;
+        nodes[idx].right = right;
+        nodes[idx].max_end = max_end;
+        Some(idx)
+    }
+
+    /// Every stored interval overlapping `[p, q)`, in no particular order.
+    pub fn overlaps(&self, p: usize, q: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let mut hits = Vec::new();
+        if let Some(root) = self.root {
+            self.collect_overlaps(root, p, q, &mut hits);
+        }
+        hits.into_iter()
+    }
+
+    fn collect_overlaps(&self, idx: usize, p: usize, q: usize, hits: &mut Vec<(usize, usize)>) {
+        let node = &self.nodes[idx];
+        // nothing in this subtree ends after the query starts, so nothing here can overlap
+        if node.max_end <= p {
+            return;
+        }
+        if let Some(left) = node.left {
+            self.collect_overlaps(left, p, q, hits);
+        }
+        if node.start < q && node.end > p {
+            hits.push((node.start, node.end));
+        }
+        // every interval in the right subtree starts at or after this node's start; if that's
+        // already past the query end, none of them can overlap either
+        if node.start < q {
+            if let Some(right) = node.right {
+                self.collect_overlaps(right, p, q, hits);
+            }
+        }
+    }
+
+    /// The largest `end` stored in the tree, i.e. the point past which no further query can
+    /// possibly find an overlap.
+    pub fn max_end(&self) -> Option<usize> {
+        self.root.map(|root| self.nodes[root].max_end)
+    }
+}
+
+/// A sorted-array interval index answering "does anything overlap `[p, q)`?" via binary search,
+/// for callers that only need a yes/no overlap check rather than [`IntervalTree`]'s full list of
+/// overlapping intervals (e.g. `update_tables_edgecount`'s include/exclude coordinate checks).
+///
+/// `update_tables_edgecount`'s original two-cursor walk over raw `include_coords`/`exclude_coords`
+/// slices is only correct when those slices are sorted by start and pairwise non-overlapping --
+/// the cursor only ever moves forward. [`IntervalIndex::is_disjoint_sorted`] tells a caller
+/// whether that cheap assumption holds; when it doesn't, building an index here and querying it
+/// with [`IntervalIndex::overlaps_any`] stays correct regardless of ordering or overlaps, at the
+/// cost of an upfront sort.
+pub struct IntervalIndex {
+    /// Intervals sorted by start.
+    sorted: Vec<(usize, usize)>,
+    /// running_max_end[i] == max(end) over sorted[..=i], so the first index whose end can
+    /// possibly exceed a query point `p` is found via binary search rather than a linear scan.
+    running_max_end: Vec<usize>,
+}
+
+impl IntervalIndex {
+    pub fn build(coords: &[(usize, usize)]) -> Self {
+        let mut sorted = coords.to_vec();
+        sorted.sort_unstable_by_key(|&(start, _)| start);
+        let mut running_max_end = Vec::with_capacity(sorted.len());
+        let mut max_end = 0;
+        for &(_, end) in &sorted {
+            max_end = max_end.max(end);
+            running_max_end.push(max_end);
+        }
+        IntervalIndex {
+            sorted,
+            running_max_end,
+        }
+    }
+
+    /// True if `coords` is already sorted by start and pairwise non-overlapping (abutting is
+    /// fine), i.e. a simple forward-only cursor walk over it is safe.
+    pub fn is_disjoint_sorted(coords: &[(usize, usize)]) -> bool {
+        coords.windows(2).all(|w| w[0].1 <= w[1].0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.sorted.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sorted.is_empty()
+    }
+
+    /// Index of the first interval that could possibly still overlap a query starting at `p`,
+    /// i.e. the first one whose running max-end exceeds `p`; `len()` if none remain.
+    pub fn first_overlap_from(&self, p: usize) -> usize {
+        self.running_max_end.partition_point(|&max_end| max_end <= p)
+    }
+
+    /// True if any stored interval overlaps `[p, q)`.
+    pub fn overlaps_any(&self, p: usize, q: usize) -> bool {
+        let start = self.first_overlap_from(p);
+        self.sorted[start..]
+            .iter()
+            .take_while(|&&(s, _)| s < q)
+            .any(|&(s, e)| s < q && e > p)
+    }
+}
+
+/// The parts of `[lo, hi)` not covered by any interval in `a` (sorted/non-overlapping).
+fn complement_intervals(a: &[(usize, usize)], lo: usize, hi: usize) -> Vec<(usize, usize)> {
+    let mut result = Vec::new();
+    let mut cur = lo;
+    for &(s, e) in a {
+        let s = s.max(lo);
+        let e = e.min(hi);
+        if s > cur {
+            result.push((cur, s));
+        }
+        cur = cur.max(e);
+    }
+    if cur < hi {
+        result.push((cur, hi));
+    }
+    result
 }
 
 #[derive(Debug, Clone)]
@@ -186,28 +844,7 @@ impl IntervalContainer {
     }
 
     pub fn add(&mut self, id: ItemId, start: usize, end: usize) {
-        // produce union of intervals
-        self.map
-            .entry(id)
-            .and_modify(|x| {
-	               let i = x
-                    .binary_search_by_key(&start, |&(y, _)| y)
-                    .unwrap_or_else(|z| z);
-                if i > 0 && x[i - 1].1 >= start {
-                    if x[i - 1].1 <= end {
-                        x[i - 1].1 = end;
-                    }
-                    // else do nothing, because the new interval is fully enclosed in the previous
-                    // interval
-                } else if i < x.len() && x[i].1 >= start && x[i].1 < end {
-                    x[i].1 = end;
-                } else if i < x.len() && x[i].0 <= end {
-                    x[i].0 = start;
-                } else {
-                    x.insert(i, (start, end));
-                }
-            })
-            .or_insert(vec![(start, end)]);
+        merge_interval(self.map.entry(id).or_default(), start, end);
     }
 
     pub fn get(&self, id: ItemId) -> Option<&[(usize, usize)]> {
@@ -222,41 +859,47 @@ impl IntervalContainer {
         self.map.remove(&id)
     }
 
+    /// The union of `id`'s stored intervals with `other`, without mutating the container.
+    #[allow(dead_code)]
+    pub fn union(&self, id: ItemId, other: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        let mut result = self.get(id).map(|v| v.to_vec()).unwrap_or_default();
+        for &(start, end) in other {
+            merge_interval(&mut result, start, end);
+        }
+        result
+    }
+
+    /// The parts of `id`'s stored intervals also covered by `other`.
+    #[allow(dead_code)]
+    pub fn intersection(&self, id: ItemId, other: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        intersect_intervals(self.get(id).unwrap_or(&[]), other)
+    }
+
+    /// The parts of `id`'s stored intervals not covered by `other`.
+    pub fn subtract(&self, id: ItemId, other: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        subtract_intervals(self.get(id).unwrap_or(&[]), other)
+    }
+
+    /// The parts of `[0, item_len)` not covered by any of `id`'s stored intervals.
+    #[allow(dead_code)]
+    pub fn complement_within(&self, id: ItemId, item_len: usize) -> Vec<(usize, usize)> {
+        complement_intervals(self.get(id).unwrap_or(&[]), 0, item_len)
+    }
+
+    /// Every `add()` above already runs through [`merge_interval`]'s sorted-list sweep, so by the
+    /// time this is called `self.map[id]` is already the fully merged set of non-overlapping,
+    /// non-touching sub-ranges accumulated across every path and every include interval that
+    /// touched this node -- summing `(e - s)` over that set, as below, is exact coverage, not an
+    /// upper bound; overlapping BED records covering the same node never get double-counted. A
+    /// node is fully covered by the subset iff this equals the node's length.
     pub fn total_coverage(&self, id: ItemId, exclude: &Option<Vec<(usize, usize)>>) -> usize {
-        self.map
-            .get(&id)
-            .as_ref()
-            .map(|v| match exclude {
-                None => v.iter().fold(0, |x, (a, b)| x + b - a),
-                Some(ex) => {
-                    let mut res = 0;
-                    let mut i = 0;
-                    for (start, end) in v.iter() {
-                        // intervals have exclusive right bound, so "<=" is the right choice here
-                        while i < ex.len() && &ex[i].1 <= start {
-                            i += 1;
-                        }
-                        if i < ex.len() && &ex[i].0 < end {
-                            // interval that starts with node start and ends with exclude start or
-                            // node end, whichever comes first
-                            //
-                            // mind the (include, exclude] character of intervals!
-                            res += usize::min(ex[i].0 - 1, *end) - start;
-
-                            // interval that starts with exclude end and ends with node end
-                            //
-                            // mind the [include, exclude) character of intervals!
-                            if &ex[i].1 < end {
-                                res += end - ex[i].1 + 1;
-                            }
-                        } else {
-                            res += end - start;
-                        }
-                    }
-                    res
-                }
-            })
-            .unwrap_or(0)
+        match self.map.get(&id) {
+            None => 0,
+            Some(v) => match exclude {
+                None => v.iter().map(|&(s, e)| e - s).sum(),
+                Some(ex) => subtract_intervals(v, ex).iter().map(|&(s, e)| e - s).sum(),
+            },
+        }
     }
 
     #[allow(dead_code)]
@@ -267,6 +910,93 @@ impl IntervalContainer {
     pub fn keys(&self) -> impl Iterator<Item = &ItemId> + '_ {
         self.map.keys()
     }
+
+    /// Folds every interval stored in `other` into `self`, id by id, via [`IntervalContainer::add`].
+    /// Used to combine per-worker-local containers (e.g. one `subset_covered_bps` per path,
+    /// computed in parallel) back into a single shared one; the result does not depend on the
+    /// order containers are merged in.
+    pub fn merge_from(&mut self, other: IntervalContainer) {
+        for (id, intervals) in other.map {
+            for (start, end) in intervals {
+                self.add(id, start, end);
+            }
+        }
+    }
+}
+
+/// Per-node depth profile: how many paths cover each base position of a node, across every
+/// path that contributes to it. Unlike [`IntervalContainer`], which only needs to know the
+/// *union* of covered sub-ranges, a depth profile needs every path's contribution kept
+/// separately -- two paths both covering `[0, 10)` make every one of those bases twice as
+/// deep, not just "covered". Storing that as a dense per-base array per node would be wasteful
+/// for large graphs, so each `add` instead pushes a `+1`/`-1` pair onto a sparse, per-node
+/// run-length / interval-count structure (a difference array); [`DepthTable::per_base_depths`]
+/// turns it into the dense array on demand by sorting the events and running a prefix sum.
+#[derive(Debug, Clone, Default)]
+pub struct DepthTable {
+    events: HashMap<ItemId, Vec<(usize, i32)>>,
+}
+
+impl DepthTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that one path covers node-local range `[start, end)`.
+    pub fn add(&mut self, id: ItemId, start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+        let events = self.events.entry(id).or_default();
+        events.push((start, 1));
+        events.push((end, -1));
+    }
+
+    pub fn contains(&self, id: ItemId) -> bool {
+        self.events.contains_key(&id)
+    }
+
+    /// The depth of every one of node `id`'s `len` bases, in position order. Nodes never
+    /// recorded via `add` are treated as entirely uncovered (all zeros).
+    pub fn per_base_depths(&self, id: ItemId, len: usize) -> Vec<u32> {
+        let mut depths = vec![0u32; len];
+        if let Some(events) = self.events.get(&id) {
+            let mut sorted = events.clone();
+            sorted.sort_unstable_by_key(|&(pos, _)| pos);
+            let mut depth = 0i32;
+            let mut pos = 0usize;
+            for (event_pos, delta) in sorted {
+                let event_pos = event_pos.min(len);
+                if event_pos > pos {
+                    depths[pos..event_pos].fill(depth.max(0) as u32);
+                }
+                pos = event_pos;
+                depth += delta;
+            }
+        }
+        depths
+    }
+
+    /// Mean and trimmed-mean depth of node `id` (of length `len`), discarding the lowest and
+    /// highest `trim_fraction` of per-base depth values from the trimmed mean before averaging
+    /// the remainder -- the same outlier-resistant estimator contig coverage tools use to keep
+    /// a handful of spuriously-deep or spuriously-shallow bases from skewing a node's reported
+    /// coverage. `trim_fraction` is clamped so at least one value always survives the trim.
+    pub fn mean_and_trimmed_mean(&self, id: ItemId, len: usize, trim_fraction: f64) -> (f64, f64) {
+        if len == 0 {
+            return (0.0, 0.0);
+        }
+        let mut depths = self.per_base_depths(id, len);
+        let mean = depths.iter().map(|&d| d as f64).sum::<f64>() / depths.len() as f64;
+
+        depths.sort_unstable();
+        let trim = ((trim_fraction.clamp(0.0, 0.5) * depths.len() as f64).floor() as usize)
+            .min((depths.len().saturating_sub(1)) / 2);
+        let trimmed = &depths[trim..depths.len() - trim];
+        let trimmed_mean = trimmed.iter().map(|&d| d as f64).sum::<f64>() / trimmed.len() as f64;
+
+        (mean, trimmed_mean)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -288,6 +1018,10 @@ impl fmt::Display for ActiveTableError {
 pub enum Threshold {
     Relative(f64),
     Absolute(usize),
+    // a fraction of group_sizes' own distribution rather than of a single scalar n, so it needs
+    // `resolve` (or an externally supplied group_sizes) to become an absolute cutoff; see
+    // `to_absolute`/`to_relative`, which fall back to treating `n` as the group count
+    Quantile(f64),
 }
 
 impl fmt::Display for Threshold {
@@ -295,6 +1029,7 @@ impl fmt::Display for Threshold {
         match self {
             Threshold::Relative(c) => write!(formatter, "{}R", c)?,
             Threshold::Absolute(c) => write!(formatter, "{}A", c)?,
+            Threshold::Quantile(c) => write!(formatter, "{}Q", c)?,
         }
         Ok(())
     }
@@ -305,6 +1040,7 @@ impl Threshold {
         match self {
             Threshold::Relative(c) => format!("{}", c),
             Threshold::Absolute(c) => format!("{}", c),
+            Threshold::Quantile(c) => format!("{}", c),
         }
     }
 
@@ -312,6 +1048,7 @@ impl Threshold {
         match self {
             Threshold::Absolute(c) => *c,
             Threshold::Relative(c) => (n as f64 * c).ceil() as usize,
+            Threshold::Quantile(c) => (n as f64 * c).ceil() as usize,
         }
     }
 
@@ -319,6 +1056,27 @@ impl Threshold {
         match self {
             Threshold::Relative(c) => *c,
             Threshold::Absolute(c) => *c as f64 / n as f64,
+            Threshold::Quantile(c) => *c,
+        }
+    }
+
+    /// Resolves this threshold against an actual distribution of per-group sizes rather than a
+    /// single scalar `n`. `Quantile(q)` picks the size at the `q`-th quantile of
+    /// `group_sizes` (sorted ascending, so `Quantile(0.9)` means "as large as the 90th
+    /// percentile group"); `Relative`/`Absolute` fall back to `to_absolute` against the group
+    /// count, matching their existing scalar-`n` semantics.
+    pub fn resolve(&self, group_sizes: &[usize]) -> usize {
+        match self {
+            Threshold::Quantile(q) => {
+                if group_sizes.is_empty() {
+                    return 0;
+                }
+                let mut sorted = group_sizes.to_vec();
+                sorted.sort_unstable();
+                let idx = ((sorted.len() as f64 - 1.0) * q.clamp(0.0, 1.0)).round() as usize;
+                sorted[idx]
+            }
+            Threshold::Relative(_) | Threshold::Absolute(_) => self.to_absolute(group_sizes.len()),
         }
     }
 }
@@ -329,11 +1087,15 @@ impl Threshold {
 
 pub fn intersects(v: &[(usize, usize)], el: &(usize, usize)) -> bool {
     // this code assumes that intervals of v are (i) sorted (ii) non-overlapping
+    //
+    // both v's intervals and el are half-open [start, end), consistent with is_contained and
+    // total_coverage: merely touching endpoints (e.g. an interval ending where el starts) do
+    // not count as intersecting.
 
     v.binary_search_by(|(s, e)| {
-        if s <= &el.1 && e >= &el.0 {
+        if s < &el.1 && e > &el.0 {
             Ordering::Equal
-        } else if e < &el.0 {
+        } else if e <= &el.0 {
             Ordering::Less
         } else {
             Ordering::Greater
@@ -495,9 +1257,289 @@ pub fn canonical(kmer_bits: u64, k: usize) -> u64 {
     }
 }
 
+/// Maximum k a [`kmer_u8_to_u128`]-packed k-mer (2 bits/base) can hold in 128 bits.
+pub const MAX_KMER_SIZE_U128: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KmerError {
+    KTooLarge { k: usize, max: usize },
+    InvalidNucleotide(u8),
+}
+
+impl std::error::Error for KmerError {}
+
+impl fmt::Display for KmerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KmerError::KTooLarge { k, max } => write!(
+                f,
+                "k-mer size {} exceeds the maximum of {} bases supported by this packing width",
+                k, max
+            ),
+            KmerError::InvalidNucleotide(b) => {
+                write!(f, "invalid nucleotide: {}", *b as char)
+            }
+        }
+    }
+}
+
+/// `u128` counterpart of [`kmer_u8_to_u64`], packing up to [`MAX_KMER_SIZE_U128`] bases (2
+/// bits/base) instead of u64's 32, so k=33..64 pangenome k-mers no longer need truncation.
+/// Rejects oversized k-mers with a [`KmerError`] rather than silently wrapping/truncating.
+pub fn kmer_u8_to_u128(kmer: &[u8]) -> Result<u128, KmerError> {
+    if kmer.len() > MAX_KMER_SIZE_U128 {
+        return Err(KmerError::KTooLarge {
+            k: kmer.len(),
+            max: MAX_KMER_SIZE_U128,
+        });
+    }
+    let mut result: u128 = 0;
+    for &nucleotide in kmer {
+        let bits = NUCLEOTIDE_BITS[nucleotide as usize];
+        if bits >= 4 {
+            return Err(KmerError::InvalidNucleotide(nucleotide));
+        }
+        result = (result << 2) | bits as u128;
+    }
+    Ok(result)
+}
+
+/// `u128` counterpart of [`revcmp`]: reverse-complements the 16 constituent bytes via the same
+/// [`LOOKUP_RC`] byte table (bit-complement + within-byte base reversal), reassembles them in
+/// reverse order, then shifts the result down so the `k` packed bases are right-aligned.
+pub fn revcmp128(kmer: u128, k: usize) -> Result<u128, KmerError> {
+    if k > MAX_KMER_SIZE_U128 {
+        return Err(KmerError::KTooLarge {
+            k,
+            max: MAX_KMER_SIZE_U128,
+        });
+    }
+    let mut result: u128 = 0;
+    for i in 0..16 {
+        let byte = ((kmer >> (8 * i)) & 0xff) as usize;
+        result |= (LOOKUP_RC[byte] as u128) << (8 * (15 - i));
+    }
+    Ok(result >> (128 - k as u32 * 2))
+}
+
+/// `u128` counterpart of [`get_infix`]: drops the k-mer's last base, keeping the leading `k - 1`
+/// bases.
+pub fn get_infix128(kmer_bits: u128, k: usize) -> u128 {
+    let mask: u128 = (1u128 << (2 * (k - 1))) - 1;
+    (kmer_bits >> 2) & mask
+}
+
+/// `u128` counterpart of [`canonical`]: the lexicographically smaller of the k-mer and its
+/// reverse complement, so strand doesn't double-count a k-mer occurring on both strands.
+pub fn canonical128(kmer_bits: u128, k: usize) -> Result<u128, KmerError> {
+    let kmer_bits_rc = revcmp128(kmer_bits, k)?;
+    Ok(kmer_bits.min(kmer_bits_rc))
+}
+
+/// Fixed invertible multiply-xor hash (splitmix64's finalizer) used to order k-mers when picking
+/// a window's minimizer. Hashing rather than comparing raw 2-bit-packed k-mers avoids biasing
+/// minimizer choice toward lexicographically small k-mers (e.g. poly-A runs).
+fn minimizer_hash(kmer_bits: u64) -> u64 {
+    let mut x = kmer_bits;
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51afd7ed558ccd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4ceb9fe1a85ec53);
+    x ^= x >> 33;
+    x
+}
+
+/// Slides a window of `w` consecutive canonical k-mers over `seq` and returns the hashed
+/// minimizer of each window, in the order the windows occur, emitting a value only when it
+/// differs from the previous window's minimizer so overlapping windows sharing the same
+/// minimizer don't produce duplicate entries. This is `CountType::Minimizer`'s sketching step:
+/// the returned ids stand in for (and are far fewer than) the path's full k-mer set, so growth
+/// and coreness curves computed over them are approximate, trading exactness for roughly a
+/// `w`-fold reduction in the item universe.
+pub fn minimizers(seq: &[u8], k: usize, w: usize) -> Vec<u64> {
+    if seq.len() < k {
+        return Vec::new();
+    }
+    let kmer_hashes: Vec<u64> = seq
+        .windows(k)
+        .map(|kmer| minimizer_hash(canonical(kmer_u8_to_u64(kmer), k)))
+        .collect();
+    if kmer_hashes.len() < w {
+        return kmer_hashes.iter().min().copied().into_iter().collect();
+    }
+    let mut result = Vec::new();
+    let mut prev = None;
+    for window in kmer_hashes.windows(w) {
+        let m = *window.iter().min().unwrap();
+        if prev != Some(m) {
+            result.push(m);
+            prev = Some(m);
+        }
+    }
+    result
+}
+
 //pub fn log2_add(a: f64, b: f64) -> f64 {
 //    // we assume both a and b are log2'd
 //    let (a, b) = if a < b { (a, b) } else { (b, a) };
 //
 //    b + (1.0 + (a - b).exp2()).log2()
 //}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_bridges_two_adjacent_intervals() {
+        let mut c = IntervalContainer::new();
+        c.add(ItemId(1), 0, 10);
+        c.add(ItemId(1), 20, 30);
+        // bridges the gap between the two existing intervals into one
+        c.add(ItemId(1), 10, 20);
+        assert_eq!(c.get(ItemId(1)), Some(&[(0, 30)][..]));
+    }
+
+    #[test]
+    fn test_add_spans_and_absorbs_multiple_existing_intervals() {
+        let mut c = IntervalContainer::new();
+        c.add(ItemId(1), 0, 5);
+        c.add(ItemId(1), 10, 15);
+        c.add(ItemId(1), 20, 25);
+        // a single interval spanning all three should collapse them into one
+        c.add(ItemId(1), 2, 22);
+        assert_eq!(c.get(ItemId(1)), Some(&[(0, 25)][..]));
+    }
+
+    #[test]
+    fn test_add_merges_touching_endpoints() {
+        let mut c = IntervalContainer::new();
+        c.add(ItemId(1), 0, 10);
+        // half-open [10, 20) is adjacent (not overlapping) to [0, 10) and should still merge
+        c.add(ItemId(1), 10, 20);
+        assert_eq!(c.get(ItemId(1)), Some(&[(0, 20)][..]));
+    }
+
+    #[test]
+    fn test_intersects_excludes_touching_endpoints() {
+        let v = [(0, 10), (20, 30)];
+        assert!(!intersects(&v, &(10, 20)));
+        assert!(intersects(&v, &(5, 15)));
+        assert!(intersects(&v, &(9, 21)));
+    }
+
+    #[test]
+    fn test_subtract_intervals_splits_around_exclusion() {
+        let a = [(0, 30)];
+        let b = [(10, 20)];
+        assert_eq!(subtract_intervals(&a, &b), vec![(0, 10), (20, 30)]);
+    }
+
+    #[test]
+    fn test_intersection_and_complement() {
+        let a = [(0, 10), (20, 30)];
+        let b = [(5, 25)];
+        assert_eq!(intersect_intervals(&a, &b), vec![(5, 10), (20, 25)]);
+        assert_eq!(complement_intervals(&a, 0, 40), vec![(10, 20), (30, 40)]);
+    }
+
+    #[test]
+    fn test_total_coverage_with_exclude() {
+        let mut c = IntervalContainer::new();
+        c.add(ItemId(1), 0, 30);
+        let exclude = Some(vec![(10, 20)]);
+        assert_eq!(c.total_coverage(ItemId(1), &exclude), 20);
+        assert_eq!(c.total_coverage(ItemId(1), &None), 30);
+    }
+
+    #[test]
+    fn test_minimizers_deduplicates_consecutive_windows() {
+        let seq = b"ACGTACGTACGTACGT";
+        let m = minimizers(seq, 4, 3);
+        // consecutive windows sharing a minimizer must not be emitted twice in a row
+        assert!(m.windows(2).all(|w| w[0] != w[1]));
+    }
+
+    #[test]
+    fn test_minimizers_short_sequence_falls_back_to_single_minimum() {
+        let seq = b"ACGT";
+        let m = minimizers(seq, 4, 3);
+        assert_eq!(m.len(), 1);
+    }
+
+    #[test]
+    fn test_minimizers_too_short_for_k_is_empty() {
+        assert!(minimizers(b"AC", 4, 3).is_empty());
+    }
+
+    #[test]
+    fn test_parse_threshold_cli_relative_success() {
+        let threshold_str = "0.2,0.5,0.9";
+        let result = parse_threshold_cli(threshold_str, RequireThreshold::Relative);
+        assert!(result.is_ok());
+        let thresholds = result.unwrap();
+        assert_eq!(thresholds.len(), 3);
+        assert_eq!(thresholds[0], Threshold::Relative(0.2));
+        assert_eq!(thresholds[1], Threshold::Relative(0.5));
+        assert_eq!(thresholds[2], Threshold::Relative(0.9));
+    }
+
+    #[test]
+    fn test_parse_threshold_cli_absolute_success() {
+        let threshold_str = "5,10,15";
+        let result = parse_threshold_cli(threshold_str, RequireThreshold::Absolute);
+        assert!(result.is_ok());
+        let thresholds = result.unwrap();
+        assert_eq!(thresholds.len(), 3);
+        assert_eq!(thresholds[0], Threshold::Absolute(5));
+        assert_eq!(thresholds[1], Threshold::Absolute(10));
+        assert_eq!(thresholds[2], Threshold::Absolute(15));
+    }
+
+    #[test]
+    fn test_parse_threshold_cli_invalid_float_in_absolute() {
+        let threshold_str = "5.5,10,15";
+        let result = parse_threshold_cli(threshold_str, RequireThreshold::Absolute);
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_parse_threshold_cli_invalid_value_in_relative() {
+        let threshold_str = "0.2,1.2,0.9"; // 1.2 is out of range for relative threshold
+        let result = parse_threshold_cli(threshold_str, RequireThreshold::Relative);
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_parse_threshold_cli_either_mixed_list() {
+        let threshold_str = "5,0.5,10";
+        let result = parse_threshold_cli(threshold_str, RequireThreshold::Either);
+        assert!(result.is_ok());
+        let thresholds = result.unwrap();
+        assert_eq!(thresholds.len(), 3);
+        assert_eq!(thresholds[0], Threshold::Absolute(5));
+        assert_eq!(thresholds[1], Threshold::Relative(0.5));
+        assert_eq!(thresholds[2], Threshold::Absolute(10));
+    }
+
+    #[test]
+    fn test_parse_threshold_cli_percentage_success() {
+        let threshold_str = "50%,90%";
+        let result = parse_threshold_cli(threshold_str, RequireThreshold::Either);
+        assert!(result.is_ok());
+        let thresholds = result.unwrap();
+        assert_eq!(thresholds.len(), 2);
+        assert_eq!(thresholds[0], Threshold::Relative(0.5));
+        assert_eq!(thresholds[1], Threshold::Relative(0.9));
+    }
+
+    #[test]
+    fn test_parse_threshold_cli_percentage_out_of_range() {
+        let threshold_str = "150%";
+        let result = parse_threshold_cli(threshold_str, RequireThreshold::Either);
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+}
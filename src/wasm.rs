@@ -0,0 +1,28 @@
+// WebAssembly bindings for the report-rendering path, so a GFA file's coverage-histogram and
+// pangenome-growth report can be rendered in-browser without installing the CLI. Only compiled
+// for `wasm32-unknown-unknown`; the native build is unaffected. The computation that produces
+// the `AnalysisSection`s (reading the GFA, building histograms/growth curves) already runs
+// through the same `Analysis` trait used natively via `report --json`, so this module only has
+// to cover the rendering step and keeps it the single source of truth for both targets.
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen::prelude::*;
+
+use crate::html_report::AnalysisSection;
+
+#[wasm_bindgen(start)]
+pub fn init_panic_hook() {
+    console_error_panic_hook::set_once();
+}
+
+/// Renders a JSON-encoded list of `AnalysisSection`s (the same format produced by
+/// `panacus report --json`) into the interactive HTML report, so a browser can render it
+/// without a server-side `panacus` process.
+#[wasm_bindgen]
+pub fn render_report(sections_json: &str, title: &str) -> Result<String, JsValue> {
+    let (sections, _digest) = AnalysisSection::load_json_str(sections_json)
+        .map_err(|e| JsValue::from_str(&format!("invalid report JSON: {e}")))?;
+    let mut registry = handlebars::Handlebars::new();
+    AnalysisSection::generate_report(sections, &mut registry, title)
+        .map_err(|e| JsValue::from_str(&format!("failed to render report: {e}")))
+}